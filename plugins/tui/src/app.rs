@@ -1,35 +1,47 @@
 //! TUI Application state and event handling
 
-use crate::task_storage::{Task, TaskStorage};
+use crate::task_storage::{format_scheduled, TaskStorage};
 use crate::ui;
-use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone};
+use chrono::{DateTime, Local};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::path::PathBuf;
+use taiga_core::{category, Locale, Priority, Query, QuickAdd, Status, Task, WorkCalendar, Zone};
+use taiga_core::date::parse_datetime;
 use taiga_plugin_api::PluginContext;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FilterMode {
     All,
     Unchecked,
+    InProgress,
+    Waiting,
     Checked,
+    Cancelled,
     Scheduled,
     Overdue,
+    /// Tasks whose start date is still ahead, hidden in every other mode
+    Deferred,
 }
 
 impl FilterMode {
     pub fn next(&self) -> Self {
         match self {
             FilterMode::All => FilterMode::Unchecked,
-            FilterMode::Unchecked => FilterMode::Checked,
-            FilterMode::Checked => FilterMode::Scheduled,
+            FilterMode::Unchecked => FilterMode::InProgress,
+            FilterMode::InProgress => FilterMode::Waiting,
+            FilterMode::Waiting => FilterMode::Checked,
+            FilterMode::Checked => FilterMode::Cancelled,
+            FilterMode::Cancelled => FilterMode::Scheduled,
             FilterMode::Scheduled => FilterMode::Overdue,
-            FilterMode::Overdue => FilterMode::All,
+            FilterMode::Overdue => FilterMode::Deferred,
+            FilterMode::Deferred => FilterMode::All,
         }
     }
 
@@ -37,9 +49,13 @@ impl FilterMode {
         match self {
             FilterMode::All => "All",
             FilterMode::Unchecked => "Incomplete",
+            FilterMode::InProgress => "In progress",
+            FilterMode::Waiting => "Waiting",
             FilterMode::Checked => "Complete",
+            FilterMode::Cancelled => "Cancelled",
             FilterMode::Scheduled => "Scheduled",
             FilterMode::Overdue => "Overdue",
+            FilterMode::Deferred => "Deferred",
         }
     }
 }
@@ -50,6 +66,7 @@ pub enum SortMode {
     Date,
     Name,
     Status,
+    Priority,
 }
 
 impl SortMode {
@@ -58,7 +75,8 @@ impl SortMode {
             SortMode::Id => SortMode::Date,
             SortMode::Date => SortMode::Name,
             SortMode::Name => SortMode::Status,
-            SortMode::Status => SortMode::Id,
+            SortMode::Status => SortMode::Priority,
+            SortMode::Priority => SortMode::Id,
         }
     }
 
@@ -68,6 +86,7 @@ impl SortMode {
             SortMode::Date => "Date",
             SortMode::Name => "Name",
             SortMode::Status => "Status",
+            SortMode::Priority => "Priority",
         }
     }
 }
@@ -83,6 +102,8 @@ pub enum DialogMode {
     MoveCategory { task_id: u32, categories: Vec<String>, selected: usize },
     AddTag { task_id: u32, input: String },
     RemoveTag { task_id: u32, tags: Vec<String>, selected: usize },
+    Checklist { task_id: u32, selected: usize },
+    OpenLink { task_id: u32, selected: usize },
 }
 
 /// Sidebar section selection
@@ -98,16 +119,24 @@ pub struct App {
     pub filter_mode: FilterMode,
     pub sort_mode: SortMode,
     pub search_query: String,
+    /// Why the search text is not a valid query; it is then searched for as typed
+    pub search_error: Option<String>,
     pub is_searching: bool,
     pub dialog: DialogMode,
     pub should_quit: bool,
     pub error_message: Option<String>,
     filtered_tasks: Vec<u32>,
+    /// Nesting depth of each visible task
+    pub task_depths: HashMap<u32, usize>,
+    /// Tasks whose subtasks are hidden
+    pub collapsed: HashSet<u32>,
     // Sidebar state
     pub categories: Vec<String>,
     pub all_tags: Vec<String>,
     pub selected_category: Option<Option<String>>, // None = "All", Some(None) = "Uncategorized", Some(Some("X")) = category X
     pub selected_tag_filter: Option<String>,
+    /// Categories whose subcategories are hidden in the sidebar
+    pub collapsed_categories: HashSet<String>,
     pub sidebar_focused: bool,
     pub sidebar_section: SidebarSection,
     pub sidebar_selection: usize,
@@ -122,15 +151,19 @@ impl App {
             filter_mode: FilterMode::All,
             sort_mode: SortMode::Id,
             search_query: String::new(),
+            search_error: None,
             is_searching: false,
             dialog: DialogMode::None,
             should_quit: false,
             error_message: None,
             filtered_tasks: Vec::new(),
+            task_depths: HashMap::new(),
+            collapsed: HashSet::new(),
             categories: Vec::new(),
             all_tags: Vec::new(),
             selected_category: None,
             selected_tag_filter: None,
+            collapsed_categories: HashSet::new(),
             sidebar_focused: false,
             sidebar_section: SidebarSection::Categories,
             sidebar_selection: 0,
@@ -138,7 +171,14 @@ impl App {
     }
 
     pub fn load_tasks(&mut self) -> Result<(), String> {
-        self.storage.load()?;
+        let skipped = self.storage.load()?;
+        if let Some(first) = skipped.first() {
+            self.error_message = Some(format!(
+                "Skipped {} unreadable line(s), saving will drop them: {}",
+                skipped.len(),
+                first
+            ));
+        }
         self.update_categories_tags();
         self.update_filtered_tasks();
         Ok(())
@@ -155,21 +195,39 @@ impl App {
     }
 
     pub fn update_filtered_tasks(&mut self) {
-        let today = Local::now().date_naive();
         let search_lower = self.search_query.to_lowercase();
+        // The search box takes a query such as "tag:work due<+3d"
+        let query = (!self.search_query.trim().is_empty()).then(|| Query::parse(&self.search_query));
+        self.search_error = query.as_ref().and_then(|parsed| parsed.as_ref().err()).map(ToString::to_string);
+        let query = query.and_then(Result::ok);
 
-        let mut tasks: Vec<&Task> = self.storage.tasks.values()
+        let mut tasks: Vec<&Task> = self.storage.collection.tasks.values()
             .filter(|task| {
                 // Apply search filter
                 if !self.search_query.is_empty() {
-                    if !task.title.to_lowercase().contains(&search_lower) {
+                    let matches = match &query {
+                        Some(query) => query.matches_with(task, |id| self.storage.is_blocked(id)),
+                        None => {
+                            let in_notes = task
+                                .notes
+                                .as_ref()
+                                .is_some_and(|notes| notes.to_lowercase().contains(&search_lower));
+                            task.title.to_lowercase().contains(&search_lower) || in_notes
+                        }
+                    };
+                    if !matches {
                         return false;
                     }
                 }
 
-                // Apply category filter
+                // Apply category filter, including subcategories
                 if let Some(ref cat_filter) = self.selected_category {
-                    if task.category.as_ref() != cat_filter.as_ref() {
+                    let matches = match (&task.category, cat_filter) {
+                        (Some(path), Some(ancestor)) => category::is_within(path, ancestor),
+                        (None, None) => true,
+                        _ => false,
+                    };
+                    if !matches {
                         return false;
                     }
                 }
@@ -181,19 +239,22 @@ impl App {
                     }
                 }
 
+                // Tasks that have not started yet only show in their own mode
+                if task.is_deferred() != (self.filter_mode == FilterMode::Deferred) {
+                    return false;
+                }
+
                 // Apply filter mode
                 match self.filter_mode {
                     FilterMode::All => true,
-                    FilterMode::Unchecked => !task.is_complete,
-                    FilterMode::Checked => task.is_complete,
+                    FilterMode::Unchecked => !task.status.is_closed(),
+                    FilterMode::InProgress => task.status == Status::InProgress,
+                    FilterMode::Waiting => task.status == Status::Waiting,
+                    FilterMode::Checked => task.status == Status::Done,
+                    FilterMode::Cancelled => task.status == Status::Cancelled,
                     FilterMode::Scheduled => task.scheduled.is_some(),
-                    FilterMode::Overdue => {
-                        if let Some(dt) = task.scheduled {
-                            dt.date_naive() < today && !task.is_complete
-                        } else {
-                            false
-                        }
-                    }
+                    FilterMode::Overdue => task.is_overdue(),
+                    FilterMode::Deferred => true,
                 }
             })
             .collect();
@@ -210,10 +271,13 @@ impl App {
                 }
             }),
             SortMode::Name => tasks.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase())),
-            SortMode::Status => tasks.sort_by_key(|t| (t.is_complete, t.id)),
+            SortMode::Status => tasks.sort_by_key(|t| (t.status, t.id)),
+            SortMode::Priority => tasks.sort_by_key(|t| (t.priority.map_or(u8::MAX, Priority::level), t.id)),
         }
 
-        self.filtered_tasks = tasks.iter().map(|t| t.id).collect();
+        let ordered = tree_order(&tasks, &self.collapsed);
+        self.filtered_tasks = ordered.iter().map(|&(id, _)| id).collect();
+        self.task_depths = ordered.into_iter().collect();
 
         // Adjust selection if needed
         if self.selected_index >= self.filtered_tasks.len() && !self.filtered_tasks.is_empty() {
@@ -223,13 +287,13 @@ impl App {
 
     pub fn get_visible_tasks(&self) -> Vec<&Task> {
         self.filtered_tasks.iter()
-            .filter_map(|id| self.storage.tasks.get(id))
+            .filter_map(|&id| self.storage.collection.get(id))
             .collect()
     }
 
     pub fn selected_task(&self) -> Option<&Task> {
         self.filtered_tasks.get(self.selected_index)
-            .and_then(|id| self.storage.tasks.get(id))
+            .and_then(|&id| self.storage.collection.get(id))
     }
 
     pub fn selected_task_id(&self) -> Option<u32> {
@@ -253,14 +317,46 @@ impl App {
 
     pub fn toggle_selected(&mut self) {
         if let Some(id) = self.selected_task_id() {
-            self.storage.toggle_task(id);
+            let next_id = self.storage.toggle_task(id);
             self.update_filtered_tasks();
             if let Err(e) = self.save_tasks() {
                 self.error_message = Some(format!("Failed to save: {}", e));
+            } else if let Some(next) = next_id.and_then(|next_id| self.storage.collection.get(next_id)) {
+                let date = next.scheduled.map(|dt| format_scheduled(&dt)).unwrap_or_default();
+                self.error_message = Some(format!("Next occurrence #{} scheduled for {}", next.id, date));
             }
         }
     }
 
+    /// Put the selected task in a state, or reopen it if it already is
+    pub fn toggle_selected_status(&mut self, status: Status) {
+        if let Some(task) = self.selected_task() {
+            let status = if task.status == status { Status::Open } else { status };
+            let id = task.id;
+            let next_id = self.storage.set_status(id, status);
+            self.update_filtered_tasks();
+            if let Err(e) = self.save_tasks() {
+                self.error_message = Some(format!("Failed to save: {}", e));
+            } else if let Some(next) = next_id.and_then(|next_id| self.storage.collection.get(next_id)) {
+                let date = next.scheduled.map(|dt| format_scheduled(&dt)).unwrap_or_default();
+                self.error_message = Some(format!("Next occurrence #{} scheduled for {}", next.id, date));
+            }
+        }
+    }
+
+    /// Collapse or expand the subtasks of the selected task
+    pub fn toggle_collapse_selected(&mut self) {
+        if let Some(id) = self.selected_task_id() {
+            if !self.storage.has_children(id) {
+                return;
+            }
+            if !self.collapsed.remove(&id) {
+                self.collapsed.insert(id);
+            }
+            self.update_filtered_tasks();
+        }
+    }
+
     pub fn delete_selected(&mut self) {
         if let Some(id) = self.selected_task_id() {
             self.storage.remove_task(id);
@@ -273,13 +369,26 @@ impl App {
     }
 
     pub fn add_task(&mut self, name: String, date_str: String) {
-        if name.trim().is_empty() {
+        // Dates, #tags, @category and !priority typed into the name are
+        // picked up; with a date in its own field date words stay in the name
+        let date = parse_date_input(&date_str);
+        let quick = if date.is_some() {
+            QuickAdd::parse_undated(&name)
+        } else {
+            QuickAdd::parse(&name)
+        };
+        if quick.title.is_empty() {
             self.error_message = Some("Task name cannot be empty".to_string());
             return;
         }
 
-        let scheduled = parse_date_input(&date_str);
-        self.storage.add_task(name, scheduled);
+        let scheduled = date.or(quick.scheduled);
+        let id = self
+            .storage
+            .add_task_with_category_tags(quick.title, scheduled, quick.category, quick.tags);
+        if let Some(task) = self.storage.collection.get_mut(id) {
+            task.priority = quick.priority;
+        }
         self.update_filtered_tasks();
 
         if let Err(e) = self.save_tasks() {
@@ -352,24 +461,24 @@ impl App {
     }
 
     pub fn task_count(&self) -> usize {
-        self.storage.tasks.len()
+        self.storage.collection.len()
     }
 
     pub fn completed_count(&self) -> usize {
-        self.storage.tasks.values().filter(|t| t.is_complete).count()
+        self.status_count(Status::Done)
+    }
+
+    pub fn status_count(&self, status: Status) -> usize {
+        self.storage.collection.count_with_status(status)
+    }
+
+    /// Count tasks removed by clearing: done and cancelled ones
+    pub fn closed_count(&self) -> usize {
+        self.storage.collection.tasks.values().filter(|t| t.status.is_closed()).count()
     }
 
     pub fn overdue_count(&self) -> usize {
-        let today = Local::now().date_naive();
-        self.storage.tasks.values()
-            .filter(|t| {
-                if let Some(dt) = t.scheduled {
-                    dt.date_naive() < today && !t.is_complete
-                } else {
-                    false
-                }
-            })
-            .count()
+        self.storage.collection.count_overdue()
     }
 
     // Sidebar navigation methods
@@ -385,7 +494,7 @@ impl App {
     /// Move sidebar selection up or down
     pub fn move_sidebar_selection(&mut self, delta: i32) {
         let max_items = match self.sidebar_section {
-            SidebarSection::Categories => self.categories.len() + 2, // +2 for "All" and "Uncategorized"
+            SidebarSection::Categories => self.visible_categories().len() + 2, // +2 for "All" and "Uncategorized"
             SidebarSection::Tags => self.all_tags.len() + 1, // +1 for "All"
         };
 
@@ -402,6 +511,41 @@ impl App {
         self.sidebar_selection = new_selection;
     }
 
+    /// Categories shown in the sidebar, leaving out those under a collapsed one
+    pub fn visible_categories(&self) -> Vec<&str> {
+        self.categories
+            .iter()
+            .map(String::as_str)
+            .filter(|cat| {
+                !self
+                    .collapsed_categories
+                    .iter()
+                    .any(|collapsed| *cat != collapsed.as_str() && category::is_within(cat, collapsed))
+            })
+            .collect()
+    }
+
+    /// Check if a category has subcategories
+    pub fn has_subcategories(&self, path: &str) -> bool {
+        self.categories
+            .iter()
+            .any(|cat| category::parent(cat) == Some(path))
+    }
+
+    /// Expand or collapse the subcategories of the selected sidebar category
+    pub fn toggle_collapse_category(&mut self) {
+        if self.sidebar_section != SidebarSection::Categories || self.sidebar_selection < 2 {
+            return;
+        }
+        let Some(cat) = self.visible_categories().get(self.sidebar_selection - 2).map(|c| c.to_string())
+        else {
+            return;
+        };
+        if !self.collapsed_categories.remove(&cat) && self.has_subcategories(&cat) {
+            self.collapsed_categories.insert(cat);
+        }
+    }
+
     /// Switch between Categories and Tags sections in sidebar
     pub fn toggle_sidebar_section(&mut self) {
         self.sidebar_section = match self.sidebar_section {
@@ -420,11 +564,8 @@ impl App {
                     self.selected_category = None;
                 } else if self.sidebar_selection == 1 {
                     self.selected_category = Some(None); // Uncategorized
-                } else {
-                    let idx = self.sidebar_selection - 2;
-                    if idx < self.categories.len() {
-                        self.selected_category = Some(Some(self.categories[idx].clone()));
-                    }
+                } else if let Some(cat) = self.visible_categories().get(self.sidebar_selection - 2) {
+                    self.selected_category = Some(Some(cat.to_string()));
                 }
                 self.selected_tag_filter = None;
             }
@@ -482,10 +623,75 @@ impl App {
         }
     }
 
-    /// Move task to category
+    /// Open checklist dialog for selected task
+    pub fn open_checklist_dialog(&mut self) {
+        if let Some(task) = self.selected_task()
+            && !task.checklist.is_empty()
+        {
+            self.dialog = DialogMode::Checklist {
+                task_id: task.id,
+                selected: 0,
+            };
+        }
+    }
+
+    /// Check or uncheck a checklist item, keeping the dialog open
+    pub fn toggle_checklist_item(&mut self, task_id: u32, index: usize) {
+        if self.storage.toggle_checklist_item(task_id, index).is_some()
+            && let Err(e) = self.save_tasks()
+        {
+            self.error_message = Some(format!("Failed to save: {}", e));
+        }
+    }
+
+    /// Open the selected task's link, or let the user pick one when it has
+    /// several
+    pub fn open_selected_link(&mut self) {
+        let Some(task) = self.selected_task() else {
+            return;
+        };
+        match task.links.as_slice() {
+            [] => self.error_message = Some("Task has no links".to_string()),
+            [target] => {
+                let target = target.clone();
+                self.open_link(&target);
+            }
+            _ => {
+                self.dialog = DialogMode::OpenLink {
+                    task_id: task.id,
+                    selected: 0,
+                };
+            }
+        }
+    }
+
+    /// Launch the system opener without waiting for it, so the TUI stays usable
+    pub fn open_link(&mut self, target: &str) {
+        let mut command = if cfg!(target_os = "macos") {
+            std::process::Command::new("open")
+        } else if cfg!(windows) {
+            let mut command = std::process::Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        } else {
+            std::process::Command::new("xdg-open")
+        };
+        let result = command
+            .arg(target)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        if let Err(e) = result {
+            self.error_message = Some(format!("Failed to open {}: {}", target, e));
+        }
+        self.dialog = DialogMode::None;
+    }
+
+    /// Move task (and its subtasks) to category
     pub fn move_task_to_category(&mut self, task_id: u32, category: Option<String>) {
-        if let Some(task) = self.storage.tasks.get_mut(&task_id) {
-            task.category = category;
+        if self.storage.collection.get(task_id).is_some() {
+            self.storage.move_to_category(task_id, category);
             self.update_categories_tags();
             self.update_filtered_tasks();
             if let Err(e) = self.save_tasks() {
@@ -497,8 +703,9 @@ impl App {
 
     /// Add tag to task
     pub fn add_tag_to_task(&mut self, task_id: u32, tag: String) {
-        if let Some(task) = self.storage.tasks.get_mut(&task_id) {
+        if let Some(task) = self.storage.collection.get_mut(task_id) {
             task.add_tag(&tag);
+            task.touch();
             self.update_categories_tags();
             self.update_filtered_tasks();
             if let Err(e) = self.save_tasks() {
@@ -510,8 +717,10 @@ impl App {
 
     /// Remove tag from task
     pub fn remove_tag_from_task(&mut self, task_id: u32, tag: String) {
-        if let Some(task) = self.storage.tasks.get_mut(&task_id) {
-            task.remove_tag(&tag);
+        if let Some(task) = self.storage.collection.get_mut(task_id)
+            && task.remove_tag(&tag)
+        {
+            task.touch();
             self.update_categories_tags();
             self.update_filtered_tasks();
             if let Err(e) = self.save_tasks() {
@@ -522,46 +731,58 @@ impl App {
     }
 }
 
-fn parse_date_input(input: &str) -> Option<DateTime<Local>> {
-    let input = input.trim().to_lowercase();
-
-    if input.is_empty() || input == "none" {
-        return None;
+/// Arrange tasks as a tree, keeping their order among siblings
+///
+/// Returns (task ID, depth) pairs. Subtasks of collapsed tasks are left out,
+/// and tasks whose parent is not in the list are shown at the top level.
+fn tree_order(tasks: &[&Task], collapsed: &HashSet<u32>) -> Vec<(u32, usize)> {
+    fn visit(
+        task: &Task,
+        depth: usize,
+        visible: bool,
+        tasks: &[&Task],
+        collapsed: &HashSet<u32>,
+        visited: &mut HashSet<u32>,
+        result: &mut Vec<(u32, usize)>,
+    ) {
+        if !visited.insert(task.id) {
+            return;
+        }
+        if visible {
+            result.push((task.id, depth));
+        }
+        let children_visible = visible && !collapsed.contains(&task.id);
+        for child in tasks.iter().filter(|t| t.parent == Some(task.id)) {
+            visit(child, depth + 1, children_visible, tasks, collapsed, visited, result);
+        }
     }
 
-    // Try relative dates
-    let today = Local::now().date_naive();
-    let date = match input.as_str() {
-        "today" => Some(today),
-        "tomorrow" => today.succ_opt(),
-        _ => None,
-    };
+    let ids: HashSet<u32> = tasks.iter().map(|t| t.id).collect();
+    let mut result = Vec::with_capacity(tasks.len());
+    let mut visited = HashSet::new();
 
-    if let Some(d) = date {
-        return d.and_hms_opt(0, 0, 0)
-            .and_then(|dt| Local.from_local_datetime(&dt).single());
+    for task in tasks {
+        if task.parent.is_none_or(|p| !ids.contains(&p)) {
+            visit(task, 0, true, tasks, collapsed, &mut visited, &mut result);
+        }
     }
 
-    // Try ISO format
-    if let Ok(d) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
-        return d.and_hms_opt(0, 0, 0)
-            .and_then(|dt| Local.from_local_datetime(&dt).single());
+    // Anything left over is part of a parent cycle; show it flat
+    for task in tasks {
+        visit(task, 0, true, tasks, collapsed, &mut visited, &mut result);
     }
 
-    // Try other formats
-    let formats = ["%b %d", "%B %d", "%m/%d"];
-    for fmt in &formats {
-        if let Ok(mut d) = NaiveDate::parse_from_str(&input, fmt) {
-            d = d.with_year(today.year()).unwrap_or(d);
-            if d < today {
-                d = d.with_year(today.year() + 1).unwrap_or(d);
-            }
-            return d.and_hms_opt(0, 0, 0)
-                .and_then(|dt| Local.from_local_datetime(&dt).single());
-        }
+    result
+}
+
+fn parse_date_input(input: &str) -> Option<DateTime<Local>> {
+    let input = input.trim();
+
+    if input.is_empty() || input.eq_ignore_ascii_case("none") {
+        return None;
     }
 
-    None
+    parse_datetime(input).ok()
 }
 
 pub fn run_tui(ctx: &mut PluginContext) -> Result<(), String> {
@@ -587,6 +808,29 @@ pub fn run_tui(ctx: &mut PluginContext) -> Result<(), String> {
         app.error_message = Some(format!("Failed to load tasks: {}", e));
     }
 
+    // The plugin has its own copy of the core library, so the time zone,
+    // the language of dates and the working-day calendar used for dates like
+    // "next workday" are installed here too
+    match Zone::parse(ctx.extra.get("time_zone").map(|s| s.as_str()).unwrap_or("local")) {
+        Ok(zone) => zone.install(),
+        Err(e) => app.error_message = Some(e.to_string()),
+    }
+    match Locale::parse(ctx.extra.get("locale").map(|s| s.as_str()).unwrap_or("en")) {
+        Ok(locale) => locale.install(),
+        Err(e) => app.error_message = Some(e.to_string()),
+    }
+    let working_week = ctx.extra.get("working_week").map(|s| s.as_str()).unwrap_or("mon-fri");
+    let holidays = match ctx.extra.get("holidays_file") {
+        Some(path) => std::fs::read_to_string(path)
+            .map(Some)
+            .map_err(|e| format!("Failed to read holiday file {}: {}", path, e)),
+        None => Ok(None),
+    };
+    match holidays.and_then(|h| WorkCalendar::from_config(working_week, h.as_deref()).map_err(|e| e.to_string())) {
+        Ok(calendar) => calendar.install(),
+        Err(e) => app.error_message = Some(e),
+    }
+
     // Main loop
     let result = run_app(&mut terminal, &mut app);
 
@@ -651,6 +895,7 @@ fn run_app<B: ratatui::backend::Backend>(
                     KeyCode::Down | KeyCode::Char('j') => app.move_sidebar_selection(1),
                     KeyCode::Char(' ') | KeyCode::Enter => app.select_sidebar_item(),
                     KeyCode::Char('h') | KeyCode::Left => app.toggle_sidebar_section(),
+                    KeyCode::Char('z') => app.toggle_collapse_category(),
                     KeyCode::Char('?') => app.dialog = DialogMode::Help,
                     _ => {}
                 }
@@ -695,7 +940,7 @@ fn run_app<B: ratatui::backend::Backend>(
                                 id: task.id,
                                 name: task.title.clone(),
                                 date: task.scheduled
-                                    .map(|d| d.format("%Y-%m-%d").to_string())
+                                    .map(|d| format_scheduled(&d))
                                     .unwrap_or_default(),
                                 field: 0,
                             };
@@ -706,14 +951,20 @@ fn run_app<B: ratatui::backend::Backend>(
                             app.dialog = DialogMode::DeleteConfirm { id };
                         }
                     }
+                    KeyCode::Char('p') => app.toggle_selected_status(Status::InProgress),
+                    KeyCode::Char('w') => app.toggle_selected_status(Status::Waiting),
+                    KeyCode::Char('-') => app.toggle_selected_status(Status::Cancelled),
                     KeyCode::Char('c') => {
-                        if app.completed_count() > 0 {
+                        if app.closed_count() > 0 {
                             app.dialog = DialogMode::ClearConfirm;
                         }
                     }
+                    KeyCode::Char('z') => app.toggle_collapse_selected(),
                     KeyCode::Char('m') => app.open_move_category_dialog(),
                     KeyCode::Char('t') => app.open_add_tag_dialog(),
                     KeyCode::Char('T') => app.open_remove_tag_dialog(),
+                    KeyCode::Char('i') => app.open_checklist_dialog(),
+                    KeyCode::Char('o') => app.open_selected_link(),
                     KeyCode::Char('f') => app.cycle_filter(),
                     KeyCode::Char('s') => app.cycle_sort(),
                     KeyCode::Char('/') => app.start_search(),
@@ -881,6 +1132,40 @@ fn handle_dialog_input(app: &mut App, key: KeyCode, _modifiers: KeyModifiers) {
                 _ => {}
             }
         }
+        DialogMode::Checklist { task_id, selected } => {
+            let task_id = *task_id;
+            let len = app.storage.collection.get(task_id).map_or(0, |t| t.checklist.len());
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => {
+                    app.dialog = DialogMode::None
+                }
+                KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *selected = (*selected + 1).min(len.saturating_sub(1));
+                }
+                KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('x') => {
+                    let index = *selected;
+                    app.toggle_checklist_item(task_id, index);
+                }
+                _ => {}
+            }
+        }
+        DialogMode::OpenLink { task_id, selected } => {
+            let links = app.storage.collection.get(*task_id).map(|t| t.links.clone()).unwrap_or_default();
+            match key {
+                KeyCode::Esc | KeyCode::Char('q') => app.dialog = DialogMode::None,
+                KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *selected = (*selected + 1).min(links.len().saturating_sub(1));
+                }
+                KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('o') => {
+                    if let Some(target) = links.get(*selected) {
+                        app.open_link(target);
+                    }
+                }
+                _ => {}
+            }
+        }
         DialogMode::None => {}
     }
 }
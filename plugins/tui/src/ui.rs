@@ -1,8 +1,10 @@
 //! TUI rendering module
 
 use crate::app::{App, DialogMode, SidebarSection};
-use crate::task_storage::Task;
 use chrono::Local;
+use taiga_core::{category, DeadlineState, Locale, Priority, Status, Task, Zone};
+use taiga_core::date::{has_time_of_day, wall_time};
+use taiga_core::task::DEFAULT_DEADLINE_WARNING_DAYS;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -48,12 +50,12 @@ pub fn draw(f: &mut Frame, app: &App) {
             draw_edit_dialog(f, name, date, *field);
         }
         DialogMode::DeleteConfirm { id } => {
-            if let Some(task) = app.storage.tasks.get(id) {
+            if let Some(task) = app.storage.collection.get(*id) {
                 draw_confirm_dialog(f, &format!("Delete task #{}?", id), &task.title);
             }
         }
         DialogMode::ClearConfirm => {
-            let count = app.completed_count();
+            let count = app.closed_count();
             draw_confirm_dialog(
                 f,
                 "Clear completed tasks?",
@@ -72,6 +74,16 @@ pub fn draw(f: &mut Frame, app: &App) {
         DialogMode::RemoveTag { task_id: _, tags, selected } => {
             draw_remove_tag_dialog(f, tags, *selected);
         }
+        DialogMode::Checklist { task_id, selected } => {
+            if let Some(task) = app.storage.collection.get(*task_id) {
+                draw_checklist_dialog(f, task, *selected);
+            }
+        }
+        DialogMode::OpenLink { task_id, selected } => {
+            if let Some(task) = app.storage.collection.get(*task_id) {
+                draw_open_link_dialog(f, task, *selected);
+            }
+        }
     }
 
     // Draw error message if any
@@ -112,7 +124,7 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect) {
             } else {
                 Style::default()
             };
-            let total = app.storage.tasks.len();
+            let total = app.storage.collection.len();
             ListItem::new(format!("All ({})", total)).style(style)
         },
         // "Uncategorized" option
@@ -133,12 +145,12 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect) {
         },
     ];
 
-    // Add specific categories
-    for (i, cat) in app.categories.iter().enumerate() {
+    // Add specific categories as a tree, counting subcategories too
+    for (i, cat) in app.visible_categories().into_iter().enumerate() {
         let is_selected = app.sidebar_focused
             && app.sidebar_section == SidebarSection::Categories
             && app.sidebar_selection == i + 2;
-        let is_active = app.selected_category == Some(Some(cat.clone()));
+        let is_active = app.selected_category.as_ref().is_some_and(|c| c.as_deref() == Some(cat));
         let style = if is_selected {
             Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
         } else if is_active {
@@ -146,8 +158,24 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect) {
         } else {
             Style::default()
         };
-        let count = app.storage.count_in_category(Some(cat));
-        category_items.push(ListItem::new(format!("{} ({})", cat, count)).style(style));
+        let fold_marker = if !app.has_subcategories(cat) {
+            "  "
+        } else if app.collapsed_categories.contains(cat) {
+            "▸ "
+        } else {
+            "▾ "
+        };
+        let count = app.storage.count_within_category(cat);
+        category_items.push(
+            ListItem::new(format!(
+                "{}{}{} ({})",
+                "  ".repeat(category::depth(cat)),
+                fold_marker,
+                category::name(cat),
+                count
+            ))
+            .style(style),
+        );
     }
 
     let categories_list = List::new(category_items)
@@ -217,14 +245,22 @@ fn draw_sidebar(f: &mut Frame, app: &App, area: Rect) {
 
 fn draw_task_list(f: &mut Frame, app: &App, area: Rect) {
     let tasks = app.get_visible_tasks();
-    let today = Local::now().date_naive();
+    let today = Zone::current().today();
 
     let items: Vec<ListItem> = tasks
         .iter()
         .enumerate()
         .map(|(i, task)| {
             let is_selected = i == app.selected_index;
-            create_task_item(task, is_selected, today)
+            let depth = app.task_depths.get(&task.id).copied().unwrap_or(0);
+            let fold = if !app.storage.has_children(task.id) {
+                None
+            } else {
+                Some(app.collapsed.contains(&task.id))
+            };
+            let progress = app.storage.progress(task.id);
+            let blocked = app.storage.is_blocked(task.id);
+            create_task_item(task, is_selected, today, depth, fold, progress, blocked)
         })
         .collect();
 
@@ -251,35 +287,66 @@ fn draw_task_list(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(list, area);
 }
 
-fn create_task_item(task: &Task, is_selected: bool, today: chrono::NaiveDate) -> ListItem<'static> {
-    let checkbox = if task.is_complete { "[✓]" } else { "[ ]" };
+fn create_task_item(
+    task: &Task,
+    is_selected: bool,
+    today: chrono::NaiveDate,
+    depth: usize,
+    fold: Option<bool>,
+    progress: Option<u8>,
+    blocked: bool,
+) -> ListItem<'static> {
+    let checkbox = match task.status {
+        Status::Open => "[ ]",
+        Status::InProgress => "[/]",
+        Status::Waiting => "[?]",
+        Status::Done => "[✓]",
+        Status::Cancelled => "[-]",
+    };
 
+    let locale = Locale::current();
     let date_info = task.scheduled.map(|dt| {
-        let date = dt.date_naive();
+        let date = Zone::current().date_of(&dt);
         let diff = date.signed_duration_since(today).num_days();
 
-        let date_str = if diff == 0 {
-            "Today".to_string()
+        let mut date_str = if diff == 0 {
+            locale.today.to_string()
         } else if diff == 1 {
-            "Tomorrow".to_string()
+            locale.tomorrow.to_string()
         } else if diff < 0 {
-            format!("Overdue ({} days)", -diff)
+            format!("{} ({})", locale.overdue, locale.days.replace("{}", &(-diff).to_string()))
         } else if diff <= 7 {
-            date.format("%a").to_string()
+            locale.format(date, "%a")
         } else {
-            date.format("%b %d").to_string()
+            locale.format(date, locale.date_format)
         };
+        if diff >= 0 && has_time_of_day(&dt) {
+            date_str.push_str(&wall_time(&dt).format(" %H:%M").to_string());
+        }
 
         (date_str, diff)
     });
 
+    // Indentation and fold marker for subtasks
+    let fold_marker = match fold {
+        Some(true) => "▸ ",
+        Some(false) => "▾ ",
+        None => "  ",
+    };
+
     let mut spans = vec![
+        Span::styled(
+            format!("{}{}", "  ".repeat(depth), fold_marker),
+            Style::default().fg(Color::DarkGray),
+        ),
         Span::styled(
             format!("{} ", checkbox),
-            if task.is_complete {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default()
+            match task.status {
+                Status::InProgress => Style::default().fg(Color::Cyan),
+                Status::Waiting => Style::default().fg(Color::Yellow),
+                Status::Done => Style::default().fg(Color::Green),
+                Status::Cancelled => Style::default().fg(Color::DarkGray),
+                Status::Open => Style::default(),
             },
         ),
         Span::styled(
@@ -288,11 +355,22 @@ fn create_task_item(task: &Task, is_selected: bool, today: chrono::NaiveDate) ->
         ),
     ];
 
+    // Blocked marker before the title
+    if blocked && !task.status.is_closed() {
+        spans.push(Span::styled("⊘ ", Style::default().fg(Color::Red)));
+    }
+
     // Title with styling
-    let title_style = if task.is_complete {
+    let title_style = if task.is_complete() {
         Style::default()
             .fg(Color::Green)
             .add_modifier(Modifier::CROSSED_OUT)
+    } else if task.status == Status::Cancelled {
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::CROSSED_OUT)
+    } else if blocked {
+        Style::default().fg(Color::DarkGray)
     } else if is_selected {
         Style::default().add_modifier(Modifier::BOLD)
     } else {
@@ -301,6 +379,17 @@ fn create_task_item(task: &Task, is_selected: bool, today: chrono::NaiveDate) ->
 
     spans.push(Span::styled(task.title.clone(), title_style));
 
+    // Priority marker, coloured by importance
+    if let Some(priority) = task.priority {
+        let priority_style = match priority {
+            Priority::Urgent => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Priority::High => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            Priority::Medium => Style::default().fg(Color::Blue),
+            Priority::Low => Style::default().fg(Color::DarkGray),
+        };
+        spans.push(Span::styled(format!(" {}", priority), priority_style));
+    }
+
     // Tags in magenta
     for tag in &task.tags {
         spans.push(Span::styled(
@@ -309,11 +398,67 @@ fn create_task_item(task: &Task, is_selected: bool, today: chrono::NaiveDate) ->
         ));
     }
 
+    // Checklist progress
+    if let Some((done, total)) = task.checklist_progress() {
+        let style = if done == total {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        spans.push(Span::styled(format!(" [{}/{}]", done, total), style));
+    }
+
+    // Notes marker
+    if task.notes.is_some() {
+        spans.push(Span::styled(" ✎", Style::default().fg(Color::DarkGray)));
+    }
+
+    // Recurring marker
+    if task.recurrence.is_some() {
+        spans.push(Span::styled(" ↻", Style::default().fg(Color::Cyan)));
+    }
+
+    // Links marker
+    if !task.links.is_empty() {
+        spans.push(Span::styled(" ↗", Style::default().fg(Color::Blue)));
+    }
+
+    // Deadline, coloured as it approaches
+    if let Some(deadline) = task.deadline {
+        let style = match task.deadline_state_at(Local::now(), DEFAULT_DEADLINE_WARNING_DAYS) {
+            Some(DeadlineState::Passed) => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Some(DeadlineState::Approaching) => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        let date = Zone::current().date_of(&deadline);
+        spans.push(Span::styled(format!(" ⚑ {}", locale.format(date, locale.date_format)), style));
+    }
+
+    // Start date, for tasks that are still deferred
+    if let Some(start) = task.start.filter(|_| task.is_deferred()) {
+        spans.push(Span::styled(
+            format!(" [starts {}]", locale.format(start, locale.date_format)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    // Subtask progress
+    if let Some(pct) = progress {
+        let pct_style = if pct == 100 {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        spans.push(Span::styled(format!(" [{}%]", pct), pct_style));
+    }
+
     // Date info
     if let Some((date_str, diff)) = date_info {
-        let date_style = if task.is_complete {
+        let date_style = if task.is_complete() {
             Style::default().fg(Color::Green)
-        } else if diff < 0 {
+        } else if task.status == Status::Cancelled {
+            Style::default().fg(Color::DarkGray)
+        } else if task.is_overdue() {
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
         } else if diff <= 1 {
             Style::default().fg(Color::Yellow)
@@ -340,18 +485,31 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let done = app.completed_count();
     let overdue = app.overdue_count();
 
-    let status = vec![
+    let mut status = vec![
         Span::raw(" "),
         Span::styled(format!("{} total", total), Style::default()),
         Span::raw(" | "),
-        Span::styled(format!("{} done", done), Style::default().fg(Color::Green)),
-        Span::raw(" | "),
-        if overdue > 0 {
-            Span::styled(format!("{} overdue", overdue), Style::default().fg(Color::Red))
-        } else {
-            Span::styled("0 overdue", Style::default().fg(Color::DarkGray))
-        },
     ];
+    // Other states only show up once some task is in them
+    for (state, color) in [(Status::InProgress, Color::Cyan), (Status::Waiting, Color::Yellow)] {
+        let count = app.status_count(state);
+        if count > 0 {
+            status.push(Span::styled(format!("{} {}", count, state), Style::default().fg(color)));
+            status.push(Span::raw(" | "));
+        }
+    }
+    status.push(Span::styled(format!("{} done", done), Style::default().fg(Color::Green)));
+    status.push(Span::raw(" | "));
+    let cancelled = app.status_count(Status::Cancelled);
+    if cancelled > 0 {
+        status.push(Span::styled(format!("{} cancelled", cancelled), Style::default().fg(Color::DarkGray)));
+        status.push(Span::raw(" | "));
+    }
+    status.push(if overdue > 0 {
+        Span::styled(format!("{} overdue", overdue), Style::default().fg(Color::Red))
+    } else {
+        Span::styled("0 overdue", Style::default().fg(Color::DarkGray))
+    });
 
     let paragraph = Paragraph::new(Line::from(status))
         .block(
@@ -366,8 +524,12 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
 
 fn draw_controls(f: &mut Frame, app: &App, area: Rect) {
     let controls = if app.is_searching {
+        let hint = match &app.search_error {
+            Some(error) => Span::styled(format!(" {}", error), Style::default().fg(Color::Red)),
+            None => Span::styled(" Type to search, or a query like tag:work due<+3d", Style::default().fg(Color::Yellow)),
+        };
         vec![
-            Span::styled(" Type to search", Style::default().fg(Color::Yellow)),
+            hint,
             Span::raw(" | "),
             Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
             Span::raw(":Confirm "),
@@ -429,7 +591,7 @@ fn draw_add_dialog(f: &mut Frame, name: &str) {
         .margin(1)
         .constraints([
             Constraint::Length(3),
-            Constraint::Length(2),
+            Constraint::Length(3),
         ])
         .split(inner);
 
@@ -439,7 +601,7 @@ fn draw_add_dialog(f: &mut Frame, name: &str) {
 
     f.render_widget(input, chunks[0]);
 
-    let hint = Paragraph::new("Enter to save, Esc to cancel")
+    let hint = Paragraph::new("e.g. 'Pay rent tomorrow #bills @Home !1'\nEnter to save, Esc to cancel")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center);
 
@@ -553,20 +715,27 @@ fn draw_help_dialog(f: &mut Frame) {
         Line::from(""),
         Line::from(vec![Span::styled("Task Actions", Style::default().add_modifier(Modifier::BOLD))]),
         Line::from("  Space/Enter Toggle completion"),
+        Line::from("  p           Toggle in progress"),
+        Line::from("  w           Toggle waiting"),
+        Line::from("  -           Toggle cancelled"),
         Line::from("  a           Add new task"),
         Line::from("  e           Edit selected task"),
         Line::from("  d/Delete    Delete selected task"),
-        Line::from("  c           Clear completed tasks"),
+        Line::from("  c           Clear done and cancelled tasks"),
+        Line::from("  z           Collapse/expand subtasks"),
         Line::from(""),
         Line::from(vec![Span::styled("Categories & Tags", Style::default().add_modifier(Modifier::BOLD))]),
         Line::from("  m           Move task to category"),
+        Line::from("  z           Collapse/expand subcategories (sidebar)"),
         Line::from("  t           Add tag to task"),
         Line::from("  T           Remove tag from task"),
+        Line::from("  i           Check checklist items"),
+        Line::from("  o           Open the task's link"),
         Line::from(""),
         Line::from(vec![Span::styled("View Controls", Style::default().add_modifier(Modifier::BOLD))]),
-        Line::from("  f           Cycle filter mode"),
+        Line::from("  f           Cycle filter mode (deferred tasks have their own)"),
         Line::from("  s           Cycle sort mode"),
-        Line::from("  /           Search tasks"),
+        Line::from("  /           Search tasks by text or query (tag:work due<+3d)"),
         Line::from("  r/F5        Refresh from file"),
         Line::from(""),
         Line::from(vec![Span::styled("General", Style::default().add_modifier(Modifier::BOLD))]),
@@ -721,6 +890,98 @@ fn draw_remove_tag_dialog(f: &mut Frame, tags: &[String], selected: usize) {
     f.render_widget(hint, chunks[1]);
 }
 
+fn draw_checklist_dialog(f: &mut Frame, task: &Task, selected: usize) {
+    let area = centered_rect(50, 50, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Checklist: {} ", task.title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let items: Vec<ListItem> = task
+        .checklist
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let checkbox = if item.done { "[✓]" } else { "[ ]" };
+            let mut style = if item.done {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            if i == selected {
+                style = style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+            }
+            ListItem::new(format!("{} {}", checkbox, item.text)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(3), Constraint::Length(2)])
+        .split(inner);
+
+    f.render_widget(list, chunks[0]);
+
+    let hint = Paragraph::new("↑/↓ to select, Space to toggle, Esc to close")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(hint, chunks[1]);
+}
+
+fn draw_open_link_dialog(f: &mut Frame, task: &Task, selected: usize) {
+    let area = centered_rect(60, 40, f.area());
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Open: {} ", task.title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Blue));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let items: Vec<ListItem> = task
+        .links
+        .iter()
+        .enumerate()
+        .map(|(i, link)| {
+            let style = if i == selected {
+                Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Blue)
+            };
+            ListItem::new(link.as_str()).style(style)
+        })
+        .collect();
+
+    let list = List::new(items);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(3), Constraint::Length(2)])
+        .split(inner);
+
+    f.render_widget(list, chunks[0]);
+
+    let hint = Paragraph::new("↑/↓ to select, Enter to open, Esc to cancel")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+
+    f.render_widget(hint, chunks[1]);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
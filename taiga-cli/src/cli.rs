@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use taiga_core::TaskRef;
 
 #[derive(Parser)]
 #[command(name = "taiga")]
@@ -31,26 +32,75 @@ pub enum SortBy {
     Date,
     Name,
     Status,
+    Priority,
+    Created,
+    Updated,
+    Completed,
+    Deadline,
+}
+
+/// Sort order for `list`: a built-in key or a custom field
+#[derive(Clone, Debug)]
+pub enum SortKey {
+    Builtin(SortBy),
+    Field(String),
+}
+
+/// Parse a sort key, treating unknown names as custom fields
+fn parse_sort_key(input: &str) -> Result<SortKey, String> {
+    if let Ok(sort) = SortBy::from_str(input, true) {
+        return Ok(SortKey::Builtin(sort));
+    }
+    taiga_core::field::normalize_key(input)
+        .map(SortKey::Field)
+        .map_err(|e| e.to_string())
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Adds a task
+    ///
+    /// The title may carry a date, #tags, @Category and a !priority, e.g.
+    /// 'Pay rent tomorrow #bills @Home !1'; options given explicitly win.
     Add {
         #[arg(required = true, num_args = 1..)]
         title: Vec<String>,
-        /// Schedule the task on a specific date
+        /// Keep the title as typed, without reading a date, tags, category or priority from it
+        #[arg(long)]
+        raw: bool,
+        /// Schedule the task on a specific date, optionally with a time (e.g. 'tomorrow 3pm')
         #[arg(long, short = 'd', value_name = "DATE")]
         on: Option<String>,
         /// Schedule the task on a specific date (alias for --on)
         #[arg(long, value_name = "DATE")]
         date: Option<String>,
-        /// Category for the task
+        /// Hide the task from 'list' until this date (e.g. 'in 3 days', '2026-03-01')
+        #[arg(long, value_name = "DATE")]
+        start: Option<String>,
+        /// Date (and optional time) the task must be done by
+        #[arg(long, value_name = "DATE")]
+        deadline: Option<String>,
+        /// Start warning this many days before the deadline (default from config)
+        #[arg(long, value_name = "DAYS", requires = "deadline")]
+        warn: Option<u32>,
+        /// Category for the task, nested with slashes (e.g. Work/ClientA)
         #[arg(long, short = 'C', value_name = "CATEGORY")]
         category: Option<String>,
         /// Tags for the task (can be specified multiple times)
         #[arg(long, short = 't', value_name = "TAG")]
         tag: Vec<String>,
+        /// Priority for the task (1-4 or urgent/high/medium/low)
+        #[arg(long, short = 'p', value_name = "PRIORITY")]
+        priority: Option<String>,
+        /// Add as a subtask of another task, by number or UID (inherits its category)
+        #[arg(long, value_name = "ID", conflicts_with = "category")]
+        parent: Option<TaskRef>,
+        /// Repeat rule, e.g. 'weekly', 'every 2nd tuesday', 'every 3 days from completion'
+        #[arg(long, short = 'R', value_name = "RULE")]
+        repeat: Option<String>,
+        /// Set a custom field, e.g. 'customer=Acme' (can be specified multiple times)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_var)]
+        set: Vec<(String, String)>,
     },
 
     /// Lists tasks with filtering and sorting
@@ -70,12 +120,31 @@ pub enum Commands {
         /// Show only overdue tasks
         #[arg(long)]
         overdue: bool,
+        /// Show only tasks scheduled within a date range, e.g. 'this week',
+        /// 'next 7 days', 'last month' or 'today..eom'
+        #[arg(long, value_name = "RANGE")]
+        due: Option<String>,
+        /// Show only tasks scheduled before a date
+        #[arg(long, value_name = "DATE")]
+        before: Option<String>,
+        /// Show only tasks scheduled after a date
+        #[arg(long, value_name = "DATE")]
+        after: Option<String>,
+        /// Show only tasks scheduled on a date
+        #[arg(long, value_name = "DATE")]
+        on: Option<String>,
         /// Filter tasks containing text (case-insensitive)
         #[arg(long, short = 's', value_name = "TERM")]
         search: Option<String>,
-        /// Sort tasks by field
-        #[arg(long, value_enum, default_value = "id")]
-        sort: SortBy,
+        /// Show only tasks matching a query, e.g.
+        /// 'status:open and (tag:work or tag:urgent) and due<+3d'.
+        /// Deferred tasks are included unless the query leaves them out
+        #[arg(long, short = 'q', value_name = "QUERY", allow_hyphen_values = true)]
+        query: Option<String>,
+        /// Sort tasks by id, date, name, status, priority, created, updated,
+        /// completed, deadline or a custom field
+        #[arg(long, value_name = "FIELD", default_value = "id", value_parser = parse_sort_key)]
+        sort: SortKey,
         /// Reverse sort order
         #[arg(long, short = 'r')]
         reverse: bool,
@@ -88,58 +157,213 @@ pub enum Commands {
         /// Disable colors
         #[arg(long)]
         no_color: bool,
-        /// Filter by category (use 'none' for uncategorized)
+        /// Filter by category, including its subcategories (use 'none' for uncategorized)
         #[arg(long, short = 'C', value_name = "CATEGORY")]
         category: Option<String>,
         /// Filter by tag (can be specified multiple times, all must match)
         #[arg(long, short = 't', value_name = "TAG")]
         tag: Vec<String>,
+        /// Filter by priority (use 'none' for tasks without priority)
+        #[arg(long, short = 'p', value_name = "PRIORITY")]
+        priority: Option<String>,
+        /// Show subtasks as a flat list instead of a tree
+        #[arg(long)]
+        flat: bool,
+        /// Show only actionable tasks (all blockers complete)
+        #[arg(long, conflicts_with = "blocked")]
+        actionable: bool,
+        /// Show only tasks waiting on an incomplete blocker
+        #[arg(long)]
+        blocked: bool,
+        /// Show only tasks completed on or after a date (e.g. 'yesterday', 'mon 9am')
+        #[arg(long, value_name = "DATE")]
+        completed_since: Option<String>,
+        /// Show only tasks created before a date
+        #[arg(long, value_name = "DATE")]
+        created_before: Option<String>,
+        /// Show only tasks in a state: open, in-progress, waiting, done or cancelled
+        /// (can be specified multiple times)
+        #[arg(long, value_name = "STATUS")]
+        status: Vec<String>,
+        /// List archived tasks instead of current ones
+        #[arg(long)]
+        archived: bool,
+        /// Include tasks whose start date is still ahead
+        #[arg(long, short = 'a', conflicts_with = "deferred")]
+        all: bool,
+        /// Show only tasks whose start date is still ahead
+        #[arg(long)]
+        deferred: bool,
+        /// Show only tasks with a deadline
+        #[arg(long)]
+        deadline: bool,
+        /// Show only open tasks that missed their deadline
+        #[arg(long)]
+        past_deadline: bool,
+        /// Show only open tasks whose deadline is within its warning lead time or past
+        #[arg(long)]
+        due_soon: bool,
+        /// Show only tasks with a custom field, or with the given value
+        /// (can be specified multiple times, all must match)
+        #[arg(long, value_name = "KEY[=VALUE]")]
+        field: Vec<String>,
+        /// Show a table with these columns, e.g. 'id,title,customer,deadline'
+        /// (built-in columns or custom fields)
+        #[arg(long, value_name = "COLUMNS", value_delimiter = ',', conflicts_with_all = ["compact", "detailed"])]
+        columns: Vec<String>,
+    },
+
+    /// Show all details of a task, including notes and subtasks
+    Show {
+        /// Task number or UID
+        id: TaskRef,
+        /// Disable colored output
+        #[arg(long)]
+        no_color: bool,
     },
 
     /// Toggles task completion status
     Check {
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task number or UID
+        id: TaskRef,
+    },
+
+    /// Marks a task as in progress
+    Start {
+        /// Task number or UID
+        id: TaskRef,
+    },
+
+    /// Marks a task as waiting on someone or something
+    Wait {
+        /// Task number or UID
+        id: TaskRef,
+    },
+
+    /// Marks a task as cancelled (won't do)
+    Cancel {
+        /// Task number or UID
+        id: TaskRef,
     },
 
     /// Removes a task
     Remove {
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task number or UID
+        id: TaskRef,
     },
 
-    /// Edit a task's name and/or scheduled date
+    /// Edit a task's name, dates, priority and/or parent
     Edit {
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task number or UID
+        id: TaskRef,
         /// New task name
         #[arg(long, value_name = "NAME")]
         name: Option<String>,
-        /// New scheduled date (use 'none' to clear)
+        /// New scheduled date and optional time (use 'none' to clear)
         #[arg(long, value_name = "DATE")]
         date: Option<String>,
+        /// New start date (use 'none' to clear)
+        #[arg(long, value_name = "DATE")]
+        start: Option<String>,
+        /// New deadline and optional time (use 'none' to clear)
+        #[arg(long, value_name = "DATE")]
+        deadline: Option<String>,
+        /// Days before the deadline to start warning
+        #[arg(long, value_name = "DAYS")]
+        warn: Option<u32>,
+        /// New priority (use 'none' to clear)
+        #[arg(long, short = 'p', value_name = "PRIORITY")]
+        priority: Option<String>,
+        /// New parent task number or UID (use 'none' to make it a top-level task)
+        #[arg(long, value_name = "ID")]
+        parent: Option<String>,
+        /// New repeat rule (use 'none' to stop repeating)
+        #[arg(long, short = 'R', value_name = "RULE")]
+        repeat: Option<String>,
+        /// Set a custom field, e.g. 'customer=Acme'; an empty value removes it
+        /// (can be specified multiple times)
+        #[arg(long, value_name = "KEY=VALUE", value_parser = parse_var)]
+        set: Vec<(String, String)>,
+    },
+
+    /// Edit a task's notes in $EDITOR
+    Note {
+        /// Task number or UID
+        id: TaskRef,
+        /// Remove the notes instead of editing them
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Show or edit a task's checklist
+    Checklist {
+        /// Task number or UID
+        id: TaskRef,
+        /// Checklist action (shows the checklist when omitted)
+        #[command(subcommand)]
+        action: Option<ChecklistAction>,
+    },
+
+    /// Attach a URL or file path to a task
+    Attach {
+        /// Task number or UID
+        id: TaskRef,
+        /// URL or file path (with --remove: the link or its number)
+        target: String,
+        /// Detach the link instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Open a task's link with the system opener
+    Open {
+        /// Task number or UID
+        id: TaskRef,
+        /// Link number, as shown by 'show' (default: the first link)
+        link: Option<usize>,
     },
 
     /// Reschedule a task (change only the date)
     Reschedule {
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task number or UID
+        id: TaskRef,
         /// New date (use 'none' to clear)
         #[arg(required = true, num_args = 1..)]
         date: Vec<String>,
+        /// Move the date to the next working day if it falls on a weekend or holiday
+        #[arg(short, long, conflicts_with = "any_day")]
+        workday: bool,
+        /// Keep the date even on a weekend or holiday (overrides the config)
+        #[arg(long)]
+        any_day: bool,
     },
 
     /// Rename a task (change only the name)
     Rename {
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task number or UID
+        id: TaskRef,
         #[arg(required = true, num_args = 1..)]
         name: Vec<String>,
     },
 
-    /// Clear completed tasks
+    /// Move done and cancelled tasks to the archive file
+    Archive {
+        /// Tasks to archive, by number or UID (default: every done or cancelled task)
+        ids: Vec<TaskRef>,
+        /// Only archive tasks closed more than this many days ago
+        #[arg(long, value_name = "DAYS", conflicts_with = "ids")]
+        older_than: Option<u32>,
+    },
+
+    /// Bring an archived task back into the task list
+    Unarchive {
+        /// Archived task number (as shown by 'list --archived') or UID
+        id: TaskRef,
+    },
+
+    /// Permanently delete completed tasks (see 'archive' to keep them)
     Clear {
-        /// Remove only checked/completed tasks
+        /// Remove only done and cancelled tasks
         #[arg(long)]
         checked: bool,
         /// Skip confirmation prompt
@@ -154,6 +378,30 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Revert the last change(s)
+    Undo {
+        /// Number of changes to revert
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+
+    /// Reapply change(s) reverted by 'undo'
+    Redo {
+        /// Number of changes to reapply
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+
+    /// Show recent changes to tasks
+    Log {
+        /// Only show changes since this date (e.g. 'yesterday', '2026-01-25')
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+        /// Show at most this many entries (default: 20 without --since)
+        #[arg(long, short = 'n', value_name = "COUNT")]
+        limit: Option<usize>,
+    },
+
     /// Renumber all tasks sequentially
     Reindex {
         /// Skip confirmation prompt
@@ -166,23 +414,34 @@ pub enum Commands {
 
     /// Move a task to a different category
     Move {
-        /// Task ID to move
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
-        /// Target category (use 'none' for uncategorized)
+        /// Task to move (number or UID)
+        id: TaskRef,
+        /// Target category, nested with slashes (use 'none' for uncategorized)
         category: String,
     },
 
     /// Add or remove tags from a task
     Tag {
-        /// Task ID to modify
-        #[arg(value_parser = clap::value_parser!(u32))]
-        id: u32,
+        /// Task to modify (number or UID)
+        id: TaskRef,
         /// Tag action (add or remove)
         #[command(subcommand)]
         action: TagAction,
     },
 
+    /// Manage "blocked by" dependencies between tasks
+    Depend {
+        /// Dependency action (add or remove)
+        #[command(subcommand)]
+        action: DependAction,
+    },
+
+    /// Save, list and apply task templates
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
     /// List all categories
     Categories,
 
@@ -194,6 +453,105 @@ pub enum Commands {
     External(Vec<String>),
 }
 
+/// Dependency subcommands
+#[derive(Subcommand)]
+pub enum DependAction {
+    /// Mark a task as blocked until another task is complete
+    Add {
+        /// Task that is blocked (number or UID)
+        id: TaskRef,
+        /// Task that must be completed first (number or UID)
+        blocker: TaskRef,
+    },
+    /// Remove a dependency between two tasks
+    Remove {
+        /// Task that is blocked (number or UID)
+        id: TaskRef,
+        /// Task it no longer waits on (number or UID)
+        blocker: TaskRef,
+    },
+}
+
+/// Checklist subcommands
+#[derive(Subcommand)]
+pub enum ChecklistAction {
+    /// Add an item to the checklist
+    Add {
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
+    /// Check or uncheck an item
+    Check {
+        /// Item number, as shown by 'checklist <id>'
+        item: usize,
+    },
+    /// Remove an item from the checklist
+    Remove {
+        /// Item number, as shown by 'checklist <id>'
+        item: usize,
+    },
+}
+
+/// Template subcommands
+#[derive(Subcommand)]
+pub enum TemplateAction {
+    /// List saved templates
+    List,
+    /// Show the task definitions of a template
+    Show {
+        /// Template name
+        name: String,
+    },
+    /// Add the tasks of a template
+    Apply {
+        /// Template name
+        name: String,
+        /// Date the template's offsets count from (default: today)
+        #[arg(long, value_name = "DATE")]
+        start: Option<String>,
+        /// Value for a {{variable}} in the template (can be specified multiple times)
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_var)]
+        vars: Vec<(String, String)>,
+    },
+    /// Save existing tasks, with their subtasks, as a template
+    Save {
+        /// Template name
+        name: String,
+        /// Tasks to capture, by number or UID
+        ids: Vec<TaskRef>,
+        /// Capture the tasks in a category
+        #[arg(long, short = 'C', value_name = "CATEGORY")]
+        category: Option<String>,
+        /// Capture the tasks with a tag (can be specified multiple times, all must match)
+        #[arg(long, short = 't', value_name = "TAG")]
+        tag: Vec<String>,
+        /// Date offsets count from (default: the tasks' earliest date, or today)
+        #[arg(long, value_name = "DATE")]
+        start: Option<String>,
+        /// What the template is for
+        #[arg(long, short = 'd')]
+        description: Option<String>,
+        /// Replace an existing template with the same name
+        #[arg(long, short = 'f')]
+        force: bool,
+    },
+    /// Delete a template
+    Remove {
+        /// Template name
+        name: String,
+    },
+}
+
+/// Parse a "key=value" pair (template variable or custom field)
+fn parse_var(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{}'", input)),
+    }
+}
+
 /// Tag subcommands
 #[derive(Subcommand)]
 pub enum TagAction {
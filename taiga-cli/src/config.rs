@@ -1,10 +1,111 @@
+use chrono::Local;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use taiga_core::{Locale, Zone};
+use taiga_core::task::DEFAULT_DEADLINE_WARNING_DAYS;
+
+use crate::error::Result;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
     pub data_directory: String,
     pub task_filename: String,
+    /// Give new tasks the numbers of removed ones (disable to never recycle numbers)
+    #[serde(default = "default_reuse_ids")]
+    pub reuse_ids: bool,
+    /// Move done and cancelled tasks to the archive file once they are this many days old
+    #[serde(default)]
+    pub archive_after_days: Option<u32>,
+    /// Days before a deadline that tasks start warning, unless set on the task
+    #[serde(default = "default_deadline_warning_days")]
+    pub deadline_warning_days: u32,
+    /// Working days for workday dates such as "in 3 workdays" ("mon-fri", "sun-thu", "mon,wed,fri")
+    #[serde(default = "default_working_week")]
+    pub working_week: String,
+    /// Holiday file, ICS or one date per line (default: holidays.ics or holidays.txt in the data directory)
+    #[serde(default)]
+    pub holidays_file: Option<String>,
+    /// Move rescheduled tasks off weekends and holidays without passing --workday
+    #[serde(default)]
+    pub reschedule_skip_non_working: bool,
+    /// Time zone for "today", overdue tasks and shown times: "local", a name
+    /// such as "Europe/Berlin" or an offset such as "+02:00"
+    #[serde(default = "default_time_zone")]
+    pub time_zone: String,
+    /// Language of dates typed and shown: "auto" (from LANG), "en", "de" or "es"
+    #[serde(default = "default_locale")]
+    pub locale: String,
+}
+
+fn default_reuse_ids() -> bool {
+    true
+}
+
+fn default_deadline_warning_days() -> u32 {
+    DEFAULT_DEADLINE_WARNING_DAYS
+}
+
+fn default_working_week() -> String {
+    "mon-fri".to_string()
+}
+
+fn default_time_zone() -> String {
+    "local".to_string()
+}
+
+fn default_locale() -> String {
+    "auto".to_string()
+}
+
+impl Config {
+    /// The holiday file to read, if one is configured or present in the data directory
+    pub fn holidays_path(&self) -> Option<PathBuf> {
+        if let Some(file) = &self.holidays_file {
+            return Some(PathBuf::from(&self.data_directory).join(file));
+        }
+        ["holidays.ics", "holidays.txt"]
+            .iter()
+            .map(|name| PathBuf::from(&self.data_directory).join(name))
+            .find(|path| path.is_file())
+    }
+
+    /// The configured time zone, with "local" found by name (from TZ or the
+    /// operating system) so times are stored under it
+    pub fn zone(&self) -> Result<Zone> {
+        match Zone::parse(&self.time_zone)? {
+            Zone::Local => Ok(local_zone()),
+            zone => Ok(zone),
+        }
+    }
+
+    /// The configured language of dates, with "auto" taken from LC_ALL,
+    /// LC_TIME or LANG (the first one set), English when that is not a
+    /// built-in language
+    pub fn locale(&self) -> Result<Locale> {
+        let locale = self.locale.trim();
+        if !locale.is_empty() && !locale.eq_ignore_ascii_case("auto") {
+            return Ok(Locale::parse(locale)?);
+        }
+        Ok(["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::parse(&value).ok())
+            .unwrap_or(Locale::EN))
+    }
+}
+
+/// The system's zone by its IANA name, or the plain local zone (stored by
+/// offset) if the name cannot be found or does not match the clock
+fn local_zone() -> Zone {
+    let now = Local::now();
+    std::env::var("TZ")
+        .ok()
+        .map(|name| name.trim_start_matches(':').to_string())
+        .or_else(|| iana_time_zone::get_timezone().ok())
+        .and_then(|name| Zone::parse(&name).ok())
+        .filter(|zone| matches!(zone, Zone::Named(_)) && zone.to_naive(&now) == now.naive_local())
+        .unwrap_or(Zone::Local)
 }
 
 impl Default for Config {
@@ -18,6 +119,14 @@ impl Default for Config {
         Self {
             data_directory: default_path.to_string_lossy().to_string(),
             task_filename: "taiginator.md".to_string(),
+            reuse_ids: true,
+            archive_after_days: None,
+            deadline_warning_days: DEFAULT_DEADLINE_WARNING_DAYS,
+            working_week: default_working_week(),
+            holidays_file: None,
+            reschedule_skip_non_working: false,
+            time_zone: default_time_zone(),
+            locale: default_locale(),
         }
     }
 }
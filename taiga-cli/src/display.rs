@@ -4,9 +4,11 @@
 
 use chrono::Local;
 use colored::*;
+use std::collections::HashSet;
 
-use taiga_core::date::format_date_human;
-use taiga_core::Task;
+use taiga_core::date::{format_date_human, format_datetime_human, has_time_of_day, wall_time};
+use taiga_core::task::DEFAULT_DEADLINE_WARNING_DAYS;
+use taiga_core::{DeadlineState, Priority, Status, Task, TaskCollection, Zone};
 
 /// Display mode for task list
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,27 +26,75 @@ pub fn supports_color() -> bool {
     atty::is(atty::Stream::Stdout)
 }
 
-/// Format a task for display
-pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
-    let today = Local::now().date_naive();
+/// Collection-derived state shown alongside a task
+#[derive(Debug, Clone)]
+pub struct TaskContext {
+    /// Nesting depth in the task tree (0 = top-level)
+    pub depth: usize,
+    /// Completion percentage rolled up from subtasks
+    pub progress: Option<u8>,
+    /// IDs of blockers that are not yet complete
+    pub open_blockers: Vec<u32>,
+    /// Days before a deadline to start warning, for tasks without their own
+    pub deadline_warning_days: u32,
+}
+
+impl Default for TaskContext {
+    fn default() -> Self {
+        Self {
+            depth: 0,
+            progress: None,
+            open_blockers: Vec::new(),
+            deadline_warning_days: DEFAULT_DEADLINE_WARNING_DAYS,
+        }
+    }
+}
+
+impl TaskContext {
+    /// Gather the context for a task shown at the given depth
+    pub fn new(collection: &TaskCollection, task: &Task, depth: usize) -> Self {
+        Self {
+            depth,
+            progress: collection.progress(task.id),
+            open_blockers: collection.open_blockers(task.id).iter().map(|t| t.id).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the default deadline warning lead time
+    pub fn with_deadline_warning_days(mut self, days: u32) -> Self {
+        self.deadline_warning_days = days;
+        self
+    }
+}
+
+/// Format a task for display, with its tree depth, progress and blockers
+pub fn format_task(
+    task: &Task,
+    ctx: &TaskContext,
+    mode: DisplayMode,
+    use_color: bool,
+) -> String {
+    let zone = Zone::current();
 
-    let checkbox = if task.is_complete { "[✓]" } else { "[ ]" };
+    let checkbox = format_checkbox(task.status);
 
     let status_info = match &task.scheduled {
         Some(dt) => {
-            let date = dt.date_naive();
-            let diff_days = date.signed_duration_since(today).num_days();
+            let diff_days = zone.date_of(dt).signed_duration_since(zone.today()).num_days();
 
             let date_str = match mode {
-                DisplayMode::Compact => format_date_human(date, false),
-                _ => format_date_human(date, true),
+                DisplayMode::Compact => format_datetime_human(dt, false),
+                _ => format_datetime_human(dt, true),
             };
 
             // Determine color based on status
             if use_color {
-                if task.is_complete {
+                if task.is_complete() {
                     format!("({})", date_str).green().to_string()
-                } else if diff_days < 0 {
+                } else if task.status == Status::Cancelled {
+                    format!("({})", date_str).dimmed().to_string()
+                } else if task.is_overdue() {
                     format!("({})", date_str).red().bold().to_string()
                 } else if diff_days <= 1 {
                     format!("({})", date_str).yellow().to_string()
@@ -58,10 +108,10 @@ pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
         None => String::new(),
     };
 
-    let title = if use_color && task.is_complete {
-        task.title.green().to_string()
-    } else {
-        task.title.clone()
+    let title = match task.status {
+        Status::Done if use_color => task.title.green().to_string(),
+        Status::Cancelled if use_color => task.title.dimmed().strikethrough().to_string(),
+        _ => task.title.clone(),
     };
 
     // Format tags
@@ -87,9 +137,81 @@ pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
         )
     };
 
-    match mode {
+    let priority_str = task
+        .priority
+        .map(|p| format!(" {}", format_priority(p, use_color)))
+        .unwrap_or_default();
+
+    let progress_str = ctx.progress.map(|pct| {
+        let pct_str = format!("{}%", pct);
+        if !use_color {
+            pct_str
+        } else if pct == 100 {
+            pct_str.green().to_string()
+        } else {
+            pct_str.cyan().to_string()
+        }
+    });
+
+    let deadline_state = task.deadline_state_at(Local::now(), ctx.deadline_warning_days);
+    let deadline_str = task.deadline.map(|dt| {
+        // Past dates read as "Overdue (n days ago)" relative to today
+        let relative = mode != DisplayMode::Compact && deadline_state != Some(DeadlineState::Passed);
+        let date_str = format_datetime_human(&dt, relative);
+        let marker = match deadline_state {
+            Some(DeadlineState::Passed) => format!("[deadline missed: {}]", date_str),
+            _ => format!("[deadline {}]", date_str),
+        };
+        match deadline_state {
+            Some(DeadlineState::Passed) if use_color => marker.red().bold().to_string(),
+            Some(DeadlineState::Approaching) if use_color => marker.yellow().bold().to_string(),
+            None if use_color => marker.dimmed().to_string(),
+            _ => marker,
+        }
+    });
+
+    let blockers_str = if ctx.open_blockers.is_empty() || task.status.is_closed() {
+        None
+    } else {
+        let ids: Vec<String> = ctx.open_blockers.iter().map(|id| format!("#{}", id)).collect();
+        Some(ids.join(", "))
+    };
+
+    // Repeat, progress and blocked markers, appended to one-line formats
+    let mut suffix = String::new();
+    if task.recurrence.is_some() {
+        suffix.push_str(&if use_color { " ↻".cyan().to_string() } else { " ↻".to_string() });
+    }
+    if !task.links.is_empty() {
+        suffix.push_str(&if use_color { " ↗".blue().to_string() } else { " ↗".to_string() });
+    }
+    if let Some(pct) = &progress_str {
+        suffix.push_str(&format!(" [{}]", pct));
+    }
+    if let Some(count) = format_checklist_count(task, use_color) {
+        suffix.push_str(&format!(" [{}]", count));
+    }
+    if let Some(start) = task.start.filter(|_| task.is_deferred()) {
+        let marker = format!("[starts {}]", format_date_human(start, true));
+        suffix.push(' ');
+        suffix.push_str(&if use_color { marker.dimmed().to_string() } else { marker });
+    }
+    if let Some(deadline) = &deadline_str {
+        suffix.push(' ');
+        suffix.push_str(deadline);
+    }
+    if let Some(ids) = &blockers_str {
+        let marker = format!("[blocked by {}]", ids);
+        suffix.push(' ');
+        suffix.push_str(&if use_color { marker.red().to_string() } else { marker });
+    }
+
+    let output = match mode {
         DisplayMode::Compact => {
-            format!("{} [{}] {}{}", checkbox, task.id, title, tags_str)
+            format!(
+                "{} [{}] {}{}{}{}",
+                checkbox, task.id, title, tags_str, priority_str, suffix
+            )
         }
         DisplayMode::Detailed => {
             let mut parts = vec![
@@ -97,10 +219,18 @@ pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
                 format!("Title: {}{}", title, tags_str),
             ];
 
+            if !task.uid.is_empty() {
+                parts.push(format!("UID: {}", task.uid));
+            }
+
             if let Some(cat) = &task.category {
                 parts.push(format!("Category: {}", cat));
             }
 
+            if let Some(p) = task.priority {
+                parts.push(format!("Priority: {} ({})", format_priority(p, use_color), p.name()));
+            }
+
             if !task.tags.is_empty() {
                 parts.push(format!("Tags: {}", task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")));
             }
@@ -108,21 +238,91 @@ pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
             if let Some(dt) = &task.scheduled {
                 parts.push(format!(
                     "Scheduled: {} {}",
-                    dt.format("%Y-%m-%d"),
+                    format_datetime_human(dt, false),
                     status_info
                 ));
             } else {
                 parts.push("Scheduled: (none)".to_string());
             }
 
-            parts.push(format!(
-                "Status: {}",
-                if task.is_complete {
-                    "Complete"
-                } else {
-                    "Incomplete"
+            if let Some(dt) = &task.deadline {
+                let warning = task
+                    .deadline_warning
+                    .map(|days| format!(", warns {} days ahead", days))
+                    .unwrap_or_default();
+                let state = match deadline_state {
+                    Some(DeadlineState::Passed) if use_color => " (missed)".red().bold().to_string(),
+                    Some(DeadlineState::Passed) => " (missed)".to_string(),
+                    Some(DeadlineState::Approaching) if use_color => " (approaching)".yellow().to_string(),
+                    Some(DeadlineState::Approaching) => " (approaching)".to_string(),
+                    _ => String::new(),
+                };
+                parts.push(format!(
+                    "Deadline: {}{}{}",
+                    format_datetime_human(dt, false),
+                    state,
+                    warning
+                ));
+            }
+
+            if let Some(start) = task.start {
+                let state = if task.is_deferred() { " (deferred)" } else { "" };
+                parts.push(format!("Starts: {}{}", format_date_human(start, false), state));
+            }
+
+            if let Some(rule) = &task.recurrence {
+                parts.push(format!("Repeats: {}", rule));
+            }
+
+            parts.push(format!("Status: {}", capitalize(task.status.name())));
+
+            if let Some(pct) = &progress_str {
+                parts.push(format!("Progress: {}", pct));
+            }
+
+            if !task.blocked_by.is_empty() {
+                let ids: Vec<String> = task.blocked_by.iter().map(|id| format!("#{}", id)).collect();
+                let state = match &blockers_str {
+                    Some(_) if use_color => " (blocked)".red().to_string(),
+                    Some(_) => " (blocked)".to_string(),
+                    None => String::new(),
+                };
+                parts.push(format!("Blocked by: {}{}", ids.join(", "), state));
+            }
+
+            for (label, time) in [
+                ("Created", task.created),
+                ("Updated", task.updated),
+                ("Completed", task.completed.filter(|_| task.is_complete())),
+            ] {
+                if let Some(dt) = time {
+                    parts.push(format!("{}: {}", label, dt.format("%Y-%m-%d %H:%M")));
                 }
-            ));
+            }
+
+            if let Some(count) = format_checklist_count(task, use_color) {
+                parts.push(format!("Checklist: {}", count));
+                parts.extend(format_checklist(task, use_color).into_iter().map(|line| format!("  {}", line)));
+            }
+
+            if !task.links.is_empty() {
+                parts.push("Links:".to_string());
+                parts.extend(task.links.iter().enumerate().map(|(i, link)| {
+                    let link = if use_color { link.blue().underline().to_string() } else { link.clone() };
+                    format!("  {}. {}", i + 1, link)
+                }));
+            }
+
+            if !task.fields.is_empty() {
+                parts.push("Fields:".to_string());
+                parts.extend(task.fields.iter().map(|(key, value)| format!("  {}: {}", key, value)));
+            }
+
+            if let Some(notes) = &task.notes {
+                parts.push("Notes:".to_string());
+                parts.extend(notes.lines().map(|line| format!("  {}", line)));
+            }
+
             parts.join("\n  ")
         }
         DisplayMode::Default => {
@@ -133,33 +333,259 @@ pub fn format_task(task: &Task, mode: DisplayMode, use_color: bool) -> String {
             };
 
             if status_info.is_empty() {
-                format!("{} {} {}{}", checkbox, id_str, title, tags_str)
+                format!(
+                    "{} {} {}{}{}{}",
+                    checkbox, id_str, title, tags_str, priority_str, suffix
+                )
             } else {
-                format!("{} {} {}{} {}", checkbox, id_str, title, tags_str, status_info)
+                format!(
+                    "{} {} {}{}{}{} {}",
+                    checkbox, id_str, title, tags_str, priority_str, suffix, status_info
+                )
             }
         }
+    };
+
+    if ctx.depth == 0 {
+        return output;
     }
+
+    // Indent subtasks under their parent
+    let indent = "   ".repeat(ctx.depth - 1);
+    let branch = if use_color {
+        "└─ ".dimmed().to_string()
+    } else {
+        "└─ ".to_string()
+    };
+    let first_prefix = format!("{}{}", indent, branch);
+    let rest_prefix = format!("{}   ", indent);
+
+    output
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                format!("{}{}", first_prefix, line)
+            } else {
+                format!("{}{}", rest_prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-/// Format a summary line for task list
-pub fn format_summary(total: usize, completed: usize, overdue: usize, use_color: bool) -> String {
-    let parts = vec![
-        format!("{} total", total),
-        if use_color {
-            format!("{} done", completed).green().to_string()
+/// Arrange tasks as a tree, keeping the given order among siblings
+///
+/// Tasks whose parent is not in the list are shown at the top level.
+pub fn tree_order<'a>(tasks: &[&'a Task]) -> Vec<(usize, &'a Task)> {
+    let ids: HashSet<u32> = tasks.iter().map(|t| t.id).collect();
+    let mut result = Vec::with_capacity(tasks.len());
+    let mut visited = HashSet::new();
+
+    fn visit<'a>(
+        task: &'a Task,
+        depth: usize,
+        tasks: &[&'a Task],
+        visited: &mut HashSet<u32>,
+        result: &mut Vec<(usize, &'a Task)>,
+    ) {
+        if !visited.insert(task.id) {
+            return;
+        }
+        result.push((depth, task));
+        for child in tasks.iter().filter(|t| t.parent == Some(task.id)) {
+            visit(child, depth + 1, tasks, visited, result);
+        }
+    }
+
+    for task in tasks {
+        if task.parent.is_none_or(|p| !ids.contains(&p)) {
+            visit(task, 0, tasks, &mut visited, &mut result);
+        }
+    }
+
+    // Anything left over is part of a parent cycle; show it flat
+    for task in tasks {
+        visit(task, 0, tasks, &mut visited, &mut result);
+    }
+
+    result
+}
+
+/// Value of a built-in column (id, uid, status, title, category, tags,
+/// priority, scheduled, start, deadline, repeat, created, updated,
+/// completed) or of the custom field with that name
+fn column_value(task: &Task, column: &str) -> String {
+    let date = |dt: &chrono::DateTime<Local>| {
+        if has_time_of_day(dt) {
+            wall_time(dt).format("%Y-%m-%d %H:%M").to_string()
         } else {
-            format!("{} done", completed)
-        },
-        if overdue > 0 {
-            if use_color {
-                format!("{} overdue", overdue).red().to_string()
+            wall_time(dt).format("%Y-%m-%d").to_string()
+        }
+    };
+    let stamp = |dt: &chrono::DateTime<Local>| Zone::current().to_naive(dt).format("%Y-%m-%d %H:%M").to_string();
+    match column {
+        "id" => task.id.to_string(),
+        "uid" => task.uid.clone(),
+        "status" => task.status.name().to_string(),
+        "title" => task.title.clone(),
+        "category" => task.category.clone().unwrap_or_default(),
+        "tags" => task.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "),
+        "priority" => task.priority.map(|p| p.to_string()).unwrap_or_default(),
+        "scheduled" => task.scheduled.as_ref().map(date).unwrap_or_default(),
+        "start" => task.start.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+        "deadline" => task.deadline.as_ref().map(date).unwrap_or_default(),
+        "repeat" => task.recurrence.as_ref().map(|rule| rule.to_string()).unwrap_or_default(),
+        "created" => task.created.as_ref().map(stamp).unwrap_or_default(),
+        "updated" => task.updated.as_ref().map(stamp).unwrap_or_default(),
+        "completed" => task.completed.as_ref().filter(|_| task.is_complete()).map(stamp).unwrap_or_default(),
+        key => task.field(key).unwrap_or_default().to_string(),
+    }
+}
+
+/// Format tasks as a table with a header line, indenting titles by tree depth
+pub fn format_table(rows: &[(usize, &Task)], columns: &[String], use_color: bool) -> Vec<String> {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|(depth, task)| {
+            columns
+                .iter()
+                .map(|column| match column.as_str() {
+                    "title" => format!("{}{}", "  ".repeat(*depth), task.title),
+                    column => column_value(task, column),
+                })
+                .collect()
+        })
+        .collect();
+    let headers: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([headers[i].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    // Pad every column but the last, so lines carry no trailing spaces
+    let line = |row: &[String]| -> String {
+        row.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                if i + 1 == row.len() {
+                    cell.clone()
+                } else {
+                    format!("{:<width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let header = line(&headers);
+    let mut lines = vec![if use_color { header.bold().to_string() } else { header }];
+    lines.extend(cells.iter().map(|row| line(row)));
+    lines
+}
+
+/// Format a priority marker, coloured by importance
+pub fn format_priority(priority: Priority, use_color: bool) -> String {
+    let marker = priority.to_string();
+    if !use_color {
+        return marker;
+    }
+
+    match priority {
+        Priority::Urgent => marker.red().bold().to_string(),
+        Priority::High => marker.yellow().bold().to_string(),
+        Priority::Medium => marker.blue().to_string(),
+        Priority::Low => marker.dimmed().to_string(),
+    }
+}
+
+/// Checkbox shown for a task status
+fn format_checkbox(status: Status) -> &'static str {
+    match status {
+        Status::Open => "[ ]",
+        Status::InProgress => "[/]",
+        Status::Waiting => "[?]",
+        Status::Done => "[✓]",
+        Status::Cancelled => "[-]",
+    }
+}
+
+/// Checked out of total checklist items, e.g. "1/3" (None without a checklist)
+fn format_checklist_count(task: &Task, use_color: bool) -> Option<String> {
+    let (done, total) = task.checklist_progress()?;
+    let count = format!("{}/{}", done, total);
+    Some(if !use_color {
+        count
+    } else if done == total {
+        count.green().to_string()
+    } else {
+        count.cyan().to_string()
+    })
+}
+
+/// Numbered checklist lines, e.g. "2. [✓] Charger"
+pub fn format_checklist(task: &Task, use_color: bool) -> Vec<String> {
+    task.checklist
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let status = if item.done { Status::Done } else { Status::Open };
+            let text = if item.done && use_color {
+                item.text.green().to_string()
             } else {
-                format!("{} overdue", overdue)
-            }
+                item.text.clone()
+            };
+            format!("{}. {} {}", i + 1, format_checkbox(status), text)
+        })
+        .collect()
+}
+
+/// Uppercase the first letter of a word
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Format a summary line for task list
+pub fn format_summary(tasks: &[&Task], use_color: bool) -> String {
+    let count = |status: Status| tasks.iter().filter(|t| t.status == status).count();
+    let overdue = tasks.iter().filter(|t| t.is_overdue()).count();
+
+    // Done is always shown, the other states only when present
+    let mut parts = vec![format!("{} total", tasks.len())];
+    for status in [Status::InProgress, Status::Waiting, Status::Done, Status::Cancelled] {
+        let n = count(status);
+        if n == 0 && status != Status::Done {
+            continue;
+        }
+        let part = format!("{} {}", n, status.name());
+        parts.push(match status {
+            Status::InProgress if use_color => part.cyan().to_string(),
+            Status::Waiting if use_color => part.yellow().to_string(),
+            Status::Done if use_color => part.green().to_string(),
+            Status::Cancelled if use_color => part.dimmed().to_string(),
+            _ => part,
+        });
+    }
+    if overdue > 0 {
+        parts.push(if use_color {
+            format!("{} overdue", overdue).red().to_string()
         } else {
-            String::new()
-        },
-    ];
+            format!("{} overdue", overdue)
+        });
+    }
 
     let summary: Vec<&str> = parts.iter().filter(|s| !s.is_empty()).map(|s| s.as_str()).collect();
 
@@ -169,12 +595,13 @@ pub fn format_summary(total: usize, completed: usize, overdue: usize, use_color:
 #[cfg(test)]
 mod tests {
     use super::*;
+    use taiga_core::{ChecklistItem, Recurrence};
 
     #[test]
     fn test_format_task_compact() {
         let task = Task::new("Test task").with_id(1);
 
-        let output = format_task(&task, DisplayMode::Compact, false);
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Compact, false);
         assert!(output.contains("[ ]"));
         assert!(output.contains("[1]"));
         assert!(output.contains("Test task"));
@@ -184,15 +611,207 @@ mod tests {
     fn test_format_task_completed() {
         let task = Task::new("Done task").with_id(2).with_complete(true);
 
-        let output = format_task(&task, DisplayMode::Default, false);
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
         assert!(output.contains("[✓]"));
     }
 
+    #[test]
+    fn test_format_task_priority() {
+        let task = Task::new("Urgent task")
+            .with_id(3)
+            .with_priority(Some(Priority::Urgent));
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert!(output.contains("Urgent task !1"));
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("Priority: !1 (urgent)"));
+        assert!(!detailed.contains("UID:"));
+
+        let task = task.with_uid("k3x9a2mf");
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("\n  UID: k3x9a2mf\n"));
+    }
+
+    #[test]
+    fn test_format_task_nested() {
+        let task = Task::new("Subtask").with_id(4);
+        let ctx = TaskContext {
+            depth: 2,
+            progress: Some(50),
+            ..Default::default()
+        };
+
+        let output = format_task(&task, &ctx, DisplayMode::Default, false);
+        assert!(output.starts_with("   └─ [ ] [4] Subtask"));
+        assert!(output.ends_with("[50%]"));
+    }
+
+    #[test]
+    fn test_format_task_blocked() {
+        let task = Task::new("Deploy").with_id(7).with_blocked_by(vec![3, 5]);
+        let ctx = TaskContext {
+            open_blockers: vec![5],
+            ..Default::default()
+        };
+
+        let output = format_task(&task, &ctx, DisplayMode::Default, false);
+        assert!(output.contains("Deploy [blocked by #5]"));
+
+        let detailed = format_task(&task, &ctx, DisplayMode::Detailed, false);
+        assert!(detailed.contains("Blocked by: #3, #5 (blocked)"));
+
+        let unblocked = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert!(!unblocked.contains("blocked"));
+    }
+
+    #[test]
+    fn test_format_task_recurring() {
+        let task = Task::new("Pay rent")
+            .with_id(8)
+            .with_recurrence(Some(Recurrence::parse("monthly").unwrap()));
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert!(output.contains("Pay rent ↻"));
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("Repeats: monthly"));
+    }
+
+    #[test]
+    fn test_format_task_notes() {
+        let task = Task::new("Release")
+            .with_id(9)
+            .with_notes(Some("Criteria:\n- changelog".to_string()));
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("\n  Notes:\n    Criteria:\n    - changelog"));
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert!(!output.contains("Criteria"));
+    }
+
+    #[test]
+    fn test_format_task_deferred() {
+        let tomorrow = Local::now().date_naive().succ_opt();
+        let task = Task::new("Renew passport").with_id(3).with_start(tomorrow);
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert_eq!(output, "[ ] [3] Renew passport [starts Tomorrow]");
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("Starts: "));
+        assert!(detailed.contains("(deferred)"));
+    }
+
+    #[test]
+    fn test_format_task_deadline() {
+        let in_two_days = Local::now().date_naive() + chrono::Duration::days(2);
+        let due = in_two_days.and_hms_opt(0, 0, 0).and_then(|dt| dt.and_local_timezone(Local).single());
+        let task = Task::new("Grant application").with_id(5).with_deadline(due);
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Compact, false);
+        assert_eq!(
+            output,
+            format!("[ ] [5] Grant application [deadline {}]", in_two_days.format("%Y-%m-%d"))
+        );
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("(approaching)"));
+
+        let relaxed = TaskContext::default().with_deadline_warning_days(1);
+        let detailed = format_task(&task, &relaxed, DisplayMode::Detailed, false);
+        assert!(!detailed.contains("(approaching)"));
+    }
+
+    #[test]
+    fn test_format_task_links() {
+        let task = Task::new("Fix login")
+            .with_id(7)
+            .with_links(vec!["https://tracker.example/issues/42".to_string()]);
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert_eq!(output, "[ ] [7] Fix login ↗");
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("\n  Links:\n    1. https://tracker.example/issues/42"));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let mut parent = Task::new("Invoice").with_id(1);
+        parent.set_field("customer", Some("Acme Corp")).unwrap();
+        let child = Task::new("Send").with_id(2).with_priority(Some(Priority::High));
+        let columns: Vec<String> = ["id", "title", "priority", "customer"].map(String::from).to_vec();
+
+        let lines = format_table(&[(0, &parent), (1, &child)], &columns, false);
+        assert_eq!(
+            lines,
+            vec![
+                "ID  TITLE    PRIORITY  CUSTOMER",
+                "1   Invoice            Acme Corp",
+                "2     Send   !2",
+            ]
+        );
+
+        let detailed = format_task(&parent, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("\n  Fields:\n    customer: Acme Corp"));
+    }
+
+    #[test]
+    fn test_format_task_checklist() {
+        let task = Task::new("Pack").with_id(4).with_checklist(vec![
+            ChecklistItem::new("Passport").with_done(true),
+            ChecklistItem::new("Charger"),
+        ]);
+
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Default, false);
+        assert_eq!(output, "[ ] [4] Pack [1/2]");
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("\n  Checklist: 1/2\n    1. [✓] Passport\n    2. [ ] Charger"));
+    }
+
+    #[test]
+    fn test_tree_order() {
+        let parent = Task::new("Parent").with_id(1);
+        let other = Task::new("Other").with_id(2);
+        let child = Task::new("Child").with_id(3).with_parent(Some(1));
+        let orphan = Task::new("Orphan").with_id(4).with_parent(Some(99));
+        let tasks = vec![&parent, &other, &child, &orphan];
+
+        let ordered: Vec<(usize, u32)> = tree_order(&tasks)
+            .into_iter()
+            .map(|(depth, t)| (depth, t.id))
+            .collect();
+
+        assert_eq!(ordered, vec![(0, 1), (1, 3), (0, 2), (0, 4)]);
+    }
+
     #[test]
     fn test_format_summary() {
-        let summary = format_summary(10, 5, 2, false);
-        assert!(summary.contains("10 total"));
-        assert!(summary.contains("5 done"));
-        assert!(summary.contains("2 overdue"));
+        let past = Local::now() - chrono::Duration::days(3);
+        let tasks = [
+            Task::new("Open").with_scheduled(Some(past)),
+            Task::new("Started").with_status(Status::InProgress),
+            Task::new("Done").with_status(Status::Done),
+            Task::new("Dropped").with_status(Status::Cancelled).with_scheduled(Some(past)),
+        ];
+        let refs: Vec<&Task> = tasks.iter().collect();
+        let summary = format_summary(&refs, false);
+        assert_eq!(summary, "[4 total | 1 in progress | 1 done | 1 cancelled | 1 overdue]");
+
+        let summary = format_summary(&refs[..1], false);
+        assert_eq!(summary, "[1 total | 0 done | 1 overdue]");
+    }
+
+    #[test]
+    fn test_format_task_statuses() {
+        let task = Task::new("Waiting on reply").with_id(6).with_status(Status::Waiting);
+        let output = format_task(&task, &TaskContext::default(), DisplayMode::Compact, false);
+        assert!(output.starts_with("[?] [6]"));
+
+        let detailed = format_task(&task, &TaskContext::default(), DisplayMode::Detailed, false);
+        assert!(detailed.contains("Status: Waiting"));
     }
 }
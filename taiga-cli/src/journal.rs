@@ -0,0 +1,198 @@
+//! Append-only journal of changes to the task file
+//!
+//! Every save records the tasks it changed as one JSON line, next to the
+//! task file. Undoing or redoing a step appends a new entry as well, so the
+//! journal is never rewritten and doubles as an activity log.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use taiga_core::history::Change;
+
+use crate::error::Result;
+
+/// What kind of operation a journal entry records
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum EntryKind {
+    /// A regular command
+    #[default]
+    Edit,
+    /// Reverting the entry with the given sequence number
+    Undo { of: u64 },
+    /// Reapplying the entry with the given sequence number
+    Redo { of: u64 },
+}
+
+/// A single recorded operation
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Entry {
+    pub seq: u64,
+    pub at: DateTime<Local>,
+    /// Command line that made the change, e.g. "check 3"
+    pub command: String,
+    #[serde(flatten)]
+    pub kind: EntryKind,
+    pub changes: Vec<Change>,
+    /// Changes to the archive file, from archiving or restoring tasks
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archive: Vec<Change>,
+}
+
+/// Journal file adapter
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Create a journal adapter for the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Read all entries, oldest first
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = std::fs::File::open(&self.path)?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => eprintln!("Warning: Skipping invalid journal entry: {}", e),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Append an entry and return it
+    pub fn append(
+        &self,
+        command: impl Into<String>,
+        kind: EntryKind,
+        changes: Vec<Change>,
+        archive: Vec<Change>,
+    ) -> Result<Entry> {
+        let seq = self.entries()?.last().map_or(1, |entry| entry.seq + 1);
+        let entry = Entry {
+            seq,
+            at: Local::now(),
+            command: command.into(),
+            kind,
+            changes,
+            archive,
+        };
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(entry)
+    }
+}
+
+/// Sequence numbers of the entries that can be undone and redone, most
+/// recent last
+///
+/// A new edit after an undo discards the redo history, like in an editor.
+fn stacks(entries: &[Entry]) -> (Vec<u64>, Vec<u64>) {
+    let mut done = Vec::new();
+    let mut undone = Vec::new();
+    for entry in entries {
+        match entry.kind {
+            EntryKind::Edit => {
+                done.push(entry.seq);
+                undone.clear();
+            }
+            EntryKind::Undo { of } => {
+                done.retain(|&seq| seq != of);
+                undone.push(of);
+            }
+            EntryKind::Redo { of } => {
+                undone.retain(|&seq| seq != of);
+                done.push(of);
+            }
+        }
+    }
+    (done, undone)
+}
+
+/// The entry `taiga undo` would revert next
+pub fn undo_target(entries: &[Entry]) -> Option<&Entry> {
+    let (done, _) = stacks(entries);
+    done.last().and_then(|&seq| find(entries, seq))
+}
+
+/// The entry `taiga redo` would reapply next
+pub fn redo_target(entries: &[Entry]) -> Option<&Entry> {
+    let (_, undone) = stacks(entries);
+    undone.last().and_then(|&seq| find(entries, seq))
+}
+
+/// Look up an entry by sequence number
+pub fn find(entries: &[Entry], seq: u64) -> Option<&Entry> {
+    entries.iter().find(|entry| entry.seq == seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("taiga-journal-{}-{}.journal", name, std::process::id()));
+        std::fs::remove_file(&path).ok();
+        path
+    }
+
+    #[test]
+    fn test_append_and_read() {
+        let path = temp_path("append");
+        let journal = Journal::new(&path);
+        journal.append("add Buy milk", EntryKind::Edit, Vec::new(), Vec::new()).unwrap();
+        journal
+            .append("undo", EntryKind::Undo { of: 1 }, Vec::new(), Vec::new())
+            .unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].seq, 1);
+        assert_eq!(entries[0].command, "add Buy milk");
+        assert_eq!(entries[1].kind, EntryKind::Undo { of: 1 });
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_undo_redo_targets() {
+        let path = temp_path("targets");
+        let journal = Journal::new(&path);
+        for command in ["add a", "add b", "add c"] {
+            journal.append(command, EntryKind::Edit, Vec::new(), Vec::new()).unwrap();
+        }
+        journal.append("undo", EntryKind::Undo { of: 3 }, Vec::new(), Vec::new()).unwrap();
+        journal.append("undo", EntryKind::Undo { of: 2 }, Vec::new(), Vec::new()).unwrap();
+
+        let entries = journal.entries().unwrap();
+        assert_eq!(undo_target(&entries).map(|e| e.seq), Some(1));
+        assert_eq!(redo_target(&entries).map(|e| e.seq), Some(2));
+
+        // A new edit discards what could be redone
+        journal.append("add d", EntryKind::Edit, Vec::new(), Vec::new()).unwrap();
+        let entries = journal.entries().unwrap();
+        assert_eq!(undo_target(&entries).map(|e| e.seq), Some(6));
+        assert!(redo_target(&entries).is_none());
+
+        std::fs::remove_file(&path).ok();
+    }
+}
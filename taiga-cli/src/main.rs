@@ -1,35 +1,64 @@
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use chrono::{Local, TimeZone};
+use chrono::{DateTime, Duration, Local};
 use clap::Parser;
 
-use taiga_core::date::parse_date;
+use taiga_core::date::{
+    floating_date, format_date_human, format_datetime_human, parse_date, parse_date_range, parse_datetime, wall_time,
+};
 use taiga_core::filter::{FilterExt, TaskFilter, TaskSort};
-
-use crate::cli::{Cli, Commands, SortBy, TagAction};
-use crate::display::{format_summary, format_task, supports_color, DisplayMode};
+use taiga_core::{category, field};
+use taiga_core::history::{self, Change};
+use taiga_core::{
+    CoreError, Deferred, Priority, Query, QuickAdd, Recurrence, Status, Task, TaskCollection, TaskRef,
+    TemplateTask, WorkCalendar, Zone,
+};
+
+use crate::cli::{
+    ChecklistAction, Cli, Commands, DependAction, SortBy, SortKey, TagAction, TemplateAction,
+};
+use crate::display::{
+    format_checklist, format_summary, format_table, format_task, supports_color, tree_order,
+    DisplayMode, TaskContext,
+};
 use crate::error::{CliError, Result};
+use crate::journal::{Entry, EntryKind};
 use crate::plugin::{CommandResult, PluginContext};
 use crate::plugin_manager::PluginManager;
 use crate::storage::MarkdownStorage;
+use crate::templates::TemplateStore;
 
 mod cli;
 mod config;
 mod display;
 mod error;
+mod journal;
 mod plugin;
 mod plugin_manager;
 mod storage;
+mod templates;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     let cfg: config::Config = confy::load("taiga", None)?;
+    let zone = cfg.zone()?;
+    zone.install();
+    let locale = cfg.locale()?;
+    locale.install();
+    let holidays_path = cfg.holidays_path();
+    load_work_calendar(&cfg.working_week, holidays_path.as_deref())?.install();
     let mut tasks_file_path = PathBuf::from(&cfg.data_directory);
     tasks_file_path.push(&cfg.task_filename);
 
-    let storage = MarkdownStorage::new(&tasks_file_path);
+    // Changes are journaled under the command line that made them
+    let command_line = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    let storage = MarkdownStorage::new(&tasks_file_path)
+        .with_reuse_ids(cfg.reuse_ids)
+        .with_archive_after_days(cfg.archive_after_days)
+        .with_journal(command_line);
 
     // Initialize plugin manager
     let mut plugin_manager = PluginManager::new();
@@ -58,41 +87,126 @@ fn main() -> Result<()> {
 
     // Create plugin context with task filename
     let mut plugin_ctx = PluginContext::new(PathBuf::from(&cfg.data_directory))
-        .with_extra("task_filename", &cfg.task_filename);
+        .with_extra("task_filename", &cfg.task_filename)
+        .with_extra("working_week", &cfg.working_week)
+        .with_extra("time_zone", zone.to_string())
+        .with_extra("locale", locale.code);
+    if let Some(path) = &holidays_path {
+        plugin_ctx = plugin_ctx.with_extra("holidays_file", path.to_string_lossy());
+    }
 
     match cli.command {
-        Commands::Add { title, on, date, category, tag } => {
+        Commands::Add {
+            title,
+            raw,
+            on,
+            date,
+            start,
+            deadline,
+            warn,
+            category,
+            tag,
+            priority,
+            parent,
+            repeat,
+            set,
+        } => {
             let mut collection = storage.load()?;
-            let title_str = title.join(" ");
 
-            // Use --on or --date (--on takes precedence)
+            // Use --on or --date (--on takes precedence), then a date from
+            // the title
             let date_str = on.or(date);
-            let scheduled = if let Some(date_input) = date_str {
-                Some(
-                    parse_date(&date_input)?
-                        .and_hms_opt(0, 0, 0)
-                        .and_then(|dt| Local.from_local_datetime(&dt).single())
-                        .ok_or_else(|| CliError::parse("Invalid date/time"))?,
-                )
-            } else {
-                None
+            let quick = read_title(&title.join(" "), raw, date_str.is_some());
+            if quick.title.is_empty() {
+                return Err(CliError::validation("title", "Task title cannot be empty"));
+            }
+            let title_str = quick.title;
+            let mut tags = quick.tags;
+            for t in tag {
+                if !tags.contains(&t) {
+                    tags.push(t);
+                }
+            }
+            let tag = tags;
+
+            let recurrence = repeat.as_deref().map(Recurrence::parse).transpose()?;
+
+            // Repeating tasks without a date start at their first
+            // occurrence from today
+            let scheduled = match (date_str, &recurrence) {
+                (Some(date_input), _) => Some(parse_datetime(&date_input)?),
+                (None, _) if quick.scheduled.is_some() => quick.scheduled,
+                (None, Some(rule)) => rule
+                    .first_on_or_after(Zone::current().today())
+                    .and_then(floating_date),
+                (None, None) => None,
             };
 
+            let start = start.as_deref().map(parse_date).transpose()?;
+            let deadline = deadline.as_deref().map(parse_datetime).transpose()?;
+
             // Handle category - "none" means uncategorized (None)
-            let task_category = category.filter(|c| c.to_lowercase() != "none");
+            let task_category = match category {
+                Some(c) if c.to_lowercase() == "none" => None,
+                Some(c) => category::normalize(&c),
+                None => quick.category,
+            };
 
-            collection.add_with_category_tags(title_str.clone(), scheduled, task_category.clone(), tag.clone());
+            let task_priority = priority.as_deref().map(Priority::parse).transpose()?.or(quick.priority);
+
+            // Subtasks take their category from the parent
+            let parent = parent.map(|p| collection.resolve(&p)).transpose()?;
+            let task_category = match parent {
+                Some(parent_id) => collection.get_or_err(parent_id)?.category.clone(),
+                None => task_category,
+            };
+
+            let mut task = Task::new(title_str.clone())
+                .with_scheduled(scheduled)
+                .with_start(start)
+                .with_deadline(deadline)
+                .with_deadline_warning(warn)
+                .with_category(task_category.clone())
+                .with_tags(tag.clone())
+                .with_priority(task_priority)
+                .with_recurrence(recurrence.clone());
+            for (key, value) in &set {
+                task.set_field(key, Some(value))?;
+            }
+            let fields = task.fields.clone();
+            let id = collection.add_task(task);
+            if parent.is_some() {
+                collection.set_parent(id, parent)?;
+            }
             storage.save(&collection)?;
 
             let mut msg = format!("Task added: {}", title_str);
+            if let Some(parent_id) = parent {
+                msg.push_str(&format!(" (subtask of #{})", parent_id));
+            }
+            if let Some(p) = task_priority {
+                msg.push_str(&format!(" {}", p));
+            }
             if let Some(cat) = task_category {
                 msg.push_str(&format!(" [{}]", cat));
             }
             if !tag.is_empty() {
                 msg.push_str(&format!(" #{}", tag.join(" #")));
             }
+            for (key, value) in &fields {
+                msg.push_str(&format!(" {}", field::format(key, value)));
+            }
             if let Some(dt) = scheduled {
-                msg.push_str(&format!(" (scheduled: {})", dt.format("%Y-%m-%d")));
+                msg.push_str(&format!(" (scheduled: {})", format_datetime_human(&dt, false)));
+            }
+            if let Some(rule) = &recurrence {
+                msg.push_str(&format!(" (repeats {})", rule));
+            }
+            if let Some(start) = start {
+                msg.push_str(&format!(" (starts: {})", format_date_human(start, false)));
+            }
+            if let Some(dt) = deadline {
+                msg.push_str(&format!(" (deadline: {})", format_datetime_human(&dt, false)));
             }
             println!("{}", msg);
         }
@@ -103,7 +217,12 @@ fn main() -> Result<()> {
             scheduled,
             unscheduled,
             overdue,
+            due,
+            before,
+            after,
+            on,
             search,
+            query,
             sort,
             reverse,
             compact,
@@ -111,8 +230,27 @@ fn main() -> Result<()> {
             no_color,
             category,
             tag,
+            priority,
+            flat,
+            actionable,
+            blocked,
+            completed_since,
+            created_before,
+            status,
+            archived,
+            all,
+            deferred,
+            deadline,
+            past_deadline,
+            due_soon,
+            field: field_filters,
+            columns,
         } => {
-            let collection = storage.load()?;
+            let collection = if archived {
+                storage.archive().load()?
+            } else {
+                storage.load()?
+            };
 
             // Determine filters
             let filter_checked = if checked {
@@ -133,33 +271,96 @@ fn main() -> Result<()> {
 
             // Get sort key
             let sort_by = match sort {
-                SortBy::Id => TaskSort::Id,
-                SortBy::Date => TaskSort::Date,
-                SortBy::Name => TaskSort::Name,
-                SortBy::Status => TaskSort::Status,
+                SortKey::Builtin(SortBy::Id) => TaskSort::Id,
+                SortKey::Builtin(SortBy::Date) => TaskSort::Date,
+                SortKey::Builtin(SortBy::Name) => TaskSort::Name,
+                SortKey::Builtin(SortBy::Status) => TaskSort::Status,
+                SortKey::Builtin(SortBy::Priority) => TaskSort::Priority,
+                SortKey::Builtin(SortBy::Created) => TaskSort::Created,
+                SortKey::Builtin(SortBy::Updated) => TaskSort::Updated,
+                SortKey::Builtin(SortBy::Completed) => TaskSort::Completed,
+                SortKey::Builtin(SortBy::Deadline) => TaskSort::Deadline,
+                SortKey::Field(key) => TaskSort::Field(key),
             };
 
+            // "key" requires the field, "key=value" a value
+            let fields = field_filters
+                .iter()
+                .map(|f| {
+                    let (key, value) = f.split_once('=').map_or((f.as_str(), None), |(k, v)| (k, Some(v)));
+                    Ok((field::normalize_key(key)?, value.map(|v| v.trim().to_string())))
+                })
+                .collect::<taiga_core::Result<Vec<_>>>()?;
+            let columns = columns
+                .iter()
+                .map(|column| field::normalize_key(column))
+                .collect::<taiga_core::Result<Vec<_>>>()?;
+
+            // Scheduled date ranges, all of which must match
+            let date_ranges = [
+                due,
+                before.map(|date| format!("before {}", date)),
+                after.map(|date| format!("after {}", date)),
+                on,
+            ]
+            .into_iter()
+            .flatten()
+            .map(|range| parse_date_range(&range))
+            .collect::<taiga_core::Result<Vec<_>>>()?;
+
             // Build filter with category and tag support
             let mut filter = TaskFilter::new()
                 .with_checked(filter_checked)
                 .with_scheduled(filter_scheduled)
                 .with_overdue(overdue)
+                .with_scheduled_within(date_ranges)
                 .with_search(search)
                 .sort_by(sort_by)
-                .with_reverse(reverse);
+                .with_reverse(reverse)
+                .with_completed_since(completed_since.as_deref().map(parse_datetime).transpose()?)
+                .with_created_before(created_before.as_deref().map(parse_datetime).transpose()?)
+                .with_statuses(status.iter().map(|s| Status::parse(s)).collect::<taiga_core::Result<_>>()?)
+                .with_deferred(if deferred {
+                    Deferred::Only
+                } else if all || query.is_some() {
+                    Deferred::Include
+                } else {
+                    Deferred::Hide
+                })
+                .with_deadline(deadline.then_some(true))
+                .with_past_deadline(past_deadline)
+                .with_deadline_soon(due_soon.then_some(cfg.deadline_warning_days))
+                .with_fields(fields)
+                .with_query(query.as_deref().map(Query::parse).transpose()?);
 
             // Handle category filter
             if let Some(cat) = category {
                 if cat.to_lowercase() == "none" {
                     filter = filter.uncategorized();
-                } else {
-                    filter = filter.in_category(cat);
+                } else if let Some(path) = category::normalize(&cat) {
+                    filter = filter.in_category(path);
                 }
             }
 
             // Handle tag filters
             filter = filter.with_tags(tag);
 
+            // Handle dependency filters
+            if actionable {
+                filter = filter.actionable_only();
+            } else if blocked {
+                filter = filter.blocked_only();
+            }
+
+            // Handle priority filter
+            if let Some(p) = priority {
+                if p.to_lowercase() == "none" {
+                    filter = filter.without_priority();
+                } else {
+                    filter = filter.with_priority(Priority::parse(&p)?);
+                }
+            }
+
             let tasks = collection.get_filtered(&filter);
 
             if tasks.is_empty() {
@@ -176,33 +377,71 @@ fn main() -> Result<()> {
 
                 let use_color = !no_color && supports_color();
 
-                for task in &tasks {
-                    println!("{}", format_task(task, mode, use_color));
+                let rows = if flat {
+                    tasks.iter().map(|t| (0, *t)).collect()
+                } else {
+                    tree_order(&tasks)
+                };
+
+                if columns.is_empty() {
+                    for (depth, task) in rows {
+                        let ctx = TaskContext::new(&collection, task, depth)
+                            .with_deadline_warning_days(cfg.deadline_warning_days);
+                        println!("{}", format_task(task, &ctx, mode, use_color));
+                    }
+                } else {
+                    for line in format_table(&rows, &columns, use_color) {
+                        println!("{}", line);
+                    }
                 }
 
                 // Show summary
                 println!();
-                let summary = format_summary(
-                    tasks.len(),
-                    tasks.iter().filter(|t| t.is_complete).count(),
-                    tasks.iter().filter(|t| t.is_overdue()).count(),
-                    use_color,
-                );
-                println!("{}", summary);
+                println!("{}", format_summary(&tasks, use_color));
+            }
+        }
+
+        Commands::Show { id, no_color } => {
+            let collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_or_err(id)?;
+            let use_color = !no_color && supports_color();
+
+            let ctx = TaskContext::new(&collection, task, 0)
+                .with_deadline_warning_days(cfg.deadline_warning_days);
+            println!("{}", format_task(task, &ctx, DisplayMode::Detailed, use_color));
+
+            let children = collection.children(id);
+            if !children.is_empty() {
+                println!("  Subtasks:");
+                for child in children {
+                    let ctx = TaskContext::new(&collection, child, 0)
+                        .with_deadline_warning_days(cfg.deadline_warning_days);
+                    println!("    {}", format_task(child, &ctx, DisplayMode::Compact, use_color));
+                }
             }
         }
 
         Commands::Check { id } => {
             let mut collection = storage.load()?;
-            let task = collection.get_mut_or_err(id)?;
-            task.toggle_complete();
-            let status = if task.is_complete { "done" } else { "open" };
+            let id = collection.resolve(&id)?;
+            let next_id = collection.toggle_complete(id, Zone::current().today())?;
+            let task = collection.get_or_err(id)?;
+            let status = if task.is_complete() { "done" } else { "open" };
             println!("Marked task #{} as {}: {}", task.id, status, task.title);
+            print_next_occurrence(&collection, next_id);
             storage.save(&collection)?;
         }
 
+        Commands::Start { id } => change_status(&storage, &id, Status::InProgress)?,
+
+        Commands::Wait { id } => change_status(&storage, &id, Status::Waiting)?,
+
+        Commands::Cancel { id } => change_status(&storage, &id, Status::Cancelled)?,
+
         Commands::Remove { id } => {
             let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
             let removed_task = collection
                 .remove(id)
                 .ok_or(CliError::TaskNotFound(id))?;
@@ -210,16 +449,37 @@ fn main() -> Result<()> {
             storage.save(&collection)?;
         }
 
-        Commands::Edit { id, name, date } => {
-            if name.is_none() && date.is_none() {
+        Commands::Edit { id, name, date, start, deadline, warn, priority, parent, repeat, set } => {
+            if name.is_none()
+                && date.is_none()
+                && start.is_none()
+                && deadline.is_none()
+                && warn.is_none()
+                && priority.is_none()
+                && parent.is_none()
+                && repeat.is_none()
+                && set.is_empty()
+            {
                 return Err(CliError::validation(
                     "edit",
-                    "At least one of --name or --date must be provided",
+                    "At least one of --name, --date, --start, --deadline, --warn, --priority, --parent, --repeat or --set must be provided",
                 ));
             }
 
             let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+
+            if let Some(parent_str) = parent {
+                let new_parent = if parent_str.to_lowercase() == "none" {
+                    None
+                } else {
+                    Some(collection.resolve(&parent_str.parse()?)?)
+                };
+                collection.set_parent(id, new_parent)?;
+            }
+
             let task = collection.get_mut_or_err(id)?;
+            let unchanged = task.clone();
 
             if let Some(new_name) = name {
                 task.title = new_name;
@@ -229,42 +489,220 @@ fn main() -> Result<()> {
                 if date_str.to_lowercase() == "none" {
                     task.scheduled = None;
                 } else {
-                    let date = parse_date(&date_str)?;
-                    task.scheduled = date
-                        .and_hms_opt(0, 0, 0)
-                        .and_then(|dt| Local.from_local_datetime(&dt).single());
+                    task.scheduled = Some(parse_datetime(&date_str)?);
+                }
+            }
+
+            if let Some(start_str) = start {
+                task.start = if start_str.to_lowercase() == "none" {
+                    None
+                } else {
+                    Some(parse_date(&start_str)?)
+                };
+            }
+
+            if let Some(deadline_str) = deadline {
+                if deadline_str.to_lowercase() == "none" {
+                    task.deadline = None;
+                    task.deadline_warning = None;
+                } else {
+                    task.deadline = Some(parse_datetime(&deadline_str)?);
+                }
+            }
+
+            if warn.is_some() {
+                if task.deadline.is_none() {
+                    return Err(CliError::validation("warn", "The task has no deadline (set one with --deadline)"));
+                }
+                task.deadline_warning = warn;
+            }
+
+            if let Some(p) = priority {
+                task.priority = if p.to_lowercase() == "none" {
+                    None
+                } else {
+                    Some(Priority::parse(&p)?)
+                };
+            }
+
+            if let Some(rule) = repeat {
+                task.recurrence = if rule.to_lowercase() == "none" {
+                    None
+                } else {
+                    Some(Recurrence::parse(&rule)?)
+                };
+            }
+
+            for (key, value) in &set {
+                let value = Some(value.trim()).filter(|v| !v.is_empty());
+                if task.set_field(key, value)?.is_none() && value.is_none() {
+                    return Err(CliError::validation(
+                        "set",
+                        format!("Task #{} has no field '{}'", task.id, key),
+                    ));
                 }
             }
+            if *task != unchanged {
+                task.touch();
+            }
 
             println!("Updated task #{}: {}", task.id, task.title);
             if let Some(dt) = &task.scheduled {
-                println!("  Scheduled: {}", dt.format("%Y-%m-%d"));
+                println!("  Scheduled: {}", format_datetime_human(dt, false));
+            }
+            if let Some(start) = task.start {
+                println!("  Starts: {}", format_date_human(start, false));
+            }
+            if let Some(dt) = &task.deadline {
+                println!("  Deadline: {}", format_datetime_human(dt, false));
+            }
+            if let Some(p) = task.priority {
+                println!("  Priority: {} ({})", p, p.name());
+            }
+            if let Some(parent_id) = task.parent {
+                println!("  Subtask of: #{}", parent_id);
+            }
+            if let Some(rule) = &task.recurrence {
+                println!("  Repeats: {}", rule);
+            }
+            if !task.fields.is_empty() {
+                let fields: Vec<String> = task.fields.iter().map(|(k, v)| field::format(k, v)).collect();
+                println!("  Fields: {}", fields.join(" "));
             }
             storage.save(&collection)?;
         }
 
-        Commands::Reschedule { id, date } => {
+        Commands::Note { id, clear } => {
+            let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_mut_or_err(id)?;
+
+            if clear {
+                if task.notes.is_some() {
+                    task.set_notes(None);
+                    task.touch();
+                }
+                println!("Cleared notes for task #{}: {}", task.id, task.title);
+            } else {
+                let current = task.notes.clone().unwrap_or_default();
+                let edited = edit_in_editor(&current, &format!("taiga-note-{}", id))?;
+                if edited.trim_end() == current {
+                    println!("Notes unchanged for task #{}: {}", task.id, task.title);
+                    return Ok(());
+                }
+                task.set_notes(Some(edited));
+                task.touch();
+                println!("Updated notes for task #{}: {}", task.id, task.title);
+            }
+            storage.save(&collection)?;
+        }
+
+        Commands::Checklist { id, action } => {
+            let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_or_err(id)?;
+
+            let Some(action) = action else {
+                if task.checklist.is_empty() {
+                    println!("Task #{} has no checklist: {}", task.id, task.title);
+                } else {
+                    println!("Checklist for task #{}: {}", task.id, task.title);
+                    for line in format_checklist(task, supports_color()) {
+                        println!("  {}", line);
+                    }
+                }
+                return Ok(());
+            };
+
+            let task = collection.get_mut_or_err(id)?;
+            match action {
+                ChecklistAction::Add { text } => {
+                    let number = task.add_checklist_item(&text.join(" "))?;
+                    println!("Added item {} to task #{}: {}", number, task.id, task.title);
+                }
+                ChecklistAction::Check { item } => {
+                    let done = task.toggle_checklist_item(item)?;
+                    let state = if done { "Checked" } else { "Unchecked" };
+                    println!("{} item {} of task #{}: {}", state, item, task.id, task.checklist[item - 1].text);
+                }
+                ChecklistAction::Remove { item } => {
+                    let removed = task.remove_checklist_item(item)?;
+                    println!("Removed item {} from task #{}: {}", item, task.id, removed.text);
+                }
+            }
+            task.touch();
+            storage.save(&collection)?;
+        }
+
+        Commands::Attach { id, target, remove } => {
+            let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_mut_or_err(id)?;
+
+            if remove {
+                let removed = task.remove_link(&target)?;
+                println!("Detached from task #{}: {}", task.id, removed);
+            } else {
+                let target = resolve_link_target(&target)?;
+                if !task.add_link(&target)? {
+                    println!("Task #{} already links to {}", task.id, target);
+                    return Ok(());
+                }
+                println!("Attached to task #{}: {}", task.id, target);
+            }
+            task.touch();
+            storage.save(&collection)?;
+        }
+
+        Commands::Open { id, link } => {
+            let collection = storage.load()?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_or_err(id)?;
+
+            let number = link.unwrap_or(1);
+            let target = number
+                .checked_sub(1)
+                .and_then(|index| task.links.get(index))
+                .ok_or_else(|| {
+                    let message = if task.links.is_empty() {
+                        format!("Task #{} has no links (add one with 'taiga attach')", task.id)
+                    } else {
+                        format!("Task #{} has no link {}", task.id, number)
+                    };
+                    CliError::validation("link", message)
+                })?;
+            println!("Opening {}", target);
+            open_with_system(target)?;
+        }
+
+        Commands::Reschedule { id, date, workday, any_day } => {
             let mut collection = storage.load()?;
             let date_str = date.join(" ");
 
+            let id = collection.resolve(&id)?;
             let task = collection.get_mut_or_err(id)?;
 
+            let unchanged = task.scheduled;
             if date_str.to_lowercase() == "none" {
                 task.scheduled = None;
                 println!("Cleared schedule for task #{}: {}", task.id, task.title);
             } else {
-                let parsed_date = parse_date(&date_str)?;
-                task.scheduled = parsed_date
-                    .and_hms_opt(0, 0, 0)
-                    .and_then(|dt| Local.from_local_datetime(&dt).single());
+                let mut parsed = parse_datetime(&date_str)?;
+                if workday || (cfg.reschedule_skip_non_working && !any_day) {
+                    parsed = next_working_datetime(parsed)?;
+                }
+                task.scheduled = Some(parsed);
 
                 println!(
                     "Rescheduled task #{} to {}: {}",
                     task.id,
-                    parsed_date.format("%Y-%m-%d"),
+                    format_datetime_human(&parsed, false),
                     task.title
                 );
             }
+            if task.scheduled != unchanged {
+                task.touch();
+            }
             storage.save(&collection)?;
         }
 
@@ -272,9 +710,13 @@ fn main() -> Result<()> {
             let mut collection = storage.load()?;
             let new_name = name.join(" ");
 
+            let id = collection.resolve(&id)?;
             let task = collection.get_mut_or_err(id)?;
             let old_name = task.title.clone();
-            task.title = new_name.clone();
+            if task.title != new_name {
+                task.title = new_name.clone();
+                task.touch();
+            }
             println!("Renamed task #{}:", id);
             println!("  From: {}", old_name);
             println!("  To:   {}", new_name);
@@ -290,21 +732,78 @@ fn main() -> Result<()> {
             }
 
             let mut collection = storage.load()?;
-            let count = collection.count_completed();
+            let count = collection.count_with_status(Status::Done)
+                + collection.count_with_status(Status::Cancelled);
 
             if count == 0 {
-                println!("No completed tasks to remove.");
+                println!("No done or cancelled tasks to remove.");
                 return Ok(());
             }
 
-            if !force && !confirm(&format!("Remove {} completed task(s)?", count))? {
+            if !force && !confirm(&format!("Remove {} done or cancelled task(s)?", count))? {
                 println!("Cancelled.");
                 return Ok(());
             }
 
             let removed = collection.remove_checked();
             storage.save(&collection)?;
-            println!("Removed {} completed task(s).", removed);
+            println!("Removed {} done or cancelled task(s).", removed);
+        }
+
+        Commands::Archive { ids, older_than } => {
+            let mut collection = storage.load()?;
+
+            let ids = if ids.is_empty() {
+                let cutoff = older_than.map(|days| Local::now() - Duration::days(days.into()));
+                collection.closed_ids(cutoff)
+            } else {
+                let mut resolved = Vec::new();
+                for task_ref in &ids {
+                    let task = collection.get_or_err(collection.resolve(task_ref)?)?;
+                    if !task.status.is_closed() {
+                        return Err(CliError::validation(
+                            "archive",
+                            format!("Task #{} is {}; only done or cancelled tasks can be archived", task.id, task.status),
+                        ));
+                    }
+                    resolved.push(task.id);
+                }
+                resolved
+            };
+
+            if ids.is_empty() {
+                println!("No tasks to archive.");
+                return Ok(());
+            }
+
+            let mut archived = storage.archive().load()?;
+            collection.move_tasks(&mut archived, &ids);
+            storage.save_with_archive(&collection, &archived)?;
+            println!(
+                "Archived {} task(s) to {}",
+                ids.len(),
+                storage.archive_path().display()
+            );
+        }
+
+        Commands::Unarchive { id } => {
+            let archive = storage.archive();
+            let mut archived = archive.load()?;
+            let archive_id = archived.resolve(&id)?;
+
+            let mut collection = storage.load()?;
+            let new_ids = archived.move_tasks(&mut collection, &[archive_id]);
+            // Mark it as updated, so archiving on save does not take it back
+            for &new_id in &new_ids {
+                if let Some(task) = collection.get_mut(new_id) {
+                    task.touch();
+                }
+            }
+            storage.save_with_archive(&collection, &archived)?;
+
+            if let Some(task) = new_ids.first().and_then(|&new_id| collection.get(new_id)) {
+                println!("Restored task #{}: {}", task.id, task.title);
+            }
         }
 
         Commands::Recover { force } => {
@@ -322,11 +821,76 @@ fn main() -> Result<()> {
             println!("Recovered {} tasks from backup.", backup_collection.len());
         }
 
+        Commands::Undo { steps } => {
+            for _ in 0..steps {
+                let entries = storage.journal().entries()?;
+                let Some(entry) = journal::undo_target(&entries) else {
+                    println!("Nothing to undo.");
+                    break;
+                };
+                let inverse: Vec<Change> = entry.changes.iter().map(Change::inverse).collect();
+                let archive_inverse: Vec<Change> =
+                    entry.archive.iter().map(Change::inverse).collect();
+                replay(&storage, entry, &inverse, &archive_inverse, EntryKind::Undo { of: entry.seq })?;
+                println!("Undone: {}", entry.command);
+            }
+        }
+
+        Commands::Redo { steps } => {
+            for _ in 0..steps {
+                let entries = storage.journal().entries()?;
+                let Some(entry) = journal::redo_target(&entries) else {
+                    println!("Nothing to redo.");
+                    break;
+                };
+                replay(&storage, entry, &entry.changes, &entry.archive, EntryKind::Redo { of: entry.seq })?;
+                println!("Redone: {}", entry.command);
+            }
+        }
+
+        Commands::Log { since, limit } => {
+            let entries = storage.journal().entries()?;
+            let since = since.as_deref().map(parse_datetime).transpose()?;
+            let limit = limit.or(if since.is_none() { Some(20) } else { None });
+
+            let mut shown: Vec<&Entry> = entries
+                .iter()
+                .filter(|entry| since.is_none_or(|since| entry.at >= since))
+                .collect();
+            if let Some(limit) = limit {
+                shown.drain(..shown.len().saturating_sub(limit));
+            }
+
+            if shown.is_empty() {
+                println!("No changes recorded.");
+            }
+            for entry in shown {
+                println!(
+                    "{} {}",
+                    entry.at.format("%Y-%m-%d %H:%M"),
+                    describe_entry(entry, &entries)
+                );
+                for change in &entry.changes {
+                    println!("    {}", change.summary());
+                }
+                for change in &entry.archive {
+                    println!("    {} (archive)", change.summary());
+                }
+            }
+        }
+
         Commands::Reindex { force } => {
             let mut collection = storage.load()?;
 
+            if !collection.reuse_ids {
+                return Err(CliError::validation(
+                    "reindex",
+                    "ID reuse is disabled (reuse_ids = false in the config), so task numbers cannot be renumbered",
+                ));
+            }
+
             if !force
-                && !confirm("Renumber all task IDs sequentially? This cannot be undone.")?
+                && !confirm("Renumber all task IDs sequentially?")?
             {
                 println!("Cancelled.");
                 return Ok(());
@@ -365,16 +929,23 @@ fn main() -> Result<()> {
 
         Commands::Move { id, category } => {
             let mut collection = storage.load()?;
-            let task = collection.get_mut_or_err(id)?;
+            let id = collection.resolve(&id)?;
+            let task = collection.get_or_err(id)?;
 
             let new_category = if category.to_lowercase() == "none" {
                 None
             } else {
-                Some(category.clone())
+                category::normalize(&category)
             };
 
             let old_category = task.category.clone();
-            task.category = new_category.clone();
+
+            // A subtask cannot live in another category than its parent
+            if task.parent.is_some() && old_category != new_category {
+                collection.set_parent(id, None)?;
+                println!("Task #{} is no longer a subtask", id);
+            }
+            collection.move_to_category(id, new_category.clone())?;
             storage.save(&collection)?;
 
             let old_name = old_category.as_deref().unwrap_or("Uncategorized");
@@ -384,6 +955,7 @@ fn main() -> Result<()> {
 
         Commands::Tag { id, action } => {
             let mut collection = storage.load()?;
+            let id = collection.resolve(&id)?;
             let task = collection.get_mut_or_err(id)?;
 
             match action {
@@ -393,6 +965,7 @@ fn main() -> Result<()> {
                         println!("Task #{} already has tag #{}", id, tag_clean);
                     } else {
                         task.add_tag(tag_clean);
+                        task.touch();
                         storage.save(&collection)?;
                         println!("Added tag #{} to task #{}", tag_clean, id);
                     }
@@ -400,6 +973,7 @@ fn main() -> Result<()> {
                 TagAction::Remove { tag } => {
                     let tag_clean = tag.trim_start_matches('#');
                     if task.remove_tag(tag_clean) {
+                        task.touch();
                         storage.save(&collection)?;
                         println!("Removed tag #{} from task #{}", tag_clean, id);
                     } else {
@@ -409,6 +983,175 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::Depend { action } => {
+            let mut collection = storage.load()?;
+
+            match action {
+                DependAction::Add { id, blocker } => {
+                    let (id, blocker) = (collection.resolve(&id)?, collection.resolve(&blocker)?);
+                    collection.add_dependency(id, blocker)?;
+                    storage.save(&collection)?;
+                    println!("Task #{} is now blocked by #{}", id, blocker);
+                    if let Some(b) = collection.get(blocker)
+                        && b.status.is_closed()
+                    {
+                        println!("  (#{} is already {}, so #{} can start)", blocker, b.status, id);
+                    }
+                }
+                DependAction::Remove { id, blocker } => {
+                    let (id, blocker) = (collection.resolve(&id)?, collection.resolve(&blocker)?);
+                    if collection.remove_dependency(id, blocker)? {
+                        storage.save(&collection)?;
+                        println!("Task #{} is no longer blocked by #{}", id, blocker);
+                    } else {
+                        println!("Task #{} is not blocked by #{}", id, blocker);
+                    }
+                }
+            }
+        }
+
+        Commands::Template { action } => {
+            let templates = TemplateStore::new(PathBuf::from(&cfg.data_directory).join("templates"));
+
+            match action {
+                TemplateAction::List => {
+                    let names = templates.names()?;
+                    if names.is_empty() {
+                        println!("No templates yet (save one with 'taiga template save').");
+                    } else {
+                        println!("Templates:");
+                        for name in names {
+                            match templates.load(&name) {
+                                Ok(template) => {
+                                    let description = template
+                                        .description
+                                        .map(|d| format!(" - {}", d))
+                                        .unwrap_or_default();
+                                    println!("  {} ({} tasks){}", name, template.tasks.len(), description);
+                                }
+                                Err(e) => println!("  {} (unreadable: {})", name, e),
+                            }
+                        }
+                    }
+                }
+                TemplateAction::Show { name } => {
+                    let template = templates.load(&name)?;
+                    println!("Template: {}", name);
+                    if let Some(description) = &template.description {
+                        println!("  {}", description);
+                    }
+                    let variables = template.variables();
+                    if !variables.is_empty() {
+                        let names: Vec<String> = variables.into_iter().collect();
+                        println!("  Variables: {}", names.join(", "));
+                    }
+                    // Subtasks are indented under their parent, which comes first
+                    let mut depths: Vec<usize> = Vec::new();
+                    for (i, def) in template.tasks.iter().enumerate() {
+                        let depth = def
+                            .parent
+                            .filter(|&p| (1..=i).contains(&p))
+                            .map_or(0, |p| depths[p - 1] + 1);
+                        depths.push(depth);
+                        println!("  {}{}. {}", "  ".repeat(depth), i + 1, describe_template_task(def));
+                    }
+                }
+                TemplateAction::Apply { name, start, vars } => {
+                    let template = templates.load(&name)?;
+                    let base = match start {
+                        Some(date) => parse_date(&date)?,
+                        None => Zone::current().today(),
+                    };
+                    let vars: HashMap<String, String> = vars.into_iter().collect();
+
+                    let mut collection = storage.load()?;
+                    let ids = template.apply(&mut collection, base, &vars)?;
+                    storage.save(&collection)?;
+
+                    println!("Added {} task(s) from template '{}':", ids.len(), name);
+                    let tasks: Vec<&Task> = ids.iter().filter_map(|&id| collection.get(id)).collect();
+                    let use_color = supports_color();
+                    for (depth, task) in tree_order(&tasks) {
+                        let ctx = TaskContext::new(&collection, task, depth)
+                            .with_deadline_warning_days(cfg.deadline_warning_days);
+                        println!("{}", format_task(task, &ctx, DisplayMode::Default, use_color));
+                    }
+                }
+                TemplateAction::Save { name, ids, category, tag, start, description, force } => {
+                    if ids.is_empty() && category.is_none() && tag.is_empty() {
+                        return Err(CliError::validation(
+                            "template",
+                            "Choose the tasks to save: give their numbers, --category or --tag",
+                        ));
+                    }
+                    let collection = storage.load()?;
+
+                    let mut selected: HashSet<u32> = HashSet::new();
+                    for id in &ids {
+                        selected.insert(collection.resolve(id)?);
+                    }
+                    if category.is_some() || !tag.is_empty() {
+                        let mut filter = TaskFilter::new().including_deferred().with_tags(tag);
+                        if let Some(cat) = category {
+                            filter = match category::normalize(&cat) {
+                                Some(path) if cat.to_lowercase() != "none" => filter.in_category(path),
+                                _ => filter.uncategorized(),
+                            };
+                        }
+                        selected.extend(collection.get_filtered(&filter).iter().map(|t| t.id));
+                    }
+                    // Subtasks come along with their parents
+                    let mut pending: Vec<u32> = selected.iter().copied().collect();
+                    while let Some(id) = pending.pop() {
+                        for child in collection.children(id) {
+                            if selected.insert(child.id) {
+                                pending.push(child.id);
+                            }
+                        }
+                    }
+                    if selected.is_empty() {
+                        return Err(CliError::validation("template", "No tasks match"));
+                    }
+
+                    let mut tasks: Vec<&Task> = selected.iter().filter_map(|&id| collection.get(id)).collect();
+                    tasks.sort_by_key(|t| t.id);
+                    let tasks: Vec<&Task> = tree_order(&tasks).into_iter().map(|(_, t)| t).collect();
+
+                    // Offsets count from the earliest date among the tasks by default
+                    let base = match start {
+                        Some(date) => parse_date(&date)?,
+                        None => tasks
+                            .iter()
+                            .flat_map(|t| {
+                                [
+                                    t.scheduled.map(|dt| Zone::current().date_of(&dt)),
+                                    t.start,
+                                    t.deadline.map(|dt| Zone::current().date_of(&dt)),
+                                ]
+                            })
+                            .flatten()
+                            .min()
+                            .unwrap_or_else(|| Zone::current().today()),
+                    };
+
+                    let mut template = taiga_core::Template::capture(&tasks, base);
+                    template.description = description;
+                    let path = templates.save(&name, &template, force)?;
+                    println!(
+                        "Saved {} task(s) as template '{}' (dates relative to {})",
+                        tasks.len(),
+                        name,
+                        base.format("%Y-%m-%d")
+                    );
+                    println!("  Edit {} to add {{{{variables}}}}", path.display());
+                }
+                TemplateAction::Remove { name } => {
+                    templates.remove(&name)?;
+                    println!("Deleted template '{}'", name);
+                }
+            }
+        }
+
         Commands::Categories => {
             let collection = storage.load()?;
             let categories = collection.get_categories();
@@ -417,10 +1160,16 @@ fn main() -> Result<()> {
                 println!("No categories found.");
                 println!("(All tasks are uncategorized)");
             } else {
+                // Shown as a tree, counting the tasks of subcategories too
                 println!("Categories:");
                 for cat in &categories {
-                    let count = collection.tasks_in_category(Some(cat)).len();
-                    println!("  {} ({} tasks)", cat, count);
+                    let count = collection.tasks_within_category(cat).len();
+                    println!(
+                        "  {}{} ({} tasks)",
+                        "  ".repeat(category::depth(cat)),
+                        category::name(cat),
+                        count
+                    );
                 }
 
                 // Also show uncategorized count
@@ -485,6 +1234,178 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Set a task's status and report the change
+fn change_status(storage: &MarkdownStorage, id: &TaskRef, status: Status) -> Result<()> {
+    let mut collection = storage.load()?;
+    let id = collection.resolve(id)?;
+    let next_id = collection.set_status(id, status, Zone::current().today())?;
+    let task = collection.get_or_err(id)?;
+    println!("Marked task #{} as {}: {}", task.id, status, task.title);
+    print_next_occurrence(&collection, next_id);
+    storage.save(&collection)?;
+    Ok(())
+}
+
+/// Apply a journal entry's changes (or their inverse) to the task and
+/// archive files and record the step
+fn replay(
+    storage: &MarkdownStorage,
+    entry: &Entry,
+    changes: &[Change],
+    archive_changes: &[Change],
+    kind: EntryKind,
+) -> Result<()> {
+    let conflict = |e| match e {
+        CoreError::Validation { message, .. } => CliError::validation(
+            "history",
+            format!(
+                "cannot {} '{}': {} (edited outside taiga?)",
+                if matches!(kind, EntryKind::Undo { .. }) { "undo" } else { "redo" },
+                entry.command,
+                message
+            ),
+        ),
+        e => e.into(),
+    };
+
+    let mut collection = storage.load()?;
+    history::apply(&mut collection, changes).map_err(conflict)?;
+    if archive_changes.is_empty() {
+        return storage.save_as(&collection, None, kind);
+    }
+    let mut archived = storage.archive().load()?;
+    history::apply(&mut archived, archive_changes).map_err(conflict)?;
+    storage.save_as(&collection, Some(&archived), kind)
+}
+
+/// Describe a journal entry, e.g. "check 3" or "undo of #12 (check 3)"
+fn describe_entry(entry: &Entry, entries: &[Entry]) -> String {
+    let (action, of) = match entry.kind {
+        EntryKind::Edit => return entry.command.clone(),
+        EntryKind::Undo { of } => ("undo", of),
+        EntryKind::Redo { of } => ("redo", of),
+    };
+    match journal::find(entries, of) {
+        Some(original) => format!("{} of #{} ({})", action, of, original.command),
+        None => format!("{} of #{}", action, of),
+    }
+}
+
+/// Read a date, #tags, @category and !priority from the words of a new
+/// task's title; with `dated` the date comes from an option, so date words
+/// stay in the title, and with `raw` nothing is read
+fn read_title(text: &str, raw: bool, dated: bool) -> QuickAdd {
+    if raw {
+        QuickAdd { title: text.to_string(), ..QuickAdd::default() }
+    } else if dated {
+        QuickAdd::parse_undated(text)
+    } else {
+        QuickAdd::parse(text)
+    }
+}
+
+/// Report the occurrence spawned by closing a recurring task, if any
+fn print_next_occurrence(collection: &TaskCollection, next_id: Option<u32>) {
+    if let Some(next) = next_id.and_then(|next_id| collection.get(next_id)) {
+        let date = next
+            .scheduled
+            .map(|dt| format_datetime_human(&dt, false))
+            .unwrap_or_default();
+        println!("Next occurrence #{} scheduled for {}", next.id, date);
+    }
+}
+
+/// Keep URLs as they are and turn file paths into absolute paths, so links
+/// work from any directory
+fn resolve_link_target(target: &str) -> Result<String> {
+    let is_url = target.contains("://") || target.starts_with("mailto:");
+    if is_url {
+        return Ok(target.to_string());
+    }
+    std::fs::canonicalize(target)
+        .map(|path| path.display().to_string())
+        .map_err(|e| CliError::io(format!("Cannot attach '{}'", target), e))
+}
+
+/// Open a URL or file with the desktop's default application
+fn open_with_system(target: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+
+    let status = command
+        .arg(target)
+        .status()
+        .map_err(|e| CliError::io("Failed to launch the system opener", e))?;
+    if !status.success() {
+        return Err(CliError::validation(
+            "link",
+            format!("Opening '{}' failed ({})", target, status),
+        ));
+    }
+    Ok(())
+}
+
+/// Build the working-day calendar from the config and the holiday file
+fn load_work_calendar(working_week: &str, holidays_path: Option<&Path>) -> Result<WorkCalendar> {
+    let holidays = holidays_path
+        .map(|path| {
+            std::fs::read_to_string(path)
+                .map_err(|e| CliError::io(format!("Cannot read holiday file {}", path.display()), e))
+        })
+        .transpose()?;
+    WorkCalendar::from_config(working_week, holidays.as_deref()).map_err(|e| match holidays_path {
+        Some(path) => CliError::config(format!("{} (holiday file {})", e, path.display())),
+        None => CliError::config(e.to_string()),
+    })
+}
+
+/// Move a date and time to the next working day, keeping the time
+fn next_working_datetime(datetime: DateTime<Local>) -> Result<DateTime<Local>> {
+    let wall_time = wall_time(&datetime);
+    WorkCalendar::current()
+        .working_day_on_or_after(wall_time.date())
+        .and_then(|date| Zone::current().from_naive(&date.and_time(wall_time.time())))
+        .ok_or_else(|| CliError::validation("date", "No working day found after that date"))
+}
+
+/// One-line summary of a template task: title, category, tags and offsets
+fn describe_template_task(def: &TemplateTask) -> String {
+    let mut line = def.title.clone();
+    if let Some(category) = &def.category {
+        line.push_str(&format!(" [{}]", category));
+    }
+    for tag in &def.tags {
+        line.push_str(&format!(" #{}", tag));
+    }
+    if let Some(level) = def.priority {
+        line.push_str(&format!(" !{}", level));
+    }
+    for (key, value) in &def.fields {
+        line.push_str(&format!(" {}", field::format(key, value)));
+    }
+    for (label, offset) in [
+        ("start", &def.start),
+        ("scheduled", &def.scheduled),
+        ("deadline", &def.deadline),
+        ("repeats", &def.repeat),
+    ] {
+        if let Some(offset) = offset {
+            line.push_str(&format!(" ({} {})", label, offset));
+        }
+    }
+    if !def.checklist.is_empty() {
+        line.push_str(&format!(" (checklist: {} item(s))", def.checklist.len()));
+    }
+    line
+}
+
 /// Ask user for confirmation
 fn confirm(prompt: &str) -> Result<bool> {
     print!("{} [y/N] ", prompt);
@@ -495,3 +1416,55 @@ fn confirm(prompt: &str) -> Result<bool> {
 
     Ok(input.trim().to_lowercase() == "y")
 }
+
+/// Open text in the user's editor ($VISUAL, then $EDITOR) and return the result
+fn edit_in_editor(initial: &str, name: &str) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+
+    let path = std::env::temp_dir().join(format!("{}-{}.md", name, std::process::id()));
+    std::fs::write(&path, initial).map_err(|e| CliError::io("Failed to write temporary file", e))?;
+
+    // The editor may come with arguments, e.g. "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or_else(|| CliError::config("No editor configured"))?;
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .map_err(|e| CliError::io(format!("Failed to launch editor '{}'", editor), e));
+
+    let result = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(&path)
+            .map_err(|e| CliError::io("Failed to read temporary file", e)),
+        Ok(status) => Err(CliError::validation(
+            "editor",
+            format!("'{}' exited with {}, notes left unchanged", editor, status),
+        )),
+        Err(e) => Err(e),
+    };
+
+    std::fs::remove_file(&path).ok();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_title_keeps_date_words_with_date_option() {
+        let quick = read_title("Prepare demo friday #work", false, true);
+        assert_eq!(quick.title, "Prepare demo friday");
+        assert_eq!(quick.tags, vec!["work"]);
+        assert_eq!(quick.scheduled, None);
+
+        let quick = read_title("Prepare demo friday #work", false, false);
+        assert_eq!(quick.title, "Prepare demo");
+        assert!(quick.scheduled.is_some());
+
+        let quick = read_title("Prepare demo friday #work", true, false);
+        assert_eq!(quick.title, "Prepare demo friday #work");
+    }
+}
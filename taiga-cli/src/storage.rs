@@ -1,43 +1,73 @@
 //! Markdown file storage adapter for TaskCollection
 //!
-//! Handles persistence of tasks to markdown files.
+//! Handles persistence of tasks to markdown files, in the format of
+//! [`taiga_core::markdown`].
 
-use chrono::{Local, NaiveDate, TimeZone};
-use regex::Regex;
-use std::fs::OpenOptions;
-use std::io::{BufRead, BufReader, Write};
+use chrono::{Duration, Local};
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
 
-use taiga_core::{Task, TaskCollection};
+use taiga_core::{history, markdown};
+use taiga_core::TaskCollection;
 
 use crate::error::{CliError, Result};
-
-// Regex pattern is validated at compile time - invalid patterns are programming errors
-static TASK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^\[ID:(\d+)\] - \[(.)\] (.*?)(?: \(Scheduled: (.*)\))?$")
-        .expect("Invalid regex pattern - this is a compile-time constant")
-});
-
-// Category header pattern: ## Category Name
-static CATEGORY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"^##\s+(.+)$").expect("Invalid category regex pattern")
-});
-
-// Tag pattern: #word (alphanumeric and underscores)
-static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"#(\w+)").expect("Invalid tag regex pattern")
-});
+use crate::journal::{EntryKind, Journal};
 
 /// Markdown storage adapter
 pub struct MarkdownStorage {
     path: PathBuf,
+    reuse_ids: bool,
+    archive_after_days: Option<u32>,
+    /// Command recorded in the journal on save (None = no journal)
+    journal_command: Option<String>,
 }
 
 impl MarkdownStorage {
     /// Create a new storage adapter for the given path
     pub fn new(path: impl Into<PathBuf>) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            reuse_ids: true,
+            archive_after_days: None,
+            journal_command: None,
+        }
+    }
+
+    /// Builder method to record every save in the journal, attributed to
+    /// the given command
+    pub fn with_journal(mut self, command: impl Into<String>) -> Self {
+        self.journal_command = Some(command.into());
+        self
+    }
+
+    /// Journal of changes kept next to the task file
+    pub fn journal(&self) -> Journal {
+        Journal::new(self.path.with_extension("journal"))
+    }
+
+    /// Builder method to archive tasks automatically on save once they
+    /// have been done or cancelled for the given number of days
+    pub fn with_archive_after_days(mut self, days: Option<u32>) -> Self {
+        self.archive_after_days = days;
+        self
+    }
+
+    /// Path of the archive file kept next to the task file
+    pub fn archive_path(&self) -> PathBuf {
+        self.path.with_extension("archive.md")
+    }
+
+    /// Storage adapter for the archive file
+    pub fn archive(&self) -> MarkdownStorage {
+        MarkdownStorage::new(self.archive_path())
+    }
+
+    /// Builder method to enable or disable reusing IDs of removed tasks
+    ///
+    /// With reuse disabled, the next task number is stored in the file so
+    /// numbers of removed tasks are not handed out again.
+    pub fn with_reuse_ids(mut self, reuse_ids: bool) -> Self {
+        self.reuse_ids = reuse_ids;
+        self
     }
 
     /// Get the storage path
@@ -47,96 +77,103 @@ impl MarkdownStorage {
 
     /// Load tasks from the markdown file
     pub fn load(&self) -> Result<TaskCollection> {
-        let mut collection = TaskCollection::new();
-
         if !self.path.exists() {
-            return Ok(collection);
+            return Ok(TaskCollection::new().with_reuse_ids(self.reuse_ids));
         }
 
-        let file = std::fs::File::open(&self.path)?;
-        let reader = BufReader::new(file);
-
-        let mut current_category: Option<String> = None;
-
-        for line in reader.lines() {
-            let line: String = line?;
-            let trimmed = line.trim();
-
-            if trimmed.is_empty() {
-                continue;
-            }
-
-            // Check for category header
-            if let Some(caps) = CATEGORY_REGEX.captures(trimmed) {
-                let cat_name = caps.get(1).map(|m| m.as_str().trim().to_string());
-                // "Uncategorized" header maps to None
-                current_category = cat_name.filter(|s| s.to_lowercase() != "uncategorized");
-                continue;
-            }
-
-            match parse_task_line(&line, current_category.clone()) {
-                Ok(task) => {
-                    collection.insert(task);
-                }
-                Err(e) => {
-                    eprintln!("Warning: Skipping invalid task line: {}", e);
-                }
-            }
+        let content = std::fs::read_to_string(&self.path)?;
+        let (collection, skipped) = markdown::parse(&content, self.reuse_ids);
+        for e in skipped {
+            eprintln!("Warning: Skipping invalid task line: {}", e);
         }
-
         Ok(collection)
     }
 
     /// Save tasks to the markdown file
+    ///
+    /// With automatic archiving enabled, tasks closed long enough ago are
+    /// moved to the archive file instead of being written.
     pub fn save(&self, collection: &TaskCollection) -> Result<()> {
-        // Create backup before saving
-        self.backup()?;
-
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&self.path)?;
+        self.save_as(collection, None, EntryKind::Edit)
+    }
 
-        // Group tasks by category
-        let mut categorized: std::collections::BTreeMap<Option<String>, Vec<&Task>> =
-            std::collections::BTreeMap::new();
+    /// Save tasks together with the archive, after moving tasks between them
+    pub fn save_with_archive(
+        &self,
+        collection: &TaskCollection,
+        archived: &TaskCollection,
+    ) -> Result<()> {
+        self.save_as(collection, Some(archived), EntryKind::Edit)
+    }
 
-        for task in collection.list_all() {
-            categorized
-                .entry(task.category.clone())
-                .or_default()
-                .push(task);
+    /// Save tasks and optionally the archive, recording the changes to both
+    /// in the journal as the given kind of operation
+    ///
+    /// Changes are taken from the files before and after writing, so the
+    /// journal holds tasks exactly as they are stored. Undo and redo restore
+    /// the files as recorded, so only regular edits archive automatically.
+    pub fn save_as(
+        &self,
+        collection: &TaskCollection,
+        archived: Option<&TaskCollection>,
+        kind: EntryKind,
+    ) -> Result<()> {
+        let archive = self.archive();
+        let before = match self.journal_command {
+            Some(_) => Some((self.load()?, archive.load()?)),
+            None => None,
+        };
+
+        let mut archived = archived.cloned();
+        let mut remaining = None;
+        if let Some(days) = self.archive_after_days
+            && kind == EntryKind::Edit
+        {
+            let cutoff = Local::now() - Duration::days(days.into());
+            let ids = collection.closed_ids(Some(cutoff));
+            if !ids.is_empty() {
+                let mut target = match archived.take() {
+                    Some(target) => target,
+                    None => archive.load()?,
+                };
+                let mut rest = collection.clone();
+                rest.move_tasks(&mut target, &ids);
+                archived = Some(target);
+                remaining = Some(rest);
+            }
         }
-
-        // Sort categories: named categories alphabetically, then Uncategorized (None) last
-        let mut categories: Vec<Option<String>> = categorized.keys().cloned().collect();
-        categories.sort_by(|a, b| match (a, b) {
-            (None, None) => std::cmp::Ordering::Equal,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (Some(a_cat), Some(b_cat)) => a_cat.to_lowercase().cmp(&b_cat.to_lowercase()),
-        });
-
-        let mut first_category = true;
-        for category in categories {
-            // Write category header
-            if !first_category {
-                writeln!(file)?;
+        let collection = remaining.as_ref().unwrap_or(collection);
+
+        // Write the file that gains tasks first, so a failure in between
+        // leaves a task in both files rather than in neither
+        match &archived {
+            Some(archived) if archived.len() >= archive.load()?.len() => {
+                archive.write(archived)?;
+                self.write(collection)?;
             }
-            first_category = false;
-
-            let header_name = category.as_deref().unwrap_or("Uncategorized");
-            writeln!(file, "## {}", header_name)?;
+            Some(archived) => {
+                self.write(collection)?;
+                archive.write(archived)?;
+            }
+            None => self.write(collection)?,
+        }
 
-            // Write tasks in this category
-            if let Some(tasks) = categorized.get(&category) {
-                for task in tasks {
-                    writeln!(file, "{}", format_task_line(task))?;
-                }
+        if let (Some(command), Some((before, archive_before))) = (&self.journal_command, before) {
+            let changes = history::diff(&before, &self.load()?);
+            let archive_changes = history::diff(&archive_before, &archive.load()?);
+            if !changes.is_empty() || !archive_changes.is_empty() {
+                self.journal().append(command, kind, changes, archive_changes)?;
             }
         }
+        Ok(())
+    }
 
+    /// Write tasks to the markdown file, after backing up the old one
+    fn write(&self, collection: &TaskCollection) -> Result<()> {
+        // Create backup before saving
+        self.backup()?;
+
+        std::fs::write(&self.path, markdown::render(collection))?;
         Ok(())
     }
 
@@ -159,7 +196,7 @@ impl MarkdownStorage {
             return Err(CliError::storage("Backup file not found"));
         }
 
-        let backup_storage = MarkdownStorage::new(backup_path);
+        let backup_storage = MarkdownStorage::new(backup_path).with_reuse_ids(self.reuse_ids);
         backup_storage.load()
     }
 
@@ -169,202 +206,359 @@ impl MarkdownStorage {
     }
 }
 
-/// Parse a markdown line into a Task
-fn parse_task_line(line: &str, category: Option<String>) -> Result<Task> {
-    let caps = TASK_REGEX
-        .captures(line)
-        .ok_or_else(|| CliError::parse(format!("Invalid task format: {}", line)))?;
-
-    let id = caps
-        .get(1)
-        .ok_or_else(|| CliError::parse("Missing task ID"))?
-        .as_str()
-        .parse::<u32>()
-        .map_err(|e| CliError::parse_with_source("Invalid task ID", e))?;
-
-    let is_complete = caps
-        .get(2)
-        .ok_or_else(|| CliError::parse("Missing completion status"))?
-        .as_str()
-        == "x";
-
-    let raw_title = caps
-        .get(3)
-        .ok_or_else(|| CliError::parse("Missing task title"))?
-        .as_str();
-
-    // Extract tags from title
-    let tags: Vec<String> = TAG_REGEX
-        .captures_iter(raw_title)
-        .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
-        .collect();
-
-    // Remove tags from title to get clean title
-    let title = TAG_REGEX.replace_all(raw_title, "").trim().to_string();
-
-    let scheduled = match caps.get(4) {
-        Some(m) => {
-            let date_str = m.as_str();
-            NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-                .ok()
-                .and_then(|d| d.and_hms_opt(0, 0, 0))
-                .and_then(|dt| Local.from_local_datetime(&dt).single())
-        }
-        None => None,
-    };
-
-    Ok(Task::new(title)
-        .with_id(id)
-        .with_complete(is_complete)
-        .with_scheduled(scheduled)
-        .with_category(category)
-        .with_tags(tags))
-}
-
-/// Format a Task as a markdown line
-fn format_task_line(task: &Task) -> String {
-    let check_mark = if task.is_complete { "x" } else { " " };
-
-    // Build tags string
-    let tags_str = if task.tags.is_empty() {
-        String::new()
-    } else {
-        format!(
-            " {}",
-            task.tags
-                .iter()
-                .map(|t| format!("#{}", t))
-                .collect::<Vec<_>>()
-                .join(" ")
-        )
-    };
-
-    match &task.scheduled {
-        Some(dt) => format!(
-            "[ID:{}] - [{}] {}{} (Scheduled: {})",
-            task.id,
-            check_mark,
-            task.title,
-            tags_str,
-            dt.format("%Y-%m-%d")
-        ),
-        None => format!("[ID:{}] - [{}] {}{}", task.id, check_mark, task.title, tags_str),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use regex::Regex;
+    use taiga_core::date::{format_timestamp, parse_timestamp};
+    use taiga_core::{ChecklistItem, Task};
+
+    /// Drop "(UID: ...)" and "(Times: ...)" attributes so file contents can be compared exactly
+    fn without_metadata(content: &str) -> String {
+        Regex::new(r" \((UID|Times): [^()]*\)")
+            .unwrap()
+            .replace_all(content, "")
+            .into_owned()
+    }
 
     #[test]
-    fn test_parse_task_line_simple() {
-        let line = "[ID:1] - [ ] Simple task";
-        let task = parse_task_line(line, None).unwrap();
-
-        assert_eq!(task.id, 1);
-        assert_eq!(task.title, "Simple task");
-        assert!(!task.is_complete);
-        assert!(task.scheduled.is_none());
-        assert!(task.category.is_none());
-        assert!(task.tags.is_empty());
+    fn test_save_load_subtasks() {
+        let path = std::env::temp_dir().join(format!("taiga-subtasks-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path);
+
+        let mut collection = TaskCollection::new();
+        let parent = collection.add_with_category_tags("Plan trip", None, Some("Home".to_string()), Vec::new());
+        let child = collection.add_task(Task::new("Book hotel"));
+        let grandchild = collection.add_task(Task::new("Compare prices"));
+        collection.add("Unrelated", None);
+        collection.set_parent(child, Some(parent)).unwrap();
+        collection.set_parent(grandchild, Some(child)).unwrap();
+
+        storage.save(&collection).unwrap();
+        let content = without_metadata(&std::fs::read_to_string(&path).unwrap());
+        assert!(content.contains("\n  [ID:2] - [ ] Book hotel\n    [ID:3] - [ ] Compare prices\n"));
+
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.get(child).unwrap().parent, Some(parent));
+        assert_eq!(loaded.get(grandchild).unwrap().parent, Some(child));
+        assert_eq!(loaded.get(grandchild).unwrap().category, Some("Home".to_string()));
+        assert_eq!(loaded.get(4).unwrap().parent, None);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
     }
 
     #[test]
-    fn test_parse_task_line_completed() {
-        let line = "[ID:2] - [x] Completed task";
-        let task = parse_task_line(line, None).unwrap();
+    fn test_save_load_notes() {
+        let path = std::env::temp_dir().join(format!("taiga-notes-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path);
 
-        assert_eq!(task.id, 2);
-        assert!(task.is_complete);
+        let mut collection = TaskCollection::new();
+        let parent = collection.add_task(
+            Task::new("Release")
+                .with_notes(Some("Acceptance criteria:\n  - changelog updated\n\n## Not a category".to_string())),
+        );
+        let child = collection.add_task(Task::new("Tag build").with_notes(Some("git tag v1".to_string())));
+        collection.set_parent(child, Some(parent)).unwrap();
+        collection.add("Next", None);
+
+        storage.save(&collection).unwrap();
+        let content = without_metadata(&std::fs::read_to_string(&path).unwrap());
+        assert!(content.contains(
+            "[ID:1] - [ ] Release\n  Acceptance criteria:\n    - changelog updated\n\n  ## Not a category\n  [ID:2] - [ ] Tag build\n    git tag v1\n[ID:3]"
+        ));
+
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.get(parent).unwrap().notes, collection.get(parent).unwrap().notes);
+        assert_eq!(loaded.get(child).unwrap().notes.as_deref(), Some("git tag v1"));
+        assert_eq!(loaded.get(child).unwrap().parent, Some(parent));
+        assert!(loaded.get(3).unwrap().notes.is_none());
+        assert_eq!(loaded.get(3).unwrap().category, None);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
     }
 
     #[test]
-    fn test_parse_task_line_scheduled() {
-        let line = "[ID:3] - [ ] Scheduled task (Scheduled: 2026-01-25)";
-        let task = parse_task_line(line, None).unwrap();
+    fn test_save_load_without_id_reuse() {
+        let path = std::env::temp_dir().join(format!("taiga-next-id-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path).with_reuse_ids(false);
+
+        let mut collection = storage.load().unwrap();
+        collection.add("First", None);
+        let second = collection.add("Second", None);
+        let uid = collection.get(second).unwrap().uid.clone();
+        collection.remove(second);
+        storage.save(&collection).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("<!-- next-id: 3 -->\n"));
+
+        let mut loaded = storage.load().unwrap();
+        assert_eq!(loaded.add("Third", None), 3);
+        assert!(loaded.find_by_uid(&uid).is_none());
+
+        // With reuse enabled the marker is ignored and dropped on save
+        let reusing = MarkdownStorage::new(&path);
+        let mut loaded = reusing.load().unwrap();
+        assert_eq!(loaded.add("Third", None), 2);
+        reusing.save(&loaded).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("next-id"));
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
+    }
 
-        assert_eq!(task.id, 3);
-        assert!(task.scheduled.is_some());
-        assert_eq!(
-            task.scheduled.unwrap().date_naive(),
-            NaiveDate::from_ymd_opt(2026, 1, 25).unwrap()
-        );
+    #[test]
+    fn test_archive_on_save() {
+        let path = std::env::temp_dir().join(format!("taiga-archive-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path).with_archive_after_days(Some(7));
+        assert!(storage.archive_path().ends_with(format!("taiga-archive-{}.archive.md", std::process::id())));
+
+        let old = Local::now() - Duration::days(10);
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Old").with_id(1).with_complete(true).with_completed(Some(old)));
+        collection.insert(Task::new("Recent").with_id(2).with_complete(true).with_completed(Some(Local::now())));
+        collection.insert(Task::new("Open").with_id(3).with_created(Some(old)));
+        storage.save(&collection).unwrap();
+
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.list_all().iter().map(|t| t.id).collect::<Vec<_>>(), vec![2, 3]);
+        let archived = storage.archive().load().unwrap();
+        assert_eq!(archived.get(1).unwrap().title, "Old");
+        // The completion time is kept, to the minute
+        assert_eq!(archived.get(1).unwrap().completed, parse_timestamp(&format_timestamp(&old)));
+
+        for file in [path.clone(), storage.archive_path()] {
+            std::fs::remove_file(&file).ok();
+            std::fs::remove_file(file.with_extension("md.bak")).ok();
+        }
     }
 
     #[test]
-    fn test_parse_task_line_with_tags() {
-        let line = "[ID:4] - [ ] Complete report #urgent #finance";
-        let task = parse_task_line(line, Some("Work".to_string())).unwrap();
-
-        assert_eq!(task.id, 4);
-        assert_eq!(task.title, "Complete report");
-        assert_eq!(task.category, Some("Work".to_string()));
-        assert_eq!(task.tags, vec!["urgent", "finance"]);
+    fn test_save_records_journal() {
+        let path = std::env::temp_dir().join(format!("taiga-journal-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path).with_journal("add Buy milk");
+
+        let mut collection = storage.load().unwrap();
+        collection.add("Buy milk", None);
+        storage.save(&collection).unwrap();
+        // Saving without changes records nothing
+        storage.save(&collection).unwrap();
+
+        let entries = storage.journal().entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].command, "add Buy milk");
+        assert_eq!(entries[0].changes.len(), 1);
+        assert!(entries[0].changes[0].before.is_none());
+
+        // The recorded task matches the stored one, so it can be undone
+        let mut loaded = storage.load().unwrap();
+        let inverse: Vec<_> = entries[0].changes.iter().map(|c| c.inverse()).collect();
+        history::apply(&mut loaded, &inverse).unwrap();
+        storage.save_as(&loaded, None, EntryKind::Undo { of: 1 }).unwrap();
+        assert!(storage.load().unwrap().is_empty());
+        assert_eq!(storage.journal().entries().unwrap().len(), 2);
+
+        for file in [path.clone(), path.with_extension("md.bak"), path.with_extension("journal")] {
+            std::fs::remove_file(&file).ok();
+        }
+    }
+
+    /// Undo the last journal entry the way `taiga undo` does
+    fn undo_last(storage: &MarkdownStorage) {
+        let entries = storage.journal().entries().unwrap();
+        let entry = entries.last().unwrap();
+        let mut collection = storage.load().unwrap();
+        let mut archived = storage.archive().load().unwrap();
+        let inverse: Vec<_> = entry.changes.iter().map(|c| c.inverse()).collect();
+        let archive_inverse: Vec<_> = entry.archive.iter().map(|c| c.inverse()).collect();
+        history::apply(&mut collection, &inverse).unwrap();
+        history::apply(&mut archived, &archive_inverse).unwrap();
+        storage
+            .save_as(&collection, Some(&archived), EntryKind::Undo { of: entry.seq })
+            .unwrap();
+    }
+
+    fn remove_files(storage: &MarkdownStorage) {
+        let path = storage.path();
+        for file in [
+            path.to_path_buf(),
+            path.with_extension("md.bak"),
+            path.with_extension("journal"),
+            storage.archive_path(),
+            storage.archive_path().with_extension("md.bak"),
+        ] {
+            std::fs::remove_file(&file).ok();
+        }
     }
 
     #[test]
-    fn test_parse_task_line_with_tags_and_schedule() {
-        let line = "[ID:5] - [ ] Buy groceries #shopping (Scheduled: 2026-01-25)";
-        let task = parse_task_line(line, Some("Personal".to_string())).unwrap();
-
-        assert_eq!(task.id, 5);
-        assert_eq!(task.title, "Buy groceries");
-        assert_eq!(task.category, Some("Personal".to_string()));
-        assert_eq!(task.tags, vec!["shopping"]);
-        assert!(task.scheduled.is_some());
+    fn test_undo_archive() {
+        let path = std::env::temp_dir().join(format!("taiga-undo-archive-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path).with_journal("archive 2");
+
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Open").with_id(1));
+        collection.insert(Task::new("Done").with_id(2).with_complete(true));
+        storage.save(&collection).unwrap();
+
+        let mut archived = storage.archive().load().unwrap();
+        collection.move_tasks(&mut archived, &[2]);
+        storage.save_with_archive(&collection, &archived).unwrap();
+        let entries = storage.journal().entries().unwrap();
+        assert_eq!(entries.last().unwrap().archive.len(), 1);
+
+        undo_last(&storage);
+        assert_eq!(storage.load().unwrap().get(2).unwrap().title, "Done");
+        assert!(storage.archive().load().unwrap().is_empty());
+
+        remove_files(&storage);
     }
 
     #[test]
-    fn test_format_task_line_simple() {
-        let task = Task::new("Simple task").with_id(1);
-        let line = format_task_line(&task);
+    fn test_undo_unarchive() {
+        let path = std::env::temp_dir().join(format!("taiga-undo-unarchive-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path).with_journal("unarchive 1");
 
-        assert_eq!(line, "[ID:1] - [ ] Simple task");
+        let mut archived = TaskCollection::new();
+        archived.insert(Task::new("Done").with_id(1).with_complete(true));
+        storage.archive().save(&archived).unwrap();
+
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Open").with_id(1));
+        storage.save(&collection).unwrap();
+        archived.move_tasks(&mut collection, &[1]);
+        storage.save_with_archive(&collection, &archived).unwrap();
+        assert_eq!(storage.load().unwrap().len(), 2);
+        assert!(storage.archive().load().unwrap().is_empty());
+
+        undo_last(&storage);
+        assert_eq!(storage.load().unwrap().len(), 1);
+        assert_eq!(storage.archive().load().unwrap().get(1).unwrap().title, "Done");
+
+        remove_files(&storage);
     }
 
     #[test]
-    fn test_format_task_line_completed() {
-        let task = Task::new("Done").with_id(2).with_complete(true);
-        let line = format_task_line(&task);
+    fn test_undo_auto_archive() {
+        let path = std::env::temp_dir().join(format!("taiga-undo-auto-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path)
+            .with_journal("add Call mom")
+            .with_archive_after_days(Some(7));
+
+        let old = Local::now() - Duration::days(10);
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Old").with_id(1).with_complete(true).with_completed(Some(old)));
+        // Written without archiving, as if archiving was enabled afterwards
+        storage.write(&collection).unwrap();
+
+        collection.add("Call mom", None);
+        storage.save(&collection).unwrap();
+        assert!(storage.load().unwrap().get(1).is_none());
+
+        // Undo restores the archived task instead of archiving it again
+        undo_last(&storage);
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.list_all().iter().map(|t| t.title.as_str()).collect::<Vec<_>>(), vec!["Old"]);
+        assert!(storage.archive().load().unwrap().is_empty());
 
-        assert_eq!(line, "[ID:2] - [x] Done");
+        remove_files(&storage);
     }
 
     #[test]
-    fn test_format_task_line_with_tags() {
-        let task = Task::new("Complete report")
-            .with_id(3)
-            .with_tags(vec!["urgent".to_string(), "finance".to_string()]);
-        let line = format_task_line(&task);
+    fn test_roundtrip_checklist() {
+        let path = std::env::temp_dir().join(format!("taiga-checklist-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path);
 
-        assert_eq!(line, "[ID:3] - [ ] Complete report #urgent #finance");
+        let mut collection = TaskCollection::new();
+        let id = collection.add_task(
+            Task::new("Pack")
+                .with_checklist(vec![
+                    ChecklistItem::new("Passport").with_done(true),
+                    ChecklistItem::new("Charger"),
+                ])
+                .with_notes(Some("Also check:\n- [ ] visa".to_string())),
+        );
+        let child = collection.add("Book hotel", None);
+        collection.set_parent(child, Some(id)).unwrap();
+
+        storage.save(&collection).unwrap();
+        let content = without_metadata(&std::fs::read_to_string(&path).unwrap());
+        assert!(content.contains(
+            "[ID:1] - [ ] Pack\n  - [x] Passport\n  - [ ] Charger\n  Also check:\n  - [ ] visa\n  [ID:2] - [ ] Book hotel\n"
+        ));
+
+        // Checkbox lines after the notes start stay part of the notes
+        let loaded = storage.load().unwrap();
+        assert_eq!(loaded.get(id).unwrap().checklist, collection.get(id).unwrap().checklist);
+        assert_eq!(loaded.get(id).unwrap().notes, collection.get(id).unwrap().notes);
+        assert_eq!(loaded.get(child).unwrap().parent, Some(id));
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
     }
 
     #[test]
-    fn test_roundtrip() {
-        let original = Task::new("Test task").with_id(5).with_complete(true);
+    fn test_roundtrip_nested_categories() {
+        let path = std::env::temp_dir().join(format!("taiga-categories-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path);
 
-        let line = format_task_line(&original);
-        let parsed = parse_task_line(&line, None).unwrap();
+        let mut collection = TaskCollection::new();
+        for (title, category) in [
+            ("Fix API", Some("Work/ClientA/Backend")),
+            ("Plan sprint", Some("Work")),
+            ("Invoice", Some("Work/Admin")),
+            ("Sort socks", None),
+        ] {
+            collection.add_task(Task::new(title).with_category(category.map(String::from)));
+        }
+
+        storage.save(&collection).unwrap();
+        let content = without_metadata(&std::fs::read_to_string(&path).unwrap());
+        assert_eq!(
+            content,
+            "## Work\n[ID:2] - [ ] Plan sprint\n\n### Admin\n[ID:3] - [ ] Invoice\n\n### ClientA\n#### Backend\n[ID:1] - [ ] Fix API\n\n## Uncategorized\n[ID:4] - [ ] Sort socks\n"
+        );
 
-        assert_eq!(original.id, parsed.id);
-        assert_eq!(original.title, parsed.title);
-        assert_eq!(original.is_complete, parsed.is_complete);
+        let loaded = storage.load().unwrap();
+        for task in collection.list_all() {
+            assert_eq!(loaded.get(task.id).unwrap().category, task.category);
+        }
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
     }
 
     #[test]
-    fn test_roundtrip_with_tags() {
-        let original = Task::new("Test task")
-            .with_id(6)
-            .with_tags(vec!["urgent".to_string(), "work".to_string()])
-            .with_category(Some("Work".to_string()));
-
-        let line = format_task_line(&original);
-        let parsed = parse_task_line(&line, original.category.clone()).unwrap();
-
-        assert_eq!(original.id, parsed.id);
-        assert_eq!(original.title, parsed.title);
-        assert_eq!(original.tags, parsed.tags);
-        assert_eq!(original.category, parsed.category);
+    fn test_roundtrip_links() {
+        let path = std::env::temp_dir().join(format!("taiga-links-{}.md", std::process::id()));
+        let storage = MarkdownStorage::new(&path);
+
+        let mut collection = TaskCollection::new();
+        let id = collection.add_task(
+            Task::new("Fix login")
+                .with_checklist(vec![ChecklistItem::new("Reproduce")])
+                .with_links(vec![
+                    "https://tracker.example/issues/42".to_string(),
+                    "/home/me/My Specs/login (v2).pdf".to_string(),
+                ])
+                .with_notes(Some("Steps:\n- [see](elsewhere)".to_string())),
+        );
+
+        storage.save(&collection).unwrap();
+        let content = without_metadata(&std::fs::read_to_string(&path).unwrap());
+        assert!(content.contains(
+            "[ID:1] - [ ] Fix login\n  - [ ] Reproduce\n  - [42](https://tracker.example/issues/42)\n  - [login (v2).pdf](</home/me/My Specs/login (v2).pdf>)\n  Steps:\n"
+        ));
+
+        // Link lines after the notes start stay part of the notes
+        let loaded = storage.load().unwrap();
+        let task = loaded.get(id).unwrap();
+        assert_eq!(task.checklist, collection.get(id).unwrap().checklist);
+        assert_eq!(task.links, collection.get(id).unwrap().links);
+        assert_eq!(task.notes, collection.get(id).unwrap().notes);
+
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(path.with_extension("md.bak")).ok();
     }
 }
@@ -0,0 +1,148 @@
+//! Task templates stored as TOML files
+//!
+//! Each template lives in `<data directory>/templates/<name>.toml`, written
+//! by `taiga template save` or by hand:
+//!
+//! ```toml
+//! description = "Client onboarding"
+//!
+//! [[task]]
+//! title = "Kick-off call with {{client}}"
+//! category = "Clients/{{client}}"
+//! tags = ["onboarding"]
+//! scheduled = "+1d 10:00"
+//!
+//! [[task]]
+//! title = "Send contract"
+//! parent = 1
+//! deadline = "+1w"
+//! ```
+
+use std::path::PathBuf;
+
+use taiga_core::Template;
+
+use crate::error::{CliError, Result};
+
+/// Directory of template files
+pub struct TemplateStore {
+    dir: PathBuf,
+}
+
+impl TemplateStore {
+    /// Create a store for the given directory (created on first save)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path of a template file, rejecting names that are not plain words
+    fn path(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(CliError::validation(
+                "name",
+                format!("Invalid template name '{}' (use letters, digits, '-' and '_')", name),
+            ));
+        }
+        Ok(self.dir.join(format!("{}.toml", name)))
+    }
+
+    /// Names of all templates, sorted
+    pub fn names(&self) -> Result<Vec<String>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&self.dir)
+            .map_err(|e| CliError::io("Failed to read templates directory", e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Load a template by name
+    pub fn load(&self, name: &str) -> Result<Template> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(CliError::validation(
+                "template",
+                format!("No template named '{}' (see 'taiga template list')", name),
+            ));
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| CliError::io(format!("Failed to read template '{}'", name), e))?;
+        toml::from_str(&content)
+            .map_err(|e| CliError::parse_with_source(format!("Invalid template '{}'", name), e))
+    }
+
+    /// Save a template, refusing to replace an existing one unless asked to
+    pub fn save(&self, name: &str, template: &Template, overwrite: bool) -> Result<PathBuf> {
+        let path = self.path(name)?;
+        if path.exists() && !overwrite {
+            return Err(CliError::validation(
+                "template",
+                format!("Template '{}' already exists (use --force to replace it)", name),
+            ));
+        }
+        let content = toml::to_string(template)
+            .map_err(|e| CliError::parse_with_source("Failed to write template", e))?;
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| CliError::io("Failed to create templates directory", e))?;
+        std::fs::write(&path, content)
+            .map_err(|e| CliError::io(format!("Failed to write template '{}'", name), e))?;
+        Ok(path)
+    }
+
+    /// Delete a template
+    pub fn remove(&self, name: &str) -> Result<()> {
+        let path = self.path(name)?;
+        if !path.exists() {
+            return Err(CliError::validation("template", format!("No template named '{}'", name)));
+        }
+        std::fs::remove_file(&path)
+            .map_err(|e| CliError::io(format!("Failed to delete template '{}'", name), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use taiga_core::TemplateTask;
+
+    #[test]
+    fn test_save_load_template() {
+        let dir = std::env::temp_dir().join(format!("taiga-templates-{}", std::process::id()));
+        let store = TemplateStore::new(&dir);
+
+        let template = Template {
+            description: Some("Client onboarding".to_string()),
+            tasks: vec![
+                TemplateTask {
+                    title: "Kick-off with {{client}}".to_string(),
+                    tags: vec!["onboarding".to_string()],
+                    scheduled: Some("+1d 10:00".to_string()),
+                    ..TemplateTask::default()
+                },
+                TemplateTask {
+                    title: "Send contract".to_string(),
+                    parent: Some(1),
+                    ..TemplateTask::default()
+                },
+            ],
+        };
+        store.save("onboarding", &template, false).unwrap();
+        assert!(store.save("onboarding", &template, false).is_err());
+        assert_eq!(store.names().unwrap(), vec!["onboarding"]);
+        assert_eq!(store.load("onboarding").unwrap(), template);
+
+        assert!(store.load("../escape").is_err());
+        store.remove("onboarding").unwrap();
+        assert!(store.names().unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
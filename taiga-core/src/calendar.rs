@@ -0,0 +1,303 @@
+//! Working days and holidays
+//!
+//! A [`WorkCalendar`] tells working days apart from weekends and holidays,
+//! for workday expressions such as "in 3 workdays" in [`crate::date`].
+//! Holidays are read from text the caller loads: an iCalendar (ICS) file or
+//! a plain list with one date per line:
+//!
+//! ```text
+//! # Fixed dates
+//! 2026-04-03 Good Friday
+//! 2026-04-06 Easter Monday
+//! # Every year
+//! 12-25 Christmas Day
+//! ```
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+use std::sync::{LazyLock, RwLock};
+
+use crate::error::{CoreError, Result};
+
+/// Calendar installed for date parsing, Monday to Friday until replaced
+static CURRENT: LazyLock<RwLock<WorkCalendar>> = LazyLock::new(|| RwLock::new(WorkCalendar::default()));
+
+/// Longest stretch searched for a working day before giving up
+const SEARCH_DAYS: usize = 3660;
+
+/// Which days are working days
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkCalendar {
+    /// Days of the working week, Monday first
+    pub working_week: Vec<Weekday>,
+    /// Holidays on a particular date
+    pub holidays: BTreeSet<NaiveDate>,
+    /// Holidays falling on the same day every year, as (month, day)
+    pub annual_holidays: BTreeSet<(u32, u32)>,
+}
+
+impl Default for WorkCalendar {
+    fn default() -> Self {
+        Self {
+            working_week: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            holidays: BTreeSet::new(),
+            annual_holidays: BTreeSet::new(),
+        }
+    }
+}
+
+impl WorkCalendar {
+    /// Create a calendar from a working week such as "mon-fri" or
+    /// "sun-thu" and, optionally, the contents of a holiday file
+    pub fn from_config(working_week: &str, holidays: Option<&str>) -> Result<Self> {
+        let mut calendar = Self::default().with_working_week(parse_working_week(working_week)?);
+        if let Some(content) = holidays {
+            calendar.add_holidays(content)?;
+        }
+        Ok(calendar)
+    }
+
+    /// Builder method to set the working week
+    pub fn with_working_week(mut self, days: Vec<Weekday>) -> Self {
+        self.working_week = days;
+        self
+    }
+
+    /// Add the holidays from an ICS file or a plain list, returns how many were read
+    pub fn add_holidays(&mut self, content: &str) -> Result<usize> {
+        let before = self.holidays.len() + self.annual_holidays.len();
+        if content.trim_start_matches('\u{feff}').trim_start().starts_with("BEGIN:VCALENDAR") {
+            self.add_ics_holidays(content)?;
+        } else {
+            self.add_listed_holidays(content)?;
+        }
+        Ok(self.holidays.len() + self.annual_holidays.len() - before)
+    }
+
+    /// Make this the calendar used by [`crate::date::parse_date`]
+    pub fn install(self) {
+        *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = self;
+    }
+
+    /// The calendar used by [`crate::date::parse_date`]
+    pub fn current() -> Self {
+        CURRENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Check if a date is a holiday
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date) || self.annual_holidays.contains(&(date.month(), date.day()))
+    }
+
+    /// Check if a date is in the working week and not a holiday
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.working_week.contains(&date.weekday()) && !self.is_holiday(date)
+    }
+
+    /// The date itself if it is a working day, otherwise the next one
+    pub fn working_day_on_or_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        date.iter_days().take(SEARCH_DAYS).find(|&day| self.is_working_day(day))
+    }
+
+    /// The first working day after a date
+    pub fn next_working_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.working_day_on_or_after(date.succ_opt()?)
+    }
+
+    /// Move a date by a number of working days (backwards when negative)
+    ///
+    /// Counting starts from the day after (or before) the date, so adding one
+    /// working day to a Friday gives the Monday after.
+    pub fn add_working_days(&self, date: NaiveDate, count: i64) -> Option<NaiveDate> {
+        let mut date = date;
+        let mut remaining = count.unsigned_abs();
+        let mut searched = 0;
+        while remaining > 0 {
+            date = if count < 0 {
+                date.checked_sub_days(Days::new(1))?
+            } else {
+                date.checked_add_days(Days::new(1))?
+            };
+            if self.is_working_day(date) {
+                remaining -= 1;
+                searched = 0;
+            } else {
+                searched += 1;
+                if searched > SEARCH_DAYS {
+                    return None;
+                }
+            }
+        }
+        Some(date)
+    }
+
+    /// Read holidays from a list: "YYYY-MM-DD" or "MM-DD" (every year) at
+    /// the start of each line, '#' starting a comment
+    fn add_listed_holidays(&mut self, content: &str) -> Result<()> {
+        for (number, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some(word) = line.split_whitespace().next() else {
+                continue;
+            };
+            if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+                self.holidays.insert(date);
+            } else if let Some(day) = parse_month_day(word) {
+                self.annual_holidays.insert(day);
+            } else {
+                return Err(CoreError::parse(format!(
+                    "Holiday list line {}: '{}' is not a date (use YYYY-MM-DD or MM-DD)",
+                    number + 1,
+                    word
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the all-day events of an iCalendar file; yearly recurring events
+    /// become annual holidays, other recurrence rules are not expanded
+    fn add_ics_holidays(&mut self, content: &str) -> Result<()> {
+        // Continuation lines start with a space or tab
+        let mut lines: Vec<String> = Vec::new();
+        for line in content.lines() {
+            match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+                (Some(rest), Some(last)) => last.push_str(rest),
+                _ => lines.push(line.trim_end().to_string()),
+            }
+        }
+
+        let (mut start, mut end, mut yearly) = (None, None, false);
+        for line in &lines {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let name = name.split(';').next().unwrap_or_default().to_uppercase();
+            match name.as_str() {
+                "BEGIN" if value == "VEVENT" => (start, end, yearly) = (None, None, false),
+                "DTSTART" => start = Some(parse_ics_date(value)?),
+                "DTEND" => end = Some(parse_ics_date(value)?),
+                "RRULE" => yearly = value.to_uppercase().split(';').any(|part| part == "FREQ=YEARLY"),
+                "END" if value == "VEVENT" => {
+                    let Some(first) = start.take() else {
+                        continue;
+                    };
+                    // DTEND is exclusive; an event without one lasts a day
+                    let last = end.take().and_then(|end| end.pred_opt()).filter(|&last| last > first);
+                    for day in first.iter_days().take_while(|&day| day <= last.unwrap_or(first)) {
+                        if yearly {
+                            self.annual_holidays.insert((day.month(), day.day()));
+                        } else {
+                            self.holidays.insert(day);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a working week: days or ranges separated by commas, e.g.
+/// "mon-fri", "sun-thu" or "mon,tue,thu"
+pub fn parse_working_week(spec: &str) -> Result<Vec<Weekday>> {
+    let day = |name: &str| -> Result<Weekday> {
+        name.trim().parse::<Weekday>().map_err(|_| {
+            CoreError::validation("working_week", format!("'{}' is not a day of the week", name.trim()))
+        })
+    };
+
+    let mut days = BTreeSet::new();
+    for part in spec.split(',').filter(|part| !part.trim().is_empty()) {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (day(from)?, day(to)?);
+                let mut current = from;
+                days.insert(current.num_days_from_monday() as u8);
+                while current != to {
+                    current = current.succ();
+                    days.insert(current.num_days_from_monday() as u8);
+                }
+            }
+            None => {
+                days.insert(day(part)?.num_days_from_monday() as u8);
+            }
+        }
+    }
+
+    if days.is_empty() {
+        return Err(CoreError::validation("working_week", "The working week needs at least one day"));
+    }
+    Ok(days.into_iter().filter_map(|n| Weekday::try_from(n).ok()).collect())
+}
+
+/// Parse "MM-DD"
+fn parse_month_day(word: &str) -> Option<(u32, u32)> {
+    let (month, day) = word.split_once('-')?;
+    let (month, day) = (month.parse().ok()?, day.parse().ok()?);
+    // 2024 is a leap year, so February 29 is accepted
+    NaiveDate::from_ymd_opt(2024, month, day).map(|_| (month, day))
+}
+
+/// Parse an iCalendar date or date-time value, keeping the date
+fn parse_ics_date(value: &str) -> Result<NaiveDate> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| CoreError::parse(format!("Invalid iCalendar date '{}'", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_working_days() {
+        let mut calendar = WorkCalendar::default();
+        calendar.add_holidays("2026-04-06 Easter Monday\n# comment\n\n12-25 Christmas").unwrap();
+
+        // Thursday 2026-04-02 + 2 workdays skips the weekend and Easter Monday
+        assert_eq!(calendar.add_working_days(date("2026-04-02"), 2), Some(date("2026-04-07")));
+        assert_eq!(calendar.add_working_days(date("2026-04-07"), -1), Some(date("2026-04-03")));
+        assert_eq!(calendar.next_working_day(date("2026-04-03")), Some(date("2026-04-07")));
+        assert_eq!(calendar.working_day_on_or_after(date("2026-04-02")), Some(date("2026-04-02")));
+        assert!(!calendar.is_working_day(date("2027-12-25")));
+
+        assert!(calendar.add_holidays("Christmas 12-25").is_err());
+    }
+
+    #[test]
+    fn test_parse_working_week() {
+        use Weekday::*;
+        assert_eq!(parse_working_week("mon-fri").unwrap(), vec![Mon, Tue, Wed, Thu, Fri]);
+        assert_eq!(parse_working_week("sun-thu").unwrap(), vec![Mon, Tue, Wed, Thu, Sun]);
+        assert_eq!(parse_working_week("Mon, Wednesday").unwrap(), vec![Mon, Wed]);
+        assert!(parse_working_week("").is_err());
+        assert!(parse_working_week("mon-funday").is_err());
+    }
+
+    #[test]
+    fn test_ics_holidays() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\n\
+                   SUMMARY:Christmas\r\n\
+                   DTSTART;VALUE=DATE:20261225\r\n\
+                   RRULE:FREQ=YEARLY\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   SUMMARY:Company retreat\r\n\
+                   DTSTART;VALUE=DATE:20260601\r\n\
+                   DTEND;VALUE=DATE:20260603\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        let mut calendar = WorkCalendar::default();
+        assert_eq!(calendar.add_holidays(ics).unwrap(), 3);
+        assert!(calendar.is_holiday(date("2030-12-25")));
+        assert!(calendar.is_holiday(date("2026-06-02")));
+        assert!(!calendar.is_holiday(date("2026-06-03")));
+    }
+}
@@ -0,0 +1,110 @@
+//! Nested category paths
+//!
+//! Categories form a tree written as a path of names separated by slashes,
+//! e.g. `Work/ClientA/Backend`. A task belongs to exactly one category, and
+//! every category implicitly contains its subcategories.
+
+use std::cmp::Ordering;
+
+/// Separator between the levels of a category path
+pub const SEPARATOR: char = '/';
+
+/// Normalize user input to a category path
+///
+/// Trims every level and drops empty ones, so " Work / ClientA/" becomes
+/// "Work/ClientA". Returns None when nothing is left.
+pub fn normalize(input: &str) -> Option<String> {
+    let path = components(input).collect::<Vec<_>>().join("/");
+    (!path.is_empty()).then_some(path)
+}
+
+/// Names of the levels of a path, from the top
+pub fn components(path: &str) -> impl Iterator<Item = &str> {
+    path.split(SEPARATOR).map(str::trim).filter(|part| !part.is_empty())
+}
+
+/// Number of levels above the category (0 for a top-level category)
+pub fn depth(path: &str) -> usize {
+    components(path).count().saturating_sub(1)
+}
+
+/// Name of the category without its parents
+pub fn name(path: &str) -> &str {
+    components(path).last().unwrap_or(path)
+}
+
+/// Path of the parent category, if any
+pub fn parent(path: &str) -> Option<&str> {
+    path.rfind(SEPARATOR).map(|i| path[..i].trim_end())
+}
+
+/// Check if a category is `ancestor` or one of its subcategories
+pub fn is_within(path: &str, ancestor: &str) -> bool {
+    let mut levels = components(path);
+    components(ancestor).all(|level| levels.next() == Some(level))
+}
+
+/// Order categories as a tree: level by level, case-insensitively, so that
+/// subcategories directly follow their parent
+pub fn compare(a: &str, b: &str) -> Ordering {
+    lowercase_levels(a).cmp(&lowercase_levels(b)).then_with(|| a.cmp(b))
+}
+
+fn lowercase_levels(path: &str) -> Vec<String> {
+    components(path).map(str::to_lowercase).collect()
+}
+
+/// The given categories together with all their ancestors, in tree order
+pub fn with_ancestors<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut all: Vec<String> = Vec::new();
+    for path in paths {
+        let mut current = String::new();
+        for level in components(path) {
+            if !current.is_empty() {
+                current.push(SEPARATOR);
+            }
+            current.push_str(level);
+            all.push(current.clone());
+        }
+    }
+    all.sort_by(|a, b| compare(a, b));
+    all.dedup();
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(" Work / ClientA/").as_deref(), Some("Work/ClientA"));
+        assert_eq!(normalize("Home").as_deref(), Some("Home"));
+        assert_eq!(normalize(" / "), None);
+    }
+
+    #[test]
+    fn test_path_parts() {
+        assert_eq!(depth("Work/ClientA/Backend"), 2);
+        assert_eq!(name("Work/ClientA/Backend"), "Backend");
+        assert_eq!(parent("Work/ClientA/Backend"), Some("Work/ClientA"));
+        assert_eq!(parent("Work"), None);
+    }
+
+    #[test]
+    fn test_is_within() {
+        assert!(is_within("Work/ClientA", "Work"));
+        assert!(is_within("Work", "Work"));
+        assert!(!is_within("Workshop", "Work"));
+        assert!(!is_within("Work", "Work/ClientA"));
+    }
+
+    #[test]
+    fn test_tree_order() {
+        let all = with_ancestors(["Workshop", "Work/ClientA/Backend", "home", "Work/Admin"]);
+        assert_eq!(
+            all,
+            vec!["home", "Work", "Work/Admin", "Work/ClientA", "Work/ClientA/Backend", "Workshop"]
+        );
+    }
+}
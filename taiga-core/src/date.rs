@@ -3,88 +3,337 @@
 //! Supports various date formats:
 //! - ISO dates: "2026-01-25"
 //! - Human dates: "Jan 25", "January 25 2026"
-//! - Relative: "today", "tomorrow", "monday", "next friday"
-//! - Offset: "in 3 days", "in 1 week"
+//! - Relative: "today", "tomorrow", "monday", "next friday", "next week"
+//! - Period ends: "end of month", "eow"
+//! - Offset: "in 3 days", "in 3 months", "+2w", "-1d"
+//! - Within a month: "first monday of march", "last friday of the month", "the 15th"
+//! - Time of day: "tomorrow 3pm", "fri 09:30", "in 2 hours"
+//! - Ranges for filtering: "this week", "next 7 days", "before friday", "today..eom"
+//!
+//! Dates may also be written in the installed [`Locale`]'s language
+//! ("nächsten Freitag", "mañana a las 9"), see [`crate::locale`].
+//!
+//! A scheduled time of exactly midnight means "no particular time of day".
+//! Dates are read and shown in the installed [`Zone`], and midnight is
+//! judged there too, see [`crate::zone`].
 
-use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+use chrono::{
+    DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Timelike, Weekday,
+};
+use regex::Regex;
+use std::sync::LazyLock;
 
+use crate::calendar::WorkCalendar;
 use crate::error::{CoreError, Result};
+use crate::locale::{DateOrder, Locale};
+use crate::zone::Zone;
+
+// Trailing time of day: "3pm", "3:30 pm", "at 15:00", "09:30"
+static TIME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|\s)(?:at\s+)?(\d{1,2})(?::(\d{2}))?\s*(am|pm)?$")
+        .expect("Invalid time regex pattern")
+});
+
+// Short offset from today: "+2w", "-1d", "+3m", "+1y"
+static SHORT_OFFSET_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([+-])(\d+)\s?([dwmy])$").expect("Invalid offset regex pattern")
+});
+
+// Weekday within a month: "first monday of march", "last friday of the month"
+static WEEKDAY_OF_MONTH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(first|second|third|fourth|fifth|last|1st|2nd|3rd|4th|5th) ([a-z]+) of (.+)$")
+        .expect("Invalid weekday-of-month regex pattern")
+});
+
+// Day of the month: "the 15th", "1st of june"
+static DAY_OF_MONTH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:the )?(\d{1,2})(?:st|nd|rd|th)(?: of (.+))?$")
+        .expect("Invalid day-of-month regex pattern")
+});
+
+/// Parse a date with an optional time of day into a local DateTime
+///
+/// Accepts everything [`parse_date`] does, optionally followed by a time
+/// ("tomorrow 3pm", "fri 09:30", "2026-01-25 15:00"), a bare time for today
+/// ("3pm", "noon") or an offset from now ("in 2 hours", "in 30 minutes").
+/// Without a time the result is at midnight, a floating date; a time of day
+/// is taken in the installed [`Zone`].
+pub fn parse_datetime(input: &str) -> Result<DateTime<Local>> {
+    let input = input.trim().to_lowercase();
+    let translated = Locale::current().translate(&input);
+    match parse_datetime_words(&input) {
+        Err(_) if !translated.is_empty() && translated != input => parse_datetime_words(&translated),
+        result => result,
+    }
+}
+
+fn parse_datetime_words(input: &str) -> Result<DateTime<Local>> {
+    if let Some(dt) = try_parse_time_offset(input, Local::now()) {
+        return Ok(dt);
+    }
+
+    let (date_part, time) = split_time(input)?;
+    let zone = Zone::current();
+    let date = if date_part.is_empty() {
+        zone.today()
+    } else {
+        parse_date(date_part)?
+    };
+
+    let datetime = match time {
+        Some(time) => zone.from_naive(&date.and_time(time)),
+        None => floating_date(date),
+    };
+    datetime.ok_or_else(|| CoreError::parse(format!("'{}' does not exist in the {} time zone", input, zone)))
+}
+
+/// Parse a task date as stored: "2026-01-25" (floating), "2026-01-25 15:00
+/// Europe/Berlin" (zoned) or "2026-01-25 15:00" (in the installed zone);
+/// returns the zone when one is given
+pub fn parse_task_date(value: &str) -> Option<(DateTime<Local>, Option<Zone>)> {
+    let value = value.trim();
+    let (datetime, zone) = match value.rsplit_once(' ') {
+        Some((datetime, zone)) if datetime.contains(' ') => (datetime, Some(Zone::parse(zone).ok()?)),
+        _ => (value, None),
+    };
+
+    if let Ok(naive) = NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M") {
+        let dt = zone.unwrap_or_else(Zone::current).from_naive(&naive)?;
+        return Some((dt, zone));
+    }
+    let date = NaiveDate::parse_from_str(datetime, "%Y-%m-%d").ok()?;
+    Some((floating_date(date)?, None))
+}
+
+/// Format a task date for storage: floating dates as "2026-01-25", times of
+/// day with their zone, "2026-01-25 15:00 Europe/Berlin"
+///
+/// Times are written in `zone`, or the installed zone if None.
+pub fn format_task_date(dt: &DateTime<Local>, zone: Option<Zone>) -> String {
+    if !has_time_of_day(dt) {
+        return wall_time(dt).format("%Y-%m-%d").to_string();
+    }
+    let zone = zone.unwrap_or_else(Zone::current).resolve(dt);
+    format!("{} {}", zone.to_naive(dt).format("%Y-%m-%d %H:%M"), zone)
+}
+
+/// Format a timestamp compactly to the minute, e.g. "20260125T1403"
+pub fn format_timestamp(dt: &DateTime<Local>) -> String {
+    dt.format("%Y%m%dT%H%M").to_string()
+}
+
+/// Parse a timestamp written by [`format_timestamp`]
+pub fn parse_timestamp(input: &str) -> Option<DateTime<Local>> {
+    let datetime = NaiveDateTime::parse_from_str(input.trim(), "%Y%m%dT%H%M").ok()?;
+    Local.from_local_datetime(&datetime).earliest()
+}
+
+/// Check whether a task date carries a time of day (i.e. is not midnight in
+/// the installed [`Zone`])
+pub fn has_time_of_day(dt: &DateTime<Local>) -> bool {
+    wall_time(dt).time() != NaiveTime::MIN
+}
+
+/// A floating date, without a time of day: midnight in the installed [`Zone`]
+pub fn floating_date(date: NaiveDate) -> Option<DateTime<Local>> {
+    Zone::current().from_naive(&date.and_time(NaiveTime::MIN))
+}
+
+/// Split a trailing time of day off the input
+fn split_time(input: &str) -> Result<(&str, Option<NaiveTime>)> {
+    for (word, hour) in [("noon", 12), ("midnight", 0)] {
+        // Whole words only: "sat noon" is Saturday, "afternoon" no time
+        let Some(rest) = input.strip_suffix(word) else {
+            continue;
+        };
+        if !rest.is_empty() && !rest.ends_with(' ') {
+            continue;
+        }
+        let rest = rest.trim_end();
+        let rest = match rest.strip_suffix(" at") {
+            Some(before) => before,
+            None if rest == "at" => "",
+            None => rest,
+        };
+        return Ok((rest.trim(), NaiveTime::from_hms_opt(hour, 0, 0)));
+    }
+
+    let Some(caps) = TIME_REGEX.captures(input) else {
+        return Ok((input, None));
+    };
+
+    // A bare number is a day ("Jan 25"), not a time
+    let minute = caps.get(2);
+    let meridiem = caps.get(3).map(|m| m.as_str());
+    if minute.is_none() && meridiem.is_none() {
+        return Ok((input, None));
+    }
+
+    let hour: u32 = caps[1]
+        .parse()
+        .map_err(|e| CoreError::parse_with_source("Invalid hour", e))?;
+    let minute: u32 = minute
+        .map_or(Ok(0), |m| m.as_str().parse())
+        .map_err(|e| CoreError::parse_with_source("Invalid minute", e))?;
+
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => None,
+        Some("am") => Some(hour % 12),
+        Some(_) => Some(hour % 12 + 12),
+        None => Some(hour),
+    };
+
+    let time = hour
+        .and_then(|h| NaiveTime::from_hms_opt(h, minute, 0))
+        .ok_or_else(|| CoreError::parse(format!("Invalid time of day in '{}'", input)))?;
+
+    let start = caps.get(0).map_or(input.len(), |m| m.start());
+    Ok((input[..start].trim(), Some(time)))
+}
+
+/// Parse "in N hours/minutes" relative to now, rounded down to the minute
+fn try_parse_time_offset(input: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let rest = input.strip_prefix("in ")?.trim();
+    let parts: Vec<&str> = rest.split_whitespace().collect();
+
+    if parts.len() != 2 {
+        return None;
+    }
+
+    let num: i64 = parts[0].parse().ok()?;
+    let offset = match parts[1] {
+        "hour" | "hours" | "hr" | "hrs" | "h" => Duration::hours(num),
+        "minute" | "minutes" | "min" | "mins" | "m" => Duration::minutes(num),
+        _ => return None,
+    };
+
+    let dt = now.checked_add_signed(offset)?;
+    dt.with_second(0).and_then(|dt| dt.with_nanosecond(0))
+}
+
+/// Grammar accepted by [`parse_date`], listed when an input is not understood
+const DATE_GRAMMAR: &str = "today, tomorrow, yesterday; [this|next|last] monday..sunday; \
+next week/month/year; next workday (business day); \
+end of week/work week/month/year (eow, eoww, eom, eoy); \
+in N days/weeks/months/years/workdays; +N or -N with d, w, m or y (+2w, -1d); \
+first..fifth or last <weekday> of <month>/the month/next month; \
+the 15th [of <month>]; 2026-01-25, Jan 25 [2026], 25 Jan 2026, 01/25[/2026] \
+(25/01[/2026] or 25.01.[2026] in German and Spanish)";
 
 /// Parse a date string into a NaiveDate
 ///
 /// Supports multiple formats:
 /// - ISO: "2026-01-25"
 /// - Human: "Jan 25", "January 25", "Jan 25 2026"
-/// - Relative: "today", "tomorrow"
+/// - Relative: "today", "tomorrow", "yesterday"
 /// - Weekdays: "monday", "tuesday", etc. (next occurrence)
-/// - Prefixed: "next monday", "next friday"
-/// - Offset: "in 3 days", "in 1 week", "in 2 weeks"
+/// - Prefixed: "next monday", "next friday", "last monday" (the one before today)
+/// - Periods: "next week" (its Monday), "next month", "next year" (their first day)
+/// - Period ends: "end of week" / "eow" (Sunday), "end of work week" / "eoww"
+///   (last day of the working week), "end of month" / "eom", "end of year" / "eoy"
+/// - Offset: "in 3 days", "in 1 week", "in 3 months", "+2w", "-1d"
+/// - Working days: "in 3 workdays" (or business/working days), "next workday"
+///   (or business/working day), skipping weekends and holidays of the
+///   installed [`WorkCalendar`]
+/// - Weekday in a month: "first monday of march", "last friday of the month"
+/// - Day of month: "the 15th" (next one to come), "the 1st of june"
+/// - The same in the installed [`Locale`]'s language, whose numeric dates
+///   may put the day first ("25/01", "25.01.2026")
 pub fn parse_date(input: &str) -> Result<NaiveDate> {
-    let input = input.trim().to_lowercase();
+    parse_date_on(input, Zone::current().today(), &WorkCalendar::current(), &Locale::current())
+}
 
-    // Try relative dates first
-    if let Some(date) = try_parse_relative(&input) {
-        return Ok(date);
-    }
+/// Parse a date string relative to the given day, with the given working
+/// days and language
+pub fn parse_date_on(input: &str, today: NaiveDate, calendar: &WorkCalendar, locale: &Locale) -> Result<NaiveDate> {
+    let input = input.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+
+    parse_date_words(&input, today, calendar, locale.date_order)
+        .or_else(|| parse_date_words(&locale.translate(&input), today, calendar, locale.date_order))
+        .ok_or_else(|| {
+            CoreError::parse(format!(
+                "Could not parse date '{}'. Supported forms: {}",
+                input, DATE_GRAMMAR
+            ))
+        })
+}
 
-    // Try weekday parsing
-    if let Some(date) = try_parse_weekday(&input) {
-        return Ok(date);
+/// Parse a normalized date expression in English
+fn parse_date_words(input: &str, today: NaiveDate, calendar: &WorkCalendar, order: DateOrder) -> Option<NaiveDate> {
+    if let Some(date) = try_parse_workdays(input, today, calendar) {
+        return Some(date);
     }
 
-    // Try offset parsing ("in X days/weeks")
-    if let Some(date) = try_parse_offset(&input) {
-        return Ok(date);
+    let parsers: [fn(&str, NaiveDate) -> Option<NaiveDate>; 7] = [
+        try_parse_relative,
+        try_parse_period,
+        try_parse_weekday,
+        try_parse_offset,
+        try_parse_short_offset,
+        try_parse_weekday_of_month,
+        try_parse_day_of_month,
+    ];
+    if let Some(date) = parsers.iter().find_map(|parse| parse(input, today)) {
+        return Some(date);
     }
 
     // Try ISO format
-    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
-        return Ok(date);
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    // Numeric dates in the locale's order; German ones may end in a dot
+    let (numeric_formats, yearless_numeric_formats): (&[&str], &[&str]) = match order {
+        DateOrder::MonthDay => (&["%m/%d/%Y"], &["%m/%d"]), // 01/25/2026, 01/25
+        DateOrder::DayMonth => (&["%d/%m/%Y", "%d.%m.%Y"], &["%d/%m", "%d.%m"]), // 25/01/2026, 25.01.
+    };
+    let numeric = input.trim_end_matches('.');
+    for format in numeric_formats {
+        if let Ok(date) = NaiveDate::parse_from_str(numeric, format) {
+            return Some(date);
+        }
     }
 
     // Try various human-readable formats
     let formats = [
         "%b %d %Y", // Jan 25 2026
         "%B %d %Y", // January 25 2026
-        "%b %d",    // Jan 25
-        "%B %d",    // January 25
-        "%m/%d/%Y", // 01/25/2026
-        "%m/%d",    // 01/25
         "%d %b %Y", // 25 Jan 2026
         "%d %B %Y", // 25 January 2026
     ];
 
     for format in &formats {
-        if let Ok(mut date) = NaiveDate::parse_from_str(&input, format) {
-            // If year is not specified, add current year
-            if !input.contains(|c: char| {
-                c.is_numeric() && input.matches(|d: char| d.is_numeric()).count() > 4
-            }) {
-                let current_year = Local::now().year();
-                date = date
-                    .with_year(current_year)
-                    .ok_or_else(|| CoreError::parse("Invalid date"))?;
-
-                // If the date has passed this year, use next year
-                if date < Local::now().date_naive() {
-                    date = date
-                        .with_year(current_year + 1)
-                        .ok_or_else(|| CoreError::parse("Invalid date"))?;
-                }
-            }
-            return Ok(date);
+        if let Ok(date) = NaiveDate::parse_from_str(input, format) {
+            return Some(date);
         }
     }
 
-    Err(CoreError::parse(format!(
-        "Could not parse date '{}'. Try formats like: 'tomorrow', 'Jan 25', '2026-01-25', 'next monday', 'in 3 days'",
-        input
-    )))
-}
+    // Without a year: this year's date, or next year's once it has passed
+    let yearless_formats = [
+        "%b %d", // Jan 25
+        "%B %d", // January 25
+        "%d %b", // 25 Jan
+        "%d %B", // 25 January
+    ];
 
-fn try_parse_relative(input: &str) -> Option<NaiveDate> {
-    let today = Local::now().date_naive();
+    let yearless = yearless_numeric_formats
+        .iter()
+        .map(|format| (numeric, format))
+        .chain(yearless_formats.iter().map(|format| (input, format)));
+    for (input, format) in yearless {
+        let with_year = |year: i32| {
+            NaiveDate::parse_from_str(&format!("{} {}", input, year), &format!("{} %Y", format)).ok()
+        };
+        if let Some(date) = with_year(today.year()) {
+            return Some(if date < today { with_year(today.year() + 1).unwrap_or(date) } else { date });
+        }
+    }
 
+    None
+}
+
+fn try_parse_relative(input: &str, today: NaiveDate) -> Option<NaiveDate> {
     match input {
         "today" => Some(today),
         "tomorrow" => today.checked_add_days(Days::new(1)),
@@ -93,100 +342,470 @@ fn try_parse_relative(input: &str) -> Option<NaiveDate> {
     }
 }
 
-fn try_parse_weekday(input: &str) -> Option<NaiveDate> {
-    let today = Local::now().date_naive();
-    let input = input.trim();
+/// Parse the start of the next period or the end of the current one
+fn try_parse_period(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    match input {
+        "next week" => Some(monday + Duration::days(7)),
+        "next month" => first_of_month(today)?.checked_add_months(Months::new(1)),
+        "next year" => NaiveDate::from_ymd_opt(today.year() + 1, 1, 1),
+        "end of week" | "end of the week" | "eow" => Some(monday + Duration::days(6)),
+        "end of month" | "end of the month" | "eom" => last_of_month(today.year(), today.month()),
+        "end of year" | "end of the year" | "eoy" => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        _ => None,
+    }
+}
 
-    // Handle "next <weekday>"
-    let (weekday_str, use_next_week) = if let Some(stripped) = input.strip_prefix("next ") {
-        (stripped, true)
-    } else {
-        (input, false)
-    };
+fn try_parse_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    // "last <weekday>" is the most recent one before today
+    if let Some(weekday) = input.strip_prefix("last ").and_then(parse_weekday) {
+        let days = (today.weekday().num_days_from_monday() as i64
+            - weekday.num_days_from_monday() as i64
+            + 6)
+            % 7
+            + 1;
+        return today.checked_sub_days(Days::new(days as u64));
+    }
 
-    let target_weekday = match weekday_str {
-        "monday" | "mon" => Weekday::Mon,
-        "tuesday" | "tue" | "tues" => Weekday::Tue,
-        "wednesday" | "wed" => Weekday::Wed,
-        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
-        "friday" | "fri" => Weekday::Fri,
-        "saturday" | "sat" => Weekday::Sat,
-        "sunday" | "sun" => Weekday::Sun,
-        _ => return None,
-    };
+    // "next <weekday>" and "this <weekday>" both mean the coming one
+    let weekday_str = input
+        .strip_prefix("next ")
+        .or_else(|| input.strip_prefix("this "))
+        .unwrap_or(input);
+    let target_weekday = parse_weekday(weekday_str)?;
 
-    let current_weekday = today.weekday();
-    let days_until = if use_next_week {
-        // Always go to next week
-        let days = (target_weekday.num_days_from_monday() as i64
-            - current_weekday.num_days_from_monday() as i64
-            + 7)
-            % 7;
-        if days == 0 { 7 } else { days as u64 }
-    } else {
-        // Go to next occurrence (could be today if it matches)
-        let days = (target_weekday.num_days_from_monday() as i64
-            - current_weekday.num_days_from_monday() as i64
-            + 7)
-            % 7;
-        if days == 0 { 7 } else { days as u64 }
-    };
+    // Go to next occurrence (never today)
+    let days = (target_weekday.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64
+        + 7)
+        % 7;
+    let days_until = if days == 0 { 7 } else { days as u64 };
 
     today.checked_add_days(Days::new(days_until))
 }
 
-fn try_parse_offset(input: &str) -> Option<NaiveDate> {
-    let today = Local::now().date_naive();
-    let input = input.trim();
+/// Weeks searched for the end of a work week that is all holidays
+const SEARCH_WEEKS: usize = 52;
+
+/// Parse "next workday", "in 3 business days" and the end of the work week
+fn try_parse_workdays(input: &str, today: NaiveDate, calendar: &WorkCalendar) -> Option<NaiveDate> {
+    const UNITS: [&str; 3] = ["workday", "business day", "working day"];
+
+    if let Some(unit) = input.strip_prefix("next ") {
+        return UNITS
+            .contains(&unit)
+            .then(|| calendar.next_working_day(today))
+            .flatten();
+    }
 
-    // Match "in X day(s)" or "in X week(s)"
-    if !input.starts_with("in ") {
+    if matches!(input, "end of work week" | "end of the work week" | "eoww") {
+        // Last working day of the current run of working weekdays, or of the
+        // next run when today is not in one ("mon-fri", "sun-thu")
+        let in_week = |day: &NaiveDate| calendar.working_week.contains(&day.weekday());
+        let mut start = today;
+        for _ in 0..SEARCH_WEEKS {
+            start = start.iter_days().take(7).find(in_week)?;
+            let run = start.iter_days().take(7).take_while(in_week).count() as i64;
+            let end = start + Duration::days(run - 1);
+            if let Some(day) = (0..run)
+                .map(|n| end - Duration::days(n))
+                .find(|&day| calendar.is_working_day(day))
+            {
+                return Some(day);
+            }
+            start = end + Duration::days(1);
+        }
         return None;
     }
 
-    let rest = input.strip_prefix("in ")?.trim();
+    let rest = input.strip_prefix("in ")?;
+    let (num, unit) = rest.split_once(' ')?;
+    let num: i64 = match num {
+        "a" | "one" => 1,
+        n => n.parse().ok()?,
+    };
+    UNITS
+        .contains(&unit.trim_end_matches('s'))
+        .then(|| calendar.add_working_days(today, num))
+        .flatten()
+}
+
+/// Parse "in X days/weeks/months/years" ("a" or "an" for one)
+fn try_parse_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let rest = input.strip_prefix("in ")?;
     let parts: Vec<&str> = rest.split_whitespace().collect();
 
     if parts.len() != 2 {
         return None;
     }
 
-    let num: u64 = parts[0].parse().ok()?;
-    let unit = parts[1].to_lowercase();
+    let num: u32 = match parts[0] {
+        "a" | "an" => 1,
+        n => n.parse().ok()?,
+    };
+    let unit = match parts[1].trim_end_matches('s') {
+        "day" => 'd',
+        "week" => 'w',
+        "month" => 'm',
+        "year" => 'y',
+        _ => return None,
+    };
+    add_offset(today, i64::from(num), unit)
+}
+
+/// Parse "+2w", "-1d", "+3m" or "+1y"
+fn try_parse_short_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let caps = SHORT_OFFSET_REGEX.captures(input)?;
+    let num: i64 = caps[2].parse().ok()?;
+    let num = if &caps[1] == "-" { -num } else { num };
+    add_offset(today, num, caps[3].chars().next()?)
+}
 
-    match unit.as_str() {
-        "day" | "days" => today.checked_add_days(Days::new(num)),
-        "week" | "weeks" => today.checked_add_days(Days::new(num * 7)),
+/// Move a date by a number of days (d), weeks (w), months (m) or years (y)
+///
+/// Months and years keep the day of the month where it exists and fall
+/// back to the month's last day otherwise (Jan 31 + 1 month = Feb 28).
+fn add_offset(date: NaiveDate, num: i64, unit: char) -> Option<NaiveDate> {
+    let months = |n: i64| -> Option<NaiveDate> {
+        let count = Months::new(u32::try_from(n.unsigned_abs()).ok()?);
+        if n < 0 { date.checked_sub_months(count) } else { date.checked_add_months(count) }
+    };
+    match unit {
+        'd' => date.checked_add_signed(Duration::try_days(num)?),
+        'w' => date.checked_add_signed(Duration::try_weeks(num)?),
+        'm' => months(num),
+        'y' => months(num.checked_mul(12)?),
         _ => None,
     }
 }
 
-/// Format a NaiveDate for human-readable display
+/// Parse "first monday of march", "last friday of the month",
+/// "2nd tuesday of next month"
+fn try_parse_weekday_of_month(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let caps = WEEKDAY_OF_MONTH_REGEX.captures(input)?;
+    let nth = match &caps[1] {
+        "first" | "1st" => 1,
+        "second" | "2nd" => 2,
+        "third" | "3rd" => 3,
+        "fourth" | "4th" => 4,
+        "fifth" | "5th" => 5,
+        _ => 0, // last
+    };
+    let weekday = parse_weekday(&caps[2])?;
+    resolve_in_month(&caps[3], today, |year, month| {
+        if nth > 0 {
+            NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth)
+        } else {
+            let last = last_of_month(year, month)?;
+            let back = (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+            last.checked_sub_days(Days::new(back.into()))
+        }
+    })
+}
+
+/// Parse "the 15th" (the next one on or after today) or "the 1st of june"
+fn try_parse_day_of_month(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let caps = DAY_OF_MONTH_REGEX.captures(input)?;
+    let day: u32 = caps[1].parse().ok()?;
+    if let Some(month) = caps.get(2) {
+        return resolve_in_month(month.as_str(), today, |year, month| NaiveDate::from_ymd_opt(year, month, day));
+    }
+
+    // Skip months that are too short for the day
+    let first = first_of_month(today)?;
+    (0..12)
+        .filter_map(|n| first.checked_add_months(Months::new(n)))
+        .filter_map(|month| month.with_day(day))
+        .find(|&date| date >= today)
+}
+
+/// Resolve a date within "march", "march 2027", "the month", "this month"
+/// or "next month"; a month name without a year means its next occurrence
+fn resolve_in_month(
+    spec: &str,
+    today: NaiveDate,
+    date_in: impl Fn(i32, u32) -> Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    match spec {
+        "the month" | "this month" => return date_in(today.year(), today.month()),
+        "next month" => {
+            let next = first_of_month(today)?.checked_add_months(Months::new(1))?;
+            return date_in(next.year(), next.month());
+        }
+        _ => {}
+    }
+
+    let (name, year) = match spec.split_once(' ') {
+        Some((name, year)) => (name, Some(year.parse::<i32>().ok()?)),
+        None => (spec, None),
+    };
+    let month = parse_month(name)?;
+    match year {
+        Some(year) => date_in(year, month),
+        None => date_in(today.year(), month)
+            .filter(|&date| date >= today)
+            .or_else(|| date_in(today.year() + 1, month)),
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thur" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Month number from its full or three-letter name ("sept" also works)
+fn parse_month(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june", "july", "august", "september",
+        "october", "november", "december",
+    ];
+    let word = if word == "sept" { "sep" } else { word };
+    MONTHS
+        .iter()
+        .position(|name| *name == word || (word.len() == 3 && name.starts_with(word)))
+        .map(|i| i as u32 + 1)
+}
+
+fn first_of_month(date: NaiveDate) -> Option<NaiveDate> {
+    date.with_day(1)
+}
+
+fn last_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, 1)?
+        .checked_add_months(Months::new(1))?
+        .pred_opt()
+}
+
+/// Grammar accepted by [`parse_date_range`], listed when an input is not understood
+const RANGE_GRAMMAR: &str = "this/next/last week, month or year; next/last N days, weeks or months; \
+before/after/since/until <date>; <date> to <date>, <date>..<date>, between <date> and <date> \
+(either end of '..' may be left out); or a single date";
+
+/// An inclusive range of days; a missing end leaves that side open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DateRange {
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// Create a range from its first and last day
+    pub fn new(start: Option<NaiveDate>, end: Option<NaiveDate>) -> Self {
+        Self { start, end }
+    }
+
+    /// A single day
+    pub fn day(date: NaiveDate) -> Self {
+        Self::new(Some(date), Some(date))
+    }
+
+    /// Every day before a date
+    pub fn before(date: NaiveDate) -> Self {
+        Self::new(None, Some(date.pred_opt().unwrap_or(date)))
+    }
+
+    /// Every day after a date
+    pub fn after(date: NaiveDate) -> Self {
+        Self::new(Some(date.succ_opt().unwrap_or(date)), None)
+    }
+
+    /// Check if a day is within the range
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start.is_none_or(|start| date >= start) && self.end.is_none_or(|end| date <= end)
+    }
+}
+
+/// Parse a date range
+///
+/// Supports:
+/// - Periods: "this week" (Monday to Sunday), "next month", "last year"
+/// - Rolling: "next 7 days" (today and the six after), "last 2 weeks"
+///   (ending today), "next 3 months"
+/// - Open ends: "before friday", "after last monday" (both exclusive),
+///   "since monday", "until eom" (both inclusive)
+/// - Between dates: "today to friday", "2026-11-01..2026-11-15", "..eom",
+///   "between today and next friday"
+/// - A single date, as accepted by [`parse_date`]: "today", "next friday"
+///
+/// Dates within a range may be anything [`parse_date`] accepts; a period
+/// used as an end ("before this week") counts as all of its days. Ranges
+/// may be written in the installed [`Locale`]'s language ("diese Woche",
+/// "hasta el viernes").
+pub fn parse_date_range(input: &str) -> Result<DateRange> {
+    parse_date_range_on(input, Zone::current().today(), &WorkCalendar::current(), &Locale::current())
+}
+
+/// Parse a date range relative to the given day, with the given working
+/// days and language
+pub fn parse_date_range_on(
+    input: &str,
+    today: NaiveDate,
+    calendar: &WorkCalendar,
+    locale: &Locale,
+) -> Result<DateRange> {
+    let input = input.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let translated = locale.translate(&input);
+    match parse_range_words(&input, today, calendar, locale.date_order) {
+        Err(_) if translated != input => parse_range_words(&translated, today, calendar, locale.date_order),
+        result => result,
+    }
+}
+
+/// Parse a normalized date range in English
+fn parse_range_words(input: &str, today: NaiveDate, calendar: &WorkCalendar, order: DateOrder) -> Result<DateRange> {
+    let range = |input: &str| parse_range_words(input, today, calendar, order);
+
+    let parsed = if let Some(rest) = input.strip_prefix("before ") {
+        DateRange::new(None, range(rest)?.start.and_then(|start| start.pred_opt()))
+    } else if let Some(rest) = input.strip_prefix("after ") {
+        DateRange::new(range(rest)?.end.and_then(|end| end.succ_opt()), None)
+    } else if let Some(rest) = input.strip_prefix("since ") {
+        DateRange::new(range(rest)?.start, None)
+    } else if let Some(rest) = input.strip_prefix("until ") {
+        DateRange::new(None, range(rest)?.end)
+    } else if let Some((from, to)) = split_range(input) {
+        let from = if from.is_empty() { None } else { range(from)?.start };
+        let to = if to.is_empty() { None } else { range(to)?.end };
+        DateRange::new(from, to)
+    } else if let Some(period) = try_parse_period_range(input, today) {
+        period
+    } else {
+        let date = parse_date_words(input, today, calendar, order).ok_or_else(|| {
+            CoreError::parse(format!(
+                "Could not parse date range '{}'. Supported forms: {}",
+                input, RANGE_GRAMMAR
+            ))
+        })?;
+        DateRange::day(date)
+    };
+
+    if let (Some(start), Some(end)) = (parsed.start, parsed.end)
+        && start > end
+    {
+        return Err(CoreError::validation(
+            "date range",
+            format!("'{}' ends ({}) before it starts ({})", input, end, start),
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Split "a to b", "from a to b", "between a and b" or "a..b" into its ends
+fn split_range(input: &str) -> Option<(&str, &str)> {
+    if let Some((from, to)) = input.split_once("..") {
+        return Some((from.trim(), to.trim()));
+    }
+    if let Some(rest) = input.strip_prefix("between ") {
+        return rest.split_once(" and ");
+    }
+    input.strip_prefix("from ").unwrap_or(input).split_once(" to ")
+}
+
+/// Parse "this/next/last week, month or year" and "next/last N days, weeks or months"
+fn try_parse_period_range(input: &str, today: NaiveDate) -> Option<DateRange> {
+    let (which, rest) = input.split_once(' ')?;
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let shift = match which {
+        "this" => 0,
+        "next" => 1,
+        "last" | "past" | "previous" => -1,
+        _ => return None,
+    };
+
+    let (start, end) = match rest {
+        "week" => {
+            let start = monday + Duration::days(7 * shift);
+            (start, start + Duration::days(6))
+        }
+        "month" => {
+            let start = add_offset(first_of_month(today)?, shift, 'm')?;
+            (start, last_of_month(start.year(), start.month())?)
+        }
+        "year" => {
+            let year = today.year() + shift as i32;
+            (NaiveDate::from_ymd_opt(year, 1, 1)?, NaiveDate::from_ymd_opt(year, 12, 31)?)
+        }
+        _ => {
+            // Rolling periods include today: "next 7 days" ends six days ahead
+            let (num, unit) = rest.split_once(' ')?;
+            let num: i64 = num.parse().ok().filter(|&n| n > 0)?;
+            let unit = match unit.trim_end_matches('s') {
+                "day" => 'd',
+                "week" => 'w',
+                "month" => 'm',
+                _ => return None,
+            };
+            match shift {
+                1 => (today, add_offset(today, num, unit)?.pred_opt()?),
+                -1 => (add_offset(today, -num, unit)?.succ_opt()?, today),
+                _ => return None,
+            }
+        }
+    };
+    Some(DateRange::new(Some(start), Some(end)))
+}
+
+/// The wall-clock time of a task date in the installed zone
+pub fn wall_time(dt: &DateTime<Local>) -> NaiveDateTime {
+    Zone::current().to_naive(dt)
+}
+
+/// Format a date, with its time of day if any, for human-readable display
 ///
-/// Returns strings like: "Today", "Tomorrow", "Mon Jan 27", "Overdue (3 days ago)"
-pub fn format_date_human(date: NaiveDate, relative_to_today: bool) -> String {
+/// Accepts a NaiveDate or a NaiveDateTime, as a wall-clock time in the
+/// installed [`Zone`]. Returns strings like: "Today", "Tomorrow 15:00",
+/// "Mon Jan 27", "Overdue (3 days ago)", in the installed [`Locale`]'s
+/// language ("Morgen 15:00", "Mo, 27. Jan")
+pub fn format_date_human(date: impl Into<NaiveDateTime>, relative_to_today: bool) -> String {
+    let datetime = date.into();
+    let time = (datetime.time() != NaiveTime::MIN).then(|| datetime.time());
+    format_human(datetime.date(), time, relative_to_today)
+}
+
+/// Format a task date for human-readable display like [`format_date_human`],
+/// with times of day shown in the installed [`Zone`]
+pub fn format_datetime_human(dt: &DateTime<Local>, relative_to_today: bool) -> String {
+    let datetime = wall_time(dt);
+    format_human(datetime.date(), has_time_of_day(dt).then(|| datetime.time()), relative_to_today)
+}
+
+fn format_human(date: NaiveDate, time: Option<NaiveTime>, relative_to_today: bool) -> String {
+    let time = time.map(|time| time.format(" %H:%M").to_string()).unwrap_or_default();
+
     if !relative_to_today {
-        return date.format("%Y-%m-%d").to_string();
+        return format!("{}{}", date.format("%Y-%m-%d"), time);
     }
 
-    let today = Local::now().date_naive();
+    let today = Zone::current().today();
     let diff = date.signed_duration_since(today).num_days();
+    let locale = Locale::current();
 
-    match diff {
-        0 => "Today".to_string(),
-        1 => "Tomorrow".to_string(),
-        -1 => "Yesterday".to_string(),
-        2..=6 => date.format("%a %b %d").to_string(),   // "Mon Jan 27"
-        7..=365 => date.format("%b %d").to_string(),    // "Jan 27"
-        _ if diff < 0 => format!("Overdue ({} days ago)", -diff),
+    let day = match diff {
+        0 => locale.today.to_string(),
+        1 => locale.tomorrow.to_string(),
+        -1 => locale.yesterday.to_string(),
+        2..=6 => locale.format(date, locale.week_format),  // "Mon Jan 27"
+        7..=365 => locale.format(date, locale.date_format), // "Jan 27"
+        _ if diff < 0 => {
+            return format!("{} ({})", locale.overdue, locale.days_ago.replace("{}", &(-diff).to_string()));
+        }
         _ => date.format("%Y-%m-%d").to_string(),
-    }
+    };
+
+    format!("{}{}", day, time)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
 
     #[test]
     fn test_parse_today_tomorrow() {
@@ -228,6 +847,273 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_date_expressions() {
+        // Wednesday
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let cases = [
+            ("today", "2026-03-11"),
+            ("tomorrow", "2026-03-12"),
+            ("yesterday", "2026-03-10"),
+            ("friday", "2026-03-13"),
+            ("this fri", "2026-03-13"),
+            ("next wednesday", "2026-03-18"),
+            ("next week", "2026-03-16"),
+            ("next month", "2026-04-01"),
+            ("next year", "2027-01-01"),
+            ("end of week", "2026-03-15"),
+            ("eow", "2026-03-15"),
+            ("end of work week", "2026-03-13"),
+            ("end of month", "2026-03-31"),
+            ("eom", "2026-03-31"),
+            ("eoy", "2026-12-31"),
+            ("in 3 days", "2026-03-14"),
+            ("in a week", "2026-03-18"),
+            ("in 3 months", "2026-06-11"),
+            ("in 1 year", "2027-03-11"),
+            ("+2w", "2026-03-25"),
+            ("-1d", "2026-03-10"),
+            ("+1m", "2026-04-11"),
+            ("-1y", "2025-03-11"),
+            ("first monday of april", "2026-04-06"),
+            ("first monday of march", "2027-03-01"),
+            ("last friday of the month", "2026-03-27"),
+            ("2nd tuesday of next month", "2026-04-14"),
+            ("last day of", "error"),
+            ("last monday", "2026-03-09"),
+            ("last wednesday", "2026-03-04"),
+            ("the 15th", "2026-03-15"),
+            ("the 5th", "2026-04-05"),
+            ("31st", "2026-03-31"),
+            ("the 1st of june", "2026-06-01"),
+            ("Jan 25", "2027-01-25"),
+            ("2026-01-25", "2026-01-25"),
+        ];
+        for (input, expected) in cases {
+            let parsed = parse_date_on(input, today, &WorkCalendar::default(), &Locale::EN).map(|d| d.to_string());
+            match expected {
+                "error" => assert!(parsed.is_err(), "{} should not parse", input),
+                _ => assert_eq!(parsed.ok().as_deref(), Some(expected), "{}", input),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_date_month_end_and_errors() {
+        let jan_31 = NaiveDate::from_ymd_opt(2026, 1, 31).unwrap();
+        let calendar = WorkCalendar::default();
+        assert_eq!(parse_date_on("in 1 month", jan_31, &calendar, &Locale::EN).unwrap().to_string(), "2026-02-28");
+        assert_eq!(parse_date_on("the 30th", jan_31, &calendar, &Locale::EN).unwrap().to_string(), "2026-03-30");
+        assert!(parse_date_on("fifth monday of february", jan_31, &calendar, &Locale::EN).is_err());
+
+        let err = parse_date_on("someday", jan_31, &calendar, &Locale::EN).unwrap_err().to_string();
+        assert!(err.contains("'someday'"));
+        assert!(err.contains("end of week"));
+        assert!(err.contains("+2w"));
+    }
+
+    #[test]
+    fn test_parse_date_workdays() {
+        // Thursday 2026-04-02, with Good Friday and Easter Monday off
+        let today = NaiveDate::from_ymd_opt(2026, 4, 2).unwrap();
+        let mut calendar = WorkCalendar::default();
+        calendar.add_holidays("2026-04-03\n2026-04-06").unwrap();
+
+        let cases = [
+            ("next workday", "2026-04-07"),
+            ("next business day", "2026-04-07"),
+            ("in 1 workday", "2026-04-07"),
+            ("in 3 business days", "2026-04-09"),
+            ("in a working day", "2026-04-07"),
+            ("eoww", "2026-04-02"),
+            ("next holiday", "error"),
+        ];
+        for (input, expected) in cases {
+            let parsed = parse_date_on(input, today, &calendar, &Locale::EN).map(|d| d.to_string());
+            match expected {
+                "error" => assert!(parsed.is_err(), "{} should not parse", input),
+                _ => assert_eq!(parsed.ok().as_deref(), Some(expected), "{}", input),
+            }
+        }
+
+        // Sunday to Thursday week: the work week ends on Thursday
+        let calendar = WorkCalendar::default().with_working_week(crate::calendar::parse_working_week("sun-thu").unwrap());
+        assert_eq!(parse_date_on("eoww", today, &calendar, &Locale::EN).unwrap().to_string(), "2026-04-02");
+        let saturday = NaiveDate::from_ymd_opt(2026, 4, 4).unwrap();
+        assert_eq!(parse_date_on("eoww", saturday, &calendar, &Locale::EN).unwrap().to_string(), "2026-04-09");
+        assert_eq!(parse_date_on("next workday", today, &calendar, &Locale::EN).unwrap().to_string(), "2026-04-05");
+    }
+
+    #[test]
+    fn test_parse_date_range() {
+        // Wednesday 2026-03-11
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let cases = [
+            ("this week", "2026-03-09", "2026-03-15"),
+            ("next week", "2026-03-16", "2026-03-22"),
+            ("last month", "2026-02-01", "2026-02-28"),
+            ("this year", "2026-01-01", "2026-12-31"),
+            ("next 7 days", "2026-03-11", "2026-03-17"),
+            ("last 2 weeks", "2026-02-26", "2026-03-11"),
+            ("before 2026-04-01", "..", "2026-03-31"),
+            ("after last monday", "2026-03-10", ".."),
+            ("since this week", "2026-03-09", ".."),
+            ("until eom", "..", "2026-03-31"),
+            ("today to friday", "2026-03-11", "2026-03-13"),
+            ("between today and next friday", "2026-03-11", "2026-03-13"),
+            ("2026-03-01..2026-03-05", "2026-03-01", "2026-03-05"),
+            ("..tomorrow", "..", "2026-03-12"),
+            ("today", "2026-03-11", "2026-03-11"),
+        ];
+        for (input, start, end) in cases {
+            let range = parse_date_range_on(input, today, &WorkCalendar::default(), &Locale::EN).unwrap();
+            let show = |date: Option<NaiveDate>| date.map_or("..".to_string(), |d| d.to_string());
+            assert_eq!((show(range.start), show(range.end)), (start.to_string(), end.to_string()), "{}", input);
+        }
+
+        assert!(parse_date_range_on("tomorrow to yesterday", today, &WorkCalendar::default(), &Locale::EN).is_err());
+        let err = parse_date_range_on("sometime", today, &WorkCalendar::default(), &Locale::EN).unwrap_err();
+        assert!(err.to_string().contains("next/last N days"));
+
+        let range = DateRange::before(today);
+        assert!(range.contains(today - Duration::days(30)));
+        assert!(!range.contains(today));
+    }
+
+    #[test]
+    fn test_parse_date_localized() {
+        // Wednesday 2026-03-11
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let calendar = WorkCalendar::default();
+        let cases = [
+            (Locale::DE, "morgen", "2026-03-12"),
+            (Locale::DE, "Nächsten Freitag", "2026-03-13"),
+            (Locale::DE, "übermorgen", "2026-03-13"),
+            (Locale::DE, "in 2 Wochen", "2026-03-25"),
+            (Locale::DE, "Ende des Monats", "2026-03-31"),
+            (Locale::DE, "erster Montag im April", "2026-04-06"),
+            (Locale::DE, "am 3. Mai", "2026-05-03"),
+            (Locale::DE, "01.04.", "2026-04-01"),
+            (Locale::DE, "next friday", "2026-03-13"),
+            (Locale::ES, "mañana", "2026-03-12"),
+            (Locale::ES, "el próximo lunes", "2026-03-16"),
+            (Locale::ES, "en 3 días hábiles", "2026-03-16"),
+            (Locale::ES, "último viernes del mes", "2026-03-27"),
+            (Locale::ES, "25 de diciembre", "2026-12-25"),
+            (Locale::ES, "01/04/2027", "2027-04-01"),
+            (Locale::EN, "01/04/2027", "2027-01-04"),
+        ];
+        for (locale, input, expected) in cases {
+            let parsed = parse_date_on(input, today, &calendar, &locale).map(|d| d.to_string());
+            assert_eq!(parsed.ok().as_deref(), Some(expected), "{} ({})", input, locale.code);
+        }
+        assert!(parse_date_on("morgen", today, &calendar, &Locale::EN).is_err());
+
+        let range = parse_date_range_on("von heute bis Freitag", today, &calendar, &Locale::DE).unwrap();
+        assert_eq!(range, DateRange::new(Some(today), NaiveDate::from_ymd_opt(2026, 3, 13)));
+        let range = parse_date_range_on("esta semana", today, &calendar, &Locale::ES).unwrap();
+        assert_eq!(range.start, NaiveDate::from_ymd_opt(2026, 3, 9));
+        let range = parse_date_range_on("bis Ende des Monats", today, &calendar, &Locale::DE).unwrap();
+        assert_eq!(range, DateRange::new(None, NaiveDate::from_ymd_opt(2026, 3, 31)));
+    }
+
+    #[test]
+    fn test_parse_datetime_with_time() {
+        let tomorrow = Local::now().date_naive() + Duration::days(1);
+
+        let dt = parse_datetime("tomorrow 3pm").unwrap();
+        assert_eq!(dt.date_naive(), tomorrow);
+        assert_eq!(dt.time(), NaiveTime::from_hms_opt(15, 0, 0).unwrap());
+
+        let dt = parse_datetime("fri 09:30").unwrap();
+        assert_eq!(dt.weekday(), Weekday::Fri);
+        assert_eq!(dt.time(), NaiveTime::from_hms_opt(9, 30, 0).unwrap());
+
+        let dt = parse_datetime("2026-01-25 at 12:15 am").unwrap();
+        assert_eq!(dt.naive_local(), NaiveDate::from_ymd_opt(2026, 1, 25).unwrap().and_hms_opt(0, 15, 0).unwrap());
+
+        let dt = parse_datetime("tomorrow noon").unwrap();
+        assert_eq!(dt.time(), NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_parse_datetime_noon_and_midnight() {
+        let today = Local::now().date_naive();
+        let cases = [
+            ("noon", Some((None, 12))),
+            ("at noon", Some((None, 12))),
+            ("sat noon", Some((Some(Weekday::Sat), 12))),
+            ("sat at noon", Some((Some(Weekday::Sat), 12))),
+            ("sat midnight", Some((Some(Weekday::Sat), 0))),
+            ("friday at midnight", Some((Some(Weekday::Fri), 0))),
+            ("afternoon", None),
+            ("satnoon", None),
+        ];
+        for (input, expected) in cases {
+            let parsed = parse_datetime(input);
+            match expected {
+                None => assert!(parsed.is_err(), "{} should not parse", input),
+                Some((weekday, hour)) => {
+                    let dt = parsed.unwrap_or_else(|e| panic!("{}: {}", input, e));
+                    assert_eq!(dt.time(), NaiveTime::from_hms_opt(hour, 0, 0).unwrap(), "{}", input);
+                    match weekday {
+                        Some(weekday) => assert_eq!(dt.weekday(), weekday, "{}", input),
+                        None => assert_eq!(dt.date_naive(), today, "{}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_datetime_without_time() {
+        let dt = parse_datetime("Jan 25 2026").unwrap();
+        assert_eq!(dt.date_naive(), NaiveDate::from_ymd_opt(2026, 1, 25).unwrap());
+        assert!(!has_time_of_day(&dt));
+
+        let dt = parse_datetime("in 3 days").unwrap();
+        assert_eq!(dt.date_naive(), Local::now().date_naive() + Duration::days(3));
+        assert!(!has_time_of_day(&dt));
+    }
+
+    #[test]
+    fn test_parse_datetime_offset() {
+        let before = Local::now();
+        let dt = parse_datetime("in 2 hours").unwrap();
+        let diff = dt.signed_duration_since(before);
+        assert!(diff > Duration::minutes(118) && diff <= Duration::hours(2));
+        assert_eq!(dt.second(), 0);
+    }
+
+    #[test]
+    fn test_parse_datetime_invalid_time() {
+        assert!(parse_datetime("tomorrow 25:00").is_err());
+        assert!(parse_datetime("tomorrow 13pm").is_err());
+    }
+
+    #[test]
+    fn test_format_date_human_with_time() {
+        let today = Local::now().date_naive();
+        let at_three = today.and_hms_opt(15, 0, 0).unwrap();
+        assert_eq!(format_date_human(at_three, true), "Today 15:00");
+        assert_eq!(format_date_human(today.and_hms_opt(0, 0, 0).unwrap(), true), "Today");
+
+        let date = NaiveDate::from_ymd_opt(2026, 1, 25).unwrap();
+        assert_eq!(format_date_human(date.and_hms_opt(9, 30, 0).unwrap(), false), "2026-01-25 09:30");
+        assert_eq!(format_date_human(date, false), "2026-01-25");
+    }
+
+    #[test]
+    fn test_timestamp_roundtrip() {
+        let dt = Local.with_ymd_and_hms(2026, 1, 25, 14, 3, 59).unwrap();
+        assert_eq!(format_timestamp(&dt), "20260125T1403");
+        assert_eq!(
+            parse_timestamp("20260125T1403"),
+            Some(Local.with_ymd_and_hms(2026, 1, 25, 14, 3, 0).unwrap())
+        );
+        assert!(parse_timestamp("2026-01-25").is_none());
+    }
+
     #[test]
     fn test_format_date_human() {
         let today = Local::now().date_naive();
@@ -0,0 +1,213 @@
+//! Custom task fields
+//!
+//! User-defined metadata such as `customer:Acme` or `ticket:OPS-42`. Keys are
+//! lowercase words (letters, digits, `-` and `_`, starting with a letter);
+//! values are free text without double quotes or line breaks. In a task line
+//! fields follow the title as `key:value` words, quoted when the value
+//! contains spaces: `sprint:"Sprint 12"`. Title words that would read as
+//! fields are escaped with a backslash before the colon: `Trek\:TNG`.
+
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use crate::error::{CoreError, Result};
+
+// Field at the end of a title: key:value or key:"several words". Values may
+// not start with '/' so URLs such as https://example.com stay in the title.
+static FIELD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^|\s)([A-Za-z][A-Za-z0-9_-]*):("[^"]+"|[^\s"/][^\s"]*)$"#)
+        .expect("Invalid field regex pattern")
+});
+
+// Title word that reads as a field, or one escaped with backslashes before
+// the colon: Trek:TNG, Trek\:TNG
+static ESCAPED_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:^|\s)[A-Za-z][A-Za-z0-9_-]*(\\*):(?:"[^"]+"|[^\s"/][^\s"]*)$"#)
+        .expect("Invalid escaped field regex pattern")
+});
+
+// Tag or priority marker that may sit between fields: #work, !2
+static MARKER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|\s)(?:#\w+|![1-4])$").expect("Invalid marker regex pattern")
+});
+
+/// Validate a field key, returning it in lowercase
+pub fn normalize_key(key: &str) -> Result<String> {
+    let key = key.trim().to_lowercase();
+    let valid = key.starts_with(|c: char| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(CoreError::validation(
+            "field",
+            format!("Invalid field name '{}' (use letters, digits, '-' and '_', starting with a letter)", key),
+        ));
+    }
+    Ok(key)
+}
+
+/// Validate a field value, returning it trimmed
+pub fn normalize_value(value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(CoreError::validation("field", "Field value cannot be empty"));
+    }
+    if value.contains(['"', '\n', '\r']) {
+        return Err(CoreError::validation(
+            "field",
+            format!("Field value '{}' cannot contain double quotes or line breaks", value),
+        ));
+    }
+    Ok(value.to_string())
+}
+
+/// Format a field as it is written after a task title
+pub fn format(key: &str, value: &str) -> String {
+    if value.contains(char::is_whitespace) || value.starts_with('/') {
+        format!("{}:\"{}\"", key, value)
+    } else {
+        format!("{}:{}", key, value)
+    }
+}
+
+/// Split the fields off the end of a title
+///
+/// Fields are read from the end of the text, skipping tags and priority
+/// markers mixed in with them, and stop at the first other word; the text
+/// before that (plus the skipped markers) is returned with the fields.
+pub fn extract(text: &str) -> (String, BTreeMap<String, String>) {
+    let mut fields = BTreeMap::new();
+    let mut markers = Vec::new();
+    let mut rest = text.trim_end();
+    loop {
+        if let Some(caps) = FIELD_REGEX.captures(rest) {
+            let value = caps[2].trim_matches('"');
+            // Read backwards, so an earlier duplicate does not replace a later one
+            fields.entry(caps[1].to_lowercase()).or_insert_with(|| value.to_string());
+            rest = rest[..caps.get(0).map_or(0, |m| m.start())].trim_end();
+        } else if let Some(marker) = MARKER_REGEX.find(rest) {
+            markers.push(marker.as_str().trim());
+            rest = rest[..marker.start()].trim_end();
+        } else {
+            break;
+        }
+    }
+    if fields.is_empty() {
+        return (unescape(text), fields);
+    }
+
+    let mut remaining = unescape(rest);
+    for marker in markers.iter().rev() {
+        remaining.push(' ');
+        remaining.push_str(marker);
+    }
+    (remaining, fields)
+}
+
+/// Escape the words at the end of a title that would read as fields
+///
+/// Each gets one more backslash before the colon, so a title such as
+/// `Watch Star Trek:TNG` is written as `Watch Star Trek\:TNG` and
+/// [`extract`] reads it back unchanged.
+pub fn escape(title: &str) -> String {
+    let mut escaped = title.to_string();
+    // Positions come last to first, so inserting keeps the others valid
+    for position in trailing_escapes(title) {
+        escaped.insert(position, '\\');
+    }
+    escaped
+}
+
+/// Undo [`escape`], removing one backslash before each colon
+fn unescape(text: &str) -> String {
+    let mut unescaped = text.to_string();
+    for position in trailing_escapes(text) {
+        if unescaped[position..].starts_with('\\') {
+            unescaped.remove(position);
+        }
+    }
+    unescaped
+}
+
+/// Where the backslashes before the colon start in each field-like word at
+/// the end of the text, last word first
+///
+/// Words are found the way [`extract`] reads fields, skipping tags and
+/// priority markers.
+fn trailing_escapes(text: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut rest = text.trim_end();
+    loop {
+        if let Some(caps) = ESCAPED_REGEX.captures(rest) {
+            positions.push(caps.get(1).map_or(0, |m| m.start()));
+            rest = rest[..caps.get(0).map_or(0, |m| m.start())].trim_end();
+        } else if let Some(marker) = MARKER_REGEX.find(rest) {
+            rest = rest[..marker.start()].trim_end();
+        } else {
+            break;
+        }
+    }
+    positions
+}
+
+/// Compare two field values, numerically when both are numbers
+pub fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_fields() {
+        let (title, fields) = extract("Fix login #bug customer:Acme !1 sprint:\"Sprint 12\"");
+        assert_eq!(title, "Fix login #bug !1");
+        assert_eq!(fields["customer"], "Acme");
+        assert_eq!(fields["sprint"], "Sprint 12");
+
+        // Fields only count at the end, and URLs or times are not fields
+        for text in ["Read re:Invent notes", "See https://example.com", "Call at 10:30"] {
+            let (title, fields) = extract(text);
+            assert_eq!(title, text);
+            assert!(fields.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_format_and_validate_fields() {
+        assert_eq!(format("ticket", "OPS-42"), "ticket:OPS-42");
+        assert_eq!(format("sprint", "Sprint 12"), "sprint:\"Sprint 12\"");
+        assert_eq!(extract(&format("path", "/srv/data")).1["path"], "/srv/data");
+
+        assert_eq!(normalize_key("Customer").unwrap(), "customer");
+        assert!(normalize_key("2fa").is_err());
+        assert!(normalize_key("due date").is_err());
+        assert!(normalize_value("  ").is_err());
+        assert!(normalize_value("say \"hi\"").is_err());
+    }
+
+    #[test]
+    fn test_escape_roundtrip() {
+        assert_eq!(escape("Watch Star Trek:TNG"), "Watch Star Trek\\:TNG");
+        assert_eq!(escape("Read re:Invent notes"), "Read re:Invent notes");
+
+        for title in ["Watch Star Trek:TNG", "Ticket JIRA:123 ops:\"on call\"", "Keep a\\:b", "Call at 10:30"] {
+            let line = format!("{} #tv {}", escape(title), format("customer", "Acme"));
+            let (text, fields) = extract(&line);
+            assert_eq!(text, format!("{} #tv", title));
+            assert_eq!(fields.len(), 1);
+            assert_eq!(extract(&escape(title)), (title.to_string(), BTreeMap::new()));
+        }
+    }
+
+    #[test]
+    fn test_compare_values() {
+        assert_eq!(compare_values("9", "10"), Ordering::Less);
+        assert_eq!(compare_values("acme", "Beta"), Ordering::Less);
+    }
+}
@@ -2,51 +2,117 @@
 //!
 //! Provides a builder-style API for filtering and sorting tasks.
 
-use chrono::Local;
+use chrono::{DateTime, Local};
+use std::cmp::Ordering;
 
-use crate::task::Task;
+use crate::category;
+use crate::date::DateRange;
+use crate::field;
+use crate::query::Query;
+use crate::task::{DeadlineState, Priority, Status, Task, TaskCollection};
+use crate::zone::Zone;
 
 /// Sort order for tasks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum TaskSort {
     #[default]
     Id,
     Date,
     Name,
     Status,
+    Priority,
+    Created,
+    Updated,
+    Completed,
+    Deadline,
+    /// Custom field by name, numbers in numeric order
+    Field(String),
 }
 
 impl TaskSort {
-    /// Create from string (case-insensitive)
+    /// Create from string (case-insensitive); "field:NAME" sorts by a custom field
     pub fn from_str(s: &str) -> Self {
+        if let Some(key) = s.strip_prefix("field:") {
+            return Self::Field(key.to_lowercase());
+        }
         match s.to_lowercase().as_str() {
             "date" => Self::Date,
             "name" => Self::Name,
             "status" => Self::Status,
+            "priority" => Self::Priority,
+            "created" => Self::Created,
+            "updated" => Self::Updated,
+            "completed" => Self::Completed,
+            "deadline" => Self::Deadline,
             _ => Self::Id,
         }
     }
 }
 
+/// How to treat tasks whose start date is still ahead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Deferred {
+    /// Hide deferred tasks
+    #[default]
+    Hide,
+    /// Show deferred tasks along with the others
+    Include,
+    /// Show deferred tasks only
+    Only,
+}
+
 /// Builder for filtering tasks
 #[derive(Debug, Clone, Default)]
 pub struct TaskFilter {
-    /// Filter by completion status (Some(true) = completed, Some(false) = incomplete)
+    /// Filter by completion status (Some(true) = done, Some(false) = still to do;
+    /// cancelled tasks match neither)
     pub checked: Option<bool>,
+    /// Filter by lifecycle status (any may match)
+    pub statuses: Vec<Status>,
     /// Filter by scheduled status (Some(true) = has date, Some(false) = no date)
     pub scheduled: Option<bool>,
     /// Filter to only show overdue tasks
     pub overdue: bool,
+    /// Filter to tasks scheduled within these date ranges (all must match,
+    /// unscheduled tasks never match)
+    pub scheduled_within: Vec<DateRange>,
     /// Search term for title (case-insensitive)
     pub search: Option<String>,
     /// Sort order
     pub sort: TaskSort,
     /// Reverse sort order
     pub reverse: bool,
-    /// Filter by category (Some(Some("Work")) = in "Work", Some(None) = uncategorized)
+    /// Filter by category (Some(Some("Work")) = in "Work" or below, Some(None) = uncategorized)
     pub category: Option<Option<String>>,
     /// Filter by tags (all must match)
     pub tags: Vec<String>,
+    /// Filter by exact priority (Some(None) = no priority)
+    pub priority: Option<Option<Priority>>,
+    /// Filter to tasks at least as important as this priority
+    pub min_priority: Option<Priority>,
+    /// Filter by blocked status (Some(true) = waiting on an open blocker,
+    /// Some(false) = actionable). Only honoured by [`FilterExt::get_filtered`],
+    /// since it needs the other tasks in the collection.
+    pub blocked: Option<bool>,
+    /// Filter to tasks completed at or after this time
+    pub completed_since: Option<DateTime<Local>>,
+    /// Filter to tasks created before this time (tasks without a creation time never match)
+    pub created_before: Option<DateTime<Local>>,
+    /// Whether tasks that have not started yet are shown (hidden by default)
+    pub deferred: Deferred,
+    /// Filter by deadline (Some(true) = has one, Some(false) = has none)
+    pub deadline: Option<bool>,
+    /// Filter to open tasks that missed their deadline
+    pub past_deadline: bool,
+    /// Filter to open tasks within their deadline warning lead time or past
+    /// the deadline; the value is the lead time in days for tasks without
+    /// their own
+    pub deadline_soon: Option<u32>,
+    /// Filter by custom fields (all must match); a value of None only
+    /// requires the field to be set, values compare case-insensitively
+    pub fields: Vec<(String, Option<String>)>,
+    /// Filter by a query, on top of the other options
+    pub query: Option<Query>,
 }
 
 impl TaskFilter {
@@ -73,6 +139,18 @@ impl TaskFilter {
         self
     }
 
+    /// Filter by status (may be called several times, any must match)
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Set status filter (any must match)
+    pub fn with_statuses(mut self, statuses: Vec<Status>) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
     /// Filter to only show scheduled tasks
     pub fn with_schedule(mut self) -> Self {
         self.scheduled = Some(true);
@@ -91,6 +169,19 @@ impl TaskFilter {
         self
     }
 
+    /// Filter to tasks scheduled within a date range (may be called several
+    /// times, all must match)
+    pub fn scheduled_within(mut self, range: DateRange) -> Self {
+        self.scheduled_within.push(range);
+        self
+    }
+
+    /// Set scheduled date range filters (all must match)
+    pub fn with_scheduled_within(mut self, ranges: Vec<DateRange>) -> Self {
+        self.scheduled_within = ranges;
+        self
+    }
+
     /// Filter to only show overdue tasks
     pub fn overdue_only(mut self) -> Self {
         self.overdue = true;
@@ -115,6 +206,109 @@ impl TaskFilter {
         self
     }
 
+    /// Filter to tasks completed at or after a time
+    pub fn completed_since(mut self, since: DateTime<Local>) -> Self {
+        self.completed_since = Some(since);
+        self
+    }
+
+    /// Set completed-since filter
+    pub fn with_completed_since(mut self, since: Option<DateTime<Local>>) -> Self {
+        self.completed_since = since;
+        self
+    }
+
+    /// Filter to tasks created before a time
+    pub fn created_before(mut self, before: DateTime<Local>) -> Self {
+        self.created_before = Some(before);
+        self
+    }
+
+    /// Set created-before filter
+    pub fn with_created_before(mut self, before: Option<DateTime<Local>>) -> Self {
+        self.created_before = before;
+        self
+    }
+
+    /// Show deferred tasks along with the others
+    pub fn including_deferred(mut self) -> Self {
+        self.deferred = Deferred::Include;
+        self
+    }
+
+    /// Filter to deferred tasks only
+    pub fn deferred_only(mut self) -> Self {
+        self.deferred = Deferred::Only;
+        self
+    }
+
+    /// Set deferred filter
+    pub fn with_deferred(mut self, deferred: Deferred) -> Self {
+        self.deferred = deferred;
+        self
+    }
+
+    /// Set deadline filter
+    pub fn with_deadline(mut self, deadline: Option<bool>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Filter to tasks that missed their deadline
+    pub fn past_deadline_only(mut self) -> Self {
+        self.past_deadline = true;
+        self
+    }
+
+    /// Set past-deadline filter
+    pub fn with_past_deadline(mut self, past_deadline: bool) -> Self {
+        self.past_deadline = past_deadline;
+        self
+    }
+
+    /// Filter to tasks whose deadline is near or past, warning
+    /// `default_warning_days` ahead unless a task has its own lead time
+    pub fn deadline_soon(mut self, default_warning_days: u32) -> Self {
+        self.deadline_soon = Some(default_warning_days);
+        self
+    }
+
+    /// Set deadline-soon filter
+    pub fn with_deadline_soon(mut self, default_warning_days: Option<u32>) -> Self {
+        self.deadline_soon = default_warning_days;
+        self
+    }
+
+    /// Filter by a custom field value
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.fields.push((key.into(), Some(value.into())));
+        self
+    }
+
+    /// Filter to tasks that have a custom field
+    pub fn has_field(mut self, key: impl Into<String>) -> Self {
+        self.fields.push((key.into(), None));
+        self
+    }
+
+    /// Set custom field filters (all must match)
+    pub fn with_fields(mut self, fields: Vec<(String, Option<String>)>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Filter by a query
+    pub fn matching(mut self, query: Query) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// Set query filter
+    pub fn with_query(mut self, query: Option<Query>) -> Self {
+        self.query = query;
+        self
+    }
+
     /// Sort by given field
     pub fn sort_by(mut self, sort: TaskSort) -> Self {
         self.sort = sort;
@@ -133,7 +327,7 @@ impl TaskFilter {
         self
     }
 
-    /// Filter by exact category
+    /// Filter by category, including its subcategories
     pub fn in_category(mut self, category: impl Into<String>) -> Self {
         self.category = Some(Some(category.into()));
         self
@@ -163,17 +357,88 @@ impl TaskFilter {
         self
     }
 
+    /// Filter by exact priority
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(Some(priority));
+        self
+    }
+
+    /// Filter to tasks without a priority
+    pub fn without_priority(mut self) -> Self {
+        self.priority = Some(None);
+        self
+    }
+
+    /// Filter to tasks at least as important as the given priority
+    pub fn at_least_priority(mut self, priority: Priority) -> Self {
+        self.min_priority = Some(priority);
+        self
+    }
+
+    /// Set minimum priority filter
+    pub fn with_min_priority(mut self, priority: Option<Priority>) -> Self {
+        self.min_priority = priority;
+        self
+    }
+
+    /// Filter to actionable tasks (all blockers complete)
+    pub fn actionable_only(mut self) -> Self {
+        self.blocked = Some(false);
+        self
+    }
+
+    /// Filter to tasks waiting on an incomplete blocker
+    pub fn blocked_only(mut self) -> Self {
+        self.blocked = Some(true);
+        self
+    }
+
+    /// Set blocked filter
+    pub fn with_blocked(mut self, blocked: Option<bool>) -> Self {
+        self.blocked = blocked;
+        self
+    }
+
+    /// Check if a task matches this filter, including options that depend
+    /// on other tasks in the collection
+    pub fn matches_in(&self, task: &Task, collection: &TaskCollection) -> bool {
+        if let Some(blocked) = self.blocked
+            && collection.is_blocked(task.id) != blocked
+        {
+            return false;
+        }
+
+        self.matches_options(task)
+            && self
+                .query
+                .as_ref()
+                .is_none_or(|query| query.matches_with(task, |id| collection.is_blocked(id)))
+    }
+
     /// Check if a task matches this filter
     pub fn matches(&self, task: &Task) -> bool {
-        let today = Local::now().date_naive();
+        self.matches_options(task) && self.query.as_ref().is_none_or(|query| query.matches(task))
+    }
 
+    /// Check the options that do not depend on other tasks, except the query
+    fn matches_options(&self, task: &Task) -> bool {
         // Filter by completion status
         if let Some(checked) = self.checked {
-            if task.is_complete != checked {
+            let matches = if checked {
+                task.is_complete()
+            } else {
+                !task.status.is_closed()
+            };
+            if !matches {
                 return false;
             }
         }
 
+        // Filter by lifecycle status
+        if !self.statuses.is_empty() && !self.statuses.contains(&task.status) {
+            return false;
+        }
+
         // Filter by scheduled status
         if let Some(has_schedule) = self.scheduled {
             if task.scheduled.is_some() != has_schedule {
@@ -181,27 +446,62 @@ impl TaskFilter {
             }
         }
 
-        // Filter overdue
-        if self.overdue {
-            if let Some(dt) = task.scheduled {
-                if dt.date_naive() >= today || task.is_complete {
-                    return false;
-                }
-            } else {
+        // Filter by scheduled date range
+        if !self.scheduled_within.is_empty() {
+            let Some(date) = task.scheduled.map(|dt| Zone::current().date_of(&dt)) else {
+                return false;
+            };
+            if !self.scheduled_within.iter().all(|range| range.contains(date)) {
                 return false;
             }
         }
 
-        // Filter by search term
+        // Filter by start date
+        let deferred = task.is_deferred();
+        match self.deferred {
+            Deferred::Hide if deferred => return false,
+            Deferred::Only if !deferred => return false,
+            _ => {}
+        }
+
+        // Filter overdue
+        if self.overdue && !task.is_overdue() {
+            return false;
+        }
+
+        // Filter by deadline
+        if let Some(has_deadline) = self.deadline
+            && task.deadline.is_some() != has_deadline
+        {
+            return false;
+        }
+        if self.past_deadline && !task.is_past_deadline() {
+            return false;
+        }
+        if let Some(days) = self.deadline_soon
+            && !matches!(
+                task.deadline_state_at(Local::now(), days),
+                Some(DeadlineState::Approaching | DeadlineState::Passed)
+            )
+        {
+            return false;
+        }
+
+        // Filter by search term in title and notes
         if let Some(term) = &self.search {
-            if !task.title.to_lowercase().contains(&term.to_lowercase()) {
+            if !task.matches_text(term) {
                 return false;
             }
         }
 
-        // Filter by category
+        // Filter by category, including its subcategories
         if let Some(ref cat_filter) = self.category {
-            if task.category.as_ref() != cat_filter.as_ref() {
+            let matches = match (&task.category, cat_filter) {
+                (Some(path), Some(ancestor)) => category::is_within(path, ancestor),
+                (None, None) => true,
+                _ => false,
+            };
+            if !matches {
                 return false;
             }
         }
@@ -213,15 +513,58 @@ impl TaskFilter {
             }
         }
 
+        // Filter by custom fields (all must match)
+        for (key, expected) in &self.fields {
+            let matches = match (task.field(key), expected) {
+                (Some(value), Some(expected)) => value.to_lowercase() == expected.to_lowercase(),
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if !matches {
+                return false;
+            }
+        }
+
+        // Filter by exact priority
+        if let Some(priority) = self.priority
+            && task.priority != priority
+        {
+            return false;
+        }
+
+        // Filter by completion time
+        if let Some(since) = self.completed_since
+            && (!task.is_complete() || task.completed.is_none_or(|dt| dt < since))
+        {
+            return false;
+        }
+
+        // Filter by creation time
+        if let Some(before) = self.created_before
+            && task.created.is_none_or(|dt| dt >= before)
+        {
+            return false;
+        }
+
+        // Filter by minimum priority (tasks without priority never match)
+        if let Some(min) = self.min_priority {
+            match task.priority {
+                Some(p) if p <= min => {}
+                _ => return false,
+            }
+        }
+
         true
     }
 
     /// Apply filter and sort to a collection of tasks
     pub fn apply<'a>(&self, tasks: impl Iterator<Item = &'a Task>) -> Vec<&'a Task> {
-        let mut filtered: Vec<&Task> = tasks.filter(|t| self.matches(t)).collect();
+        self.sort(tasks.filter(|t| self.matches(t)).collect())
+    }
 
-        // Sort tasks
-        match self.sort {
+    /// Sort tasks in this filter's order
+    fn sort<'a>(&self, mut filtered: Vec<&'a Task>) -> Vec<&'a Task> {
+        match &self.sort {
             TaskSort::Id => filtered.sort_by_key(|t| t.id),
             TaskSort::Date => filtered.sort_by(|a, b| match (&a.scheduled, &b.scheduled) {
                 (Some(a_dt), Some(b_dt)) => a_dt.cmp(b_dt),
@@ -232,7 +575,22 @@ impl TaskFilter {
             TaskSort::Name => {
                 filtered.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
             }
-            TaskSort::Status => filtered.sort_by_key(|t| (t.is_complete, t.id)),
+            TaskSort::Status => filtered.sort_by_key(|t| (t.status, t.id)),
+            TaskSort::Priority => filtered.sort_by(|a, b| compare_present_first(a, b, |t| t.priority)),
+            TaskSort::Created => filtered.sort_by(|a, b| compare_present_first(a, b, |t| t.created)),
+            TaskSort::Updated => filtered.sort_by(|a, b| compare_present_first(a, b, |t| t.updated)),
+            TaskSort::Completed => {
+                filtered.sort_by(|a, b| compare_present_first(a, b, |t| t.completed))
+            }
+            TaskSort::Deadline => filtered.sort_by(|a, b| compare_present_first(a, b, |t| t.deadline)),
+            TaskSort::Field(key) => filtered.sort_by(|a, b| match (a.field(key), b.field(key)) {
+                (Some(a_value), Some(b_value)) => {
+                    field::compare_values(a_value, b_value).then(a.id.cmp(&b.id))
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.id.cmp(&b.id),
+            }),
         }
 
         if self.reverse {
@@ -243,6 +601,16 @@ impl TaskFilter {
     }
 }
 
+/// Compare tasks by an optional key, tasks without one last, ties by ID
+fn compare_present_first<K: Ord>(a: &Task, b: &Task, key: impl Fn(&Task) -> Option<K>) -> Ordering {
+    match (key(a), key(b)) {
+        (Some(a_key), Some(b_key)) => a_key.cmp(&b_key).then(a.id.cmp(&b.id)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.id.cmp(&b.id),
+    }
+}
+
 /// Extension trait for TaskCollection to support filtering
 pub trait FilterExt {
     /// Get tasks filtered and sorted according to the filter
@@ -260,9 +628,9 @@ pub trait FilterExt {
     ) -> Vec<&Task>;
 }
 
-impl FilterExt for crate::task::TaskCollection {
+impl FilterExt for TaskCollection {
     fn get_filtered(&self, filter: &TaskFilter) -> Vec<&Task> {
-        filter.apply(self.tasks.values())
+        filter.sort(self.tasks.values().filter(|t| filter.matches_in(t, self)).collect())
     }
 
     fn get_filtered_sorted(
@@ -289,14 +657,13 @@ impl FilterExt for crate::task::TaskCollection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::task::TaskCollection;
 
     #[test]
     fn test_filter_completed() {
         let mut collection = TaskCollection::new();
         collection.add("Task 1", None);
         collection.add("Task 2", None);
-        collection.get_mut(1).unwrap().is_complete = true;
+        collection.get_mut(1).unwrap().status = Status::Done;
 
         let filter = TaskFilter::new().completed();
         let results = collection.get_filtered(&filter);
@@ -318,6 +685,35 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_filter_search_notes() {
+        let mut collection = TaskCollection::new();
+        collection.add_task(Task::new("Release").with_notes(Some("Update the changelog".to_string())));
+        collection.add("Changelog review", None);
+        collection.add("Call mom", None);
+
+        let filter = TaskFilter::new().search("changelog");
+        let results = collection.get_filtered(&filter);
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_category_includes_subcategories() {
+        let mut collection = TaskCollection::new();
+        collection.add_with_category_tags("Deploy", None, Some("Work/ClientA".to_string()), Vec::new());
+        collection.add_with_category_tags("Plan", None, Some("Work".to_string()), Vec::new());
+        collection.add_with_category_tags("Sand", None, Some("Workshop".to_string()), Vec::new());
+        collection.add("Loose", None);
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::new().in_category("Work")), vec![1, 2]);
+        assert_eq!(ids(TaskFilter::new().in_category("Work/ClientA")), vec![1]);
+        assert_eq!(ids(TaskFilter::new().uncategorized()), vec![4]);
+    }
+
     #[test]
     fn test_sort_by_name() {
         let mut collection = TaskCollection::new();
@@ -333,6 +729,61 @@ mod tests {
         assert_eq!(results[2].title, "Zebra");
     }
 
+    #[test]
+    fn test_filter_priority() {
+        let mut collection = TaskCollection::new();
+        collection.add_task(Task::new("Urgent").with_priority(Some(Priority::Urgent)));
+        collection.add_task(Task::new("Medium").with_priority(Some(Priority::Medium)));
+        collection.add_task(Task::new("Low").with_priority(Some(Priority::Low)));
+        collection.add("None", None);
+
+        let exact = collection.get_filtered(&TaskFilter::new().with_priority(Priority::Medium));
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].title, "Medium");
+
+        let at_least = collection.get_filtered(&TaskFilter::new().at_least_priority(Priority::Medium));
+        assert_eq!(at_least.len(), 2);
+
+        let none = collection.get_filtered(&TaskFilter::new().without_priority());
+        assert_eq!(none.len(), 1);
+        assert_eq!(none[0].title, "None");
+    }
+
+    #[test]
+    fn test_sort_by_priority() {
+        let mut collection = TaskCollection::new();
+        collection.add("No priority", None);
+        collection.add_task(Task::new("Low").with_priority(Some(Priority::Low)));
+        collection.add_task(Task::new("Urgent").with_priority(Some(Priority::Urgent)));
+
+        let filter = TaskFilter::new().sort_by(TaskSort::Priority);
+        let results = collection.get_filtered(&filter);
+
+        assert_eq!(results[0].title, "Urgent");
+        assert_eq!(results[1].title, "Low");
+        assert_eq!(results[2].title, "No priority");
+    }
+
+    #[test]
+    fn test_filter_actionable() {
+        let mut collection = TaskCollection::new();
+        let blocker = collection.add("Blocker", None);
+        let blocked = collection.add("Blocked", None);
+        collection.add_dependency(blocked, blocker).unwrap();
+
+        let actionable = collection.get_filtered(&TaskFilter::new().actionable_only());
+        assert_eq!(actionable.len(), 1);
+        assert_eq!(actionable[0].id, blocker);
+
+        let waiting = collection.get_filtered(&TaskFilter::new().blocked_only());
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].id, blocked);
+
+        collection.get_mut(blocker).unwrap().status = Status::Done;
+        let actionable = collection.get_filtered(&TaskFilter::new().actionable_only());
+        assert_eq!(actionable.len(), 2);
+    }
+
     #[test]
     fn test_reverse_sort() {
         let mut collection = TaskCollection::new();
@@ -347,4 +798,168 @@ mod tests {
         assert_eq!(results[1].id, 2);
         assert_eq!(results[2].id, 1);
     }
+
+    #[test]
+    fn test_filter_timestamps() {
+        use chrono::TimeZone;
+
+        let at = |day| Local.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap();
+        let mut collection = TaskCollection::new();
+        collection.insert(
+            Task::new("Old, done late")
+                .with_id(1)
+                .with_created(Some(at(1)))
+                .with_complete(true)
+                .with_completed(Some(at(20))),
+        );
+        collection.insert(
+            Task::new("Old, done early")
+                .with_id(2)
+                .with_created(Some(at(2)))
+                .with_complete(true)
+                .with_completed(Some(at(5))),
+        );
+        collection.insert(Task::new("New, open").with_id(3).with_created(Some(at(15))));
+        collection.insert(Task::new("Legacy").with_id(4));
+
+        let filter = TaskFilter::new().completed_since(at(10));
+        let ids: Vec<u32> = collection.get_filtered(&filter).iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1]);
+
+        let filter = TaskFilter::new().created_before(at(10));
+        let ids: Vec<u32> = collection.get_filtered(&filter).iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        let filter = TaskFilter::new().sort_by(TaskSort::Completed);
+        let ids: Vec<u32> = collection.get_filtered(&filter).iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![2, 1, 3, 4]);
+
+        let filter = TaskFilter::new().sort_by(TaskSort::Created).with_reverse(true);
+        let ids: Vec<u32> = collection.get_filtered(&filter).iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_filter_and_sort_by_status() {
+        let mut collection = TaskCollection::new();
+        for (id, status) in [
+            (1, Status::Done),
+            (2, Status::Open),
+            (3, Status::Cancelled),
+            (4, Status::InProgress),
+            (5, Status::Waiting),
+        ] {
+            collection.insert(Task::new(format!("Task {}", id)).with_id(id).with_status(status));
+        }
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::new().sort_by(TaskSort::Status)), vec![4, 2, 5, 1, 3]);
+        assert_eq!(ids(TaskFilter::new().incomplete()), vec![2, 4, 5]);
+        assert_eq!(ids(TaskFilter::new().completed()), vec![1]);
+        assert_eq!(
+            ids(TaskFilter::new().with_status(Status::Waiting).with_status(Status::Cancelled)),
+            vec![3, 5]
+        );
+    }
+
+    #[test]
+    fn test_filter_deferred() {
+        let mut collection = TaskCollection::new();
+        let today = Local::now().date_naive();
+        collection.insert(Task::new("Now").with_id(1));
+        collection.insert(Task::new("Started").with_id(2).with_start(Some(today)));
+        collection.insert(Task::new("Later").with_id(3).with_start(today.succ_opt()));
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::new()), vec![1, 2]);
+        assert_eq!(ids(TaskFilter::new().including_deferred()), vec![1, 2, 3]);
+        assert_eq!(ids(TaskFilter::new().deferred_only()), vec![3]);
+    }
+
+    #[test]
+    fn test_filter_and_sort_by_deadline() {
+        let mut collection = TaskCollection::new();
+        let in_days = |days| Some(Local::now() + chrono::Duration::days(days));
+        collection.insert(Task::new("No deadline").with_id(1));
+        collection.insert(Task::new("Next month").with_id(2).with_deadline(in_days(30)));
+        collection.insert(Task::new("Missed").with_id(3).with_deadline(in_days(-2)));
+        collection.insert(Task::new("Soon").with_id(4).with_deadline(in_days(2)));
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::new().with_deadline(Some(true))), vec![2, 3, 4]);
+        assert_eq!(ids(TaskFilter::new().past_deadline_only()), vec![3]);
+        assert_eq!(ids(TaskFilter::new().deadline_soon(3)), vec![3, 4]);
+        assert_eq!(ids(TaskFilter::new().sort_by(TaskSort::Deadline)), vec![3, 4, 2, 1]);
+    }
+
+    #[test]
+    fn test_filter_scheduled_within() {
+        let mut collection = TaskCollection::new();
+        let today = Local::now().date_naive();
+        let in_days = |days| Some(Local::now() + chrono::Duration::days(days));
+        collection.insert(Task::new("Unscheduled").with_id(1));
+        collection.insert(Task::new("Today").with_id(2).with_scheduled(in_days(0)));
+        collection.insert(Task::new("Next week").with_id(3).with_scheduled(in_days(7)));
+        collection.insert(Task::new("Last week").with_id(4).with_scheduled(in_days(-7)));
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        let next_days = DateRange::new(Some(today), today.checked_add_days(chrono::Days::new(6)));
+        assert_eq!(ids(TaskFilter::new().scheduled_within(next_days)), vec![2]);
+        assert_eq!(ids(TaskFilter::new().scheduled_within(DateRange::after(today))), vec![3]);
+        assert_eq!(
+            ids(TaskFilter::new()
+                .scheduled_within(DateRange::before(today + chrono::Duration::days(1)))
+                .scheduled_within(DateRange::after(today - chrono::Duration::days(8)))),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn test_filter_and_sort_by_field() {
+        let mut collection = TaskCollection::new();
+        let with_fields = |title: &str, fields: &[(&str, &str)]| {
+            let mut task = Task::new(title);
+            for (key, value) in fields {
+                task.set_field(key, Some(value)).unwrap();
+            }
+            task
+        };
+        collection.add_task(with_fields("Invoice", &[("customer", "Acme"), ("estimate", "10")]));
+        collection.add_task(with_fields("Call", &[("customer", "Globex"), ("estimate", "9")]));
+        collection.add_task(with_fields("Report", &[("customer", "acme")]));
+        collection.add("Personal", None);
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(TaskFilter::new().with_field("Customer", "ACME")), vec![1, 3]);
+        assert_eq!(ids(TaskFilter::new().has_field("estimate")), vec![1, 2]);
+        assert_eq!(ids(TaskFilter::new().sort_by(TaskSort::Field("estimate".to_string()))), vec![2, 1, 3, 4]);
+        assert_eq!(ids(TaskFilter::new().sort_by(TaskSort::from_str("field:customer"))), vec![1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn test_filter_query() {
+        let mut collection = TaskCollection::new();
+        let blocker = collection.add_with_category_tags("Order parts", None, None, vec!["work".to_string()]);
+        let blocked = collection.add_with_category_tags("Repair", None, None, vec!["work".to_string()]);
+        collection.add_with_category_tags("Relax", None, None, vec!["home".to_string()]);
+        collection.add_dependency(blocked, blocker).unwrap();
+
+        let ids = |filter: TaskFilter| -> Vec<u32> {
+            collection.get_filtered(&filter).iter().map(|t| t.id).collect()
+        };
+        let query = |input: &str| Query::parse(input).unwrap();
+        assert_eq!(ids(TaskFilter::new().matching(query("tag:work and not is:blocked"))), vec![blocker]);
+        assert_eq!(ids(TaskFilter::new().matching(query("is:blocked or tag:home")).reversed()), vec![3, blocked]);
+        assert_eq!(ids(TaskFilter::new().matching(query("tag:work")).search("repair")), vec![blocked]);
+    }
 }
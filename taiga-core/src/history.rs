@@ -0,0 +1,215 @@
+//! Task history as a list of changes
+//!
+//! A change records a task before and after an operation, so it can be
+//! replayed forward (redo) or inverted (undo). Changes are computed by
+//! comparing two snapshots of a collection; storing them is up to the caller.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CoreError, Result};
+use crate::task::{Task, TaskCollection};
+
+/// A single task changed by an operation
+///
+/// `before` is None for an added task, `after` is None for a removed one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    pub id: u32,
+    pub before: Option<Task>,
+    pub after: Option<Task>,
+}
+
+impl Change {
+    /// The change that reverts this one
+    pub fn inverse(&self) -> Change {
+        Change {
+            id: self.id,
+            before: self.after.clone(),
+            after: self.before.clone(),
+        }
+    }
+
+    /// One-line description, e.g. "~ #3 Pay rent: status open → done, scheduled"
+    pub fn summary(&self) -> String {
+        match (&self.before, &self.after) {
+            (None, Some(task)) => format!("+ #{} {}", self.id, task.title),
+            (Some(task), None) => format!("- #{} {}", self.id, task.title),
+            (Some(old), Some(new)) => {
+                let fields = changed_fields(old, new);
+                if fields.is_empty() {
+                    format!("~ #{} {}", self.id, new.title)
+                } else {
+                    format!("~ #{} {}: {}", self.id, new.title, fields.join(", "))
+                }
+            }
+            (None, None) => format!("~ #{}", self.id),
+        }
+    }
+}
+
+/// Names of the fields that differ between two versions of a task
+fn changed_fields(old: &Task, new: &Task) -> Vec<String> {
+    let mut fields = Vec::new();
+    if old.status != new.status {
+        fields.push(format!("status {} → {}", old.status, new.status));
+    }
+    if old.title != new.title {
+        fields.push(format!("renamed from '{}'", old.title));
+    }
+    let others = [
+        ("scheduled", old.scheduled != new.scheduled),
+        ("start", old.start != new.start),
+        ("deadline", old.deadline != new.deadline || old.deadline_warning != new.deadline_warning),
+        ("category", old.category != new.category),
+        ("tags", old.tags != new.tags),
+        ("priority", old.priority != new.priority),
+        ("parent", old.parent != new.parent),
+        ("dependencies", old.blocked_by != new.blocked_by),
+        ("repeat", old.recurrence != new.recurrence),
+        ("notes", old.notes != new.notes),
+        ("checklist", old.checklist != new.checklist),
+        ("links", old.links != new.links),
+        ("fields", old.fields != new.fields),
+        ("time zone", old.zone != new.zone),
+        ("id", old.id != new.id),
+    ];
+    fields.extend(
+        others
+            .into_iter()
+            .filter(|&(_, changed)| changed)
+            .map(|(name, _)| name.to_string()),
+    );
+    fields
+}
+
+/// Whether two versions of a task are the same, ignoring the last-update time
+fn same_task(old: Option<&Task>, new: Option<&Task>) -> bool {
+    match (old, new) {
+        (Some(old), Some(new)) => old == &new.clone().with_updated(old.updated),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Compute the changes turning one snapshot into another, sorted by ID
+///
+/// Only the last-update time differing does not count as a change.
+pub fn diff(before: &TaskCollection, after: &TaskCollection) -> Vec<Change> {
+    let mut ids: Vec<u32> = before.tasks.keys().chain(after.tasks.keys()).copied().collect();
+    ids.sort();
+    ids.dedup();
+
+    ids.into_iter()
+        .filter_map(|id| {
+            let old = before.get(id);
+            let new = after.get(id);
+            (!same_task(old, new)).then(|| Change {
+                id,
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Apply changes to a collection
+///
+/// Every task must still be as the change found it (`before`), otherwise
+/// nothing is applied, since the task was changed in the meantime. As with
+/// [`diff`], a task that was only touched still counts as unchanged.
+pub fn apply(collection: &mut TaskCollection, changes: &[Change]) -> Result<()> {
+    let conflicts: Vec<String> = changes
+        .iter()
+        .filter(|change| !same_task(change.before.as_ref(), collection.get(change.id)))
+        .map(|change| format!("#{}", change.id))
+        .collect();
+    if !conflicts.is_empty() {
+        return Err(CoreError::validation(
+            "history",
+            format!("task(s) {} changed since", conflicts.join(", ")),
+        ));
+    }
+
+    for change in changes {
+        collection.tasks.remove(&change.id);
+    }
+    for task in changes.iter().filter_map(|change| change.after.clone()) {
+        collection.insert(task);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Status;
+
+    fn snapshot() -> TaskCollection {
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Pay rent").with_id(1).with_uid("aaaa1111"));
+        collection.insert(Task::new("Call mom").with_id(2).with_uid("bbbb2222"));
+        collection
+    }
+
+    #[test]
+    fn test_diff_and_summary() {
+        let before = snapshot();
+        let mut after = snapshot();
+        after.get_mut(1).unwrap().status = Status::Done;
+        after.remove(2);
+        after.insert(Task::new("Water plants").with_id(3).with_uid("cccc3333"));
+
+        let changes = diff(&before, &after);
+        let summaries: Vec<String> = changes.iter().map(Change::summary).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "~ #1 Pay rent: status open → done",
+                "- #2 Call mom",
+                "+ #3 Water plants",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_touch_alone_is_not_a_change() {
+        let before = snapshot();
+        let mut after = snapshot();
+        after.get_mut(1).unwrap().touch();
+        assert!(diff(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn test_undo_and_redo() {
+        let before = snapshot();
+        let mut after = snapshot();
+        after.get_mut(2).unwrap().title = "Call dad".to_string();
+        after.remove(1);
+        let changes = diff(&before, &after);
+
+        let mut current = after.clone();
+        let inverse: Vec<Change> = changes.iter().map(Change::inverse).collect();
+        apply(&mut current, &inverse).unwrap();
+        assert!(diff(&before, &current).is_empty());
+
+        apply(&mut current, &changes).unwrap();
+        assert!(diff(&after, &current).is_empty());
+
+        // Applying twice finds the tasks already changed
+        assert!(apply(&mut current, &changes).is_err());
+    }
+
+    #[test]
+    fn test_undo_after_touch() {
+        let before = snapshot();
+        let mut after = snapshot();
+        after.get_mut(2).unwrap().title = "Call dad".to_string();
+        let changes = diff(&before, &after);
+
+        let mut current = after.clone();
+        current.get_mut(2).unwrap().touch();
+        let inverse: Vec<Change> = changes.iter().map(Change::inverse).collect();
+        apply(&mut current, &inverse).unwrap();
+        assert_eq!(current.get(2).unwrap().title, "Call mom");
+    }
+}
@@ -3,11 +3,30 @@
 //! This crate contains no I/O operations. All persistence
 //! is handled by adapters in consuming crates.
 
+pub mod calendar;
+pub mod category;
 pub mod date;
 pub mod error;
+pub mod field;
 pub mod filter;
+pub mod history;
+pub mod locale;
+pub mod markdown;
+pub mod query;
+pub mod quick_add;
+pub mod recurrence;
 pub mod task;
+pub mod template;
+pub mod uid;
+pub mod zone;
 
+pub use calendar::WorkCalendar;
 pub use error::{CoreError, Result};
-pub use filter::{TaskFilter, TaskSort};
-pub use task::{Task, TaskCollection, TaskId};
+pub use filter::{Deferred, TaskFilter, TaskSort};
+pub use locale::Locale;
+pub use query::Query;
+pub use quick_add::QuickAdd;
+pub use recurrence::{Frequency, Recurrence};
+pub use task::{ChecklistItem, DeadlineState, Priority, Status, Task, TaskCollection, TaskId, TaskRef};
+pub use template::{Template, TemplateTask};
+pub use zone::Zone;
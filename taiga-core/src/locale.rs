@@ -0,0 +1,567 @@
+//! Languages for reading and showing dates
+//!
+//! A [`Locale`] holds what [`crate::date`] needs to work in a language: day
+//! and month names, the words for "today", "tomorrow" and "overdue", the
+//! order of day and month in numeric dates ("01/02" is January 2 in English
+//! but February 1 in German and Spanish) and a table of words that turns a
+//! date expression into the English one the parser reads, so "nächsten
+//! Freitag" reads as "next friday" and "en 3 días" as "in 3 days".
+//!
+//! English, German and Spanish are built in; another language is one more
+//! `Locale` value. English expressions are understood whatever the locale.
+
+use chrono::{Datelike, NaiveDate};
+use std::sync::{LazyLock, RwLock};
+
+use crate::error::{CoreError, Result};
+
+/// Locale installed for parsing and showing dates
+static CURRENT: LazyLock<RwLock<Locale>> = LazyLock::new(|| RwLock::new(Locale::EN));
+
+/// Longest phrase in a word table, in words
+const MAX_PHRASE_WORDS: usize = 5;
+
+/// Order of the day and the month in numeric dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// "01/25/2026"
+    MonthDay,
+    /// "25/01/2026", "25.01.2026"
+    DayMonth,
+}
+
+/// Names, words and date order of a language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    /// Language code, e.g. "de"
+    pub code: &'static str,
+    /// Day names, Monday first
+    pub weekdays: [&'static str; 7],
+    pub weekdays_short: [&'static str; 7],
+    /// Month names, January first
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    pub today: &'static str,
+    pub tomorrow: &'static str,
+    pub yesterday: &'static str,
+    /// Label of a past date, as in "Overdue (3 days ago)"
+    pub overdue: &'static str,
+    /// A number of days, "{}" standing for the number
+    pub days: &'static str,
+    /// A number of days in the past, "{}" standing for the number
+    pub days_ago: &'static str,
+    /// Format of a date in the coming week, with chrono's specifiers
+    pub week_format: &'static str,
+    /// Format of a date later this year
+    pub date_format: &'static str,
+    pub date_order: DateOrder,
+    /// Lowercase words and phrases with the English words they stand for;
+    /// an empty translation drops the word
+    pub words: &'static [(&'static str, &'static str)],
+    /// Words for "o'clock" after an hour, as in "15 Uhr"
+    pub hour_words: &'static [&'static str],
+}
+
+impl Locale {
+    pub const EN: Self = Self {
+        code: "en",
+        weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+        weekdays_short: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        months: [
+            "January", "February", "March", "April", "May", "June", "July", "August", "September", "October",
+            "November", "December",
+        ],
+        months_short: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+        today: "Today",
+        tomorrow: "Tomorrow",
+        yesterday: "Yesterday",
+        overdue: "Overdue",
+        days: "{} days",
+        days_ago: "{} days ago",
+        week_format: "%a %b %d",
+        date_format: "%b %d",
+        date_order: DateOrder::MonthDay,
+        words: &[],
+        hour_words: &[],
+    };
+
+    pub const DE: Self = Self {
+        code: "de",
+        weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+        weekdays_short: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        months: [
+            "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober",
+            "November", "Dezember",
+        ],
+        months_short: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+        today: "Heute",
+        tomorrow: "Morgen",
+        yesterday: "Gestern",
+        overdue: "Überfällig",
+        days: "{} Tage",
+        days_ago: "vor {} Tagen",
+        week_format: "%a, %d. %b",
+        date_format: "%d. %b",
+        date_order: DateOrder::DayMonth,
+        words: GERMAN_WORDS,
+        hour_words: &["uhr"],
+    };
+
+    pub const ES: Self = Self {
+        code: "es",
+        weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+        weekdays_short: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        months: [
+            "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre",
+            "noviembre", "diciembre",
+        ],
+        months_short: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"],
+        today: "Hoy",
+        tomorrow: "Mañana",
+        yesterday: "Ayer",
+        overdue: "Vencida",
+        days: "{} días",
+        days_ago: "hace {} días",
+        week_format: "%a %d %b",
+        date_format: "%d %b",
+        date_order: DateOrder::DayMonth,
+        words: SPANISH_WORDS,
+        hour_words: &["h"],
+    };
+
+    /// The built-in locales
+    pub const BUILT_IN: [Self; 3] = [Self::EN, Self::DE, Self::ES];
+
+    /// Parse a language code ("de", "es_ES.UTF-8", "C")
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        Self::find(input).ok_or_else(|| {
+            let codes: Vec<&str> = Self::BUILT_IN.iter().map(|locale| locale.code).collect();
+            CoreError::validation(
+                "locale",
+                format!("Unknown language '{}' (use one of: {})", input, codes.join(", ")),
+            )
+        })
+    }
+
+    /// Find a built-in locale by the language of a code such as "de_AT.UTF-8"
+    fn find(name: &str) -> Option<Self> {
+        let language = name.split(['_', '-', '.', '@']).next().unwrap_or_default().to_lowercase();
+        match language.as_str() {
+            "c" | "posix" => Some(Self::EN),
+            code => Self::BUILT_IN.into_iter().find(|locale| locale.code == code),
+        }
+    }
+
+    /// Make this the locale dates are parsed and shown in
+    pub fn install(self) {
+        *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = self;
+    }
+
+    /// The locale dates are parsed and shown in
+    pub fn current() -> Self {
+        *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Format a date with chrono's specifiers, with day and month names
+    /// (%A, %a, %B, %b) in this language
+    pub fn format(&self, date: NaiveDate, pattern: &str) -> String {
+        let weekday = date.weekday().num_days_from_monday() as usize;
+        let month = date.month0() as usize;
+        let pattern = pattern
+            .replace("%A", self.weekdays[weekday])
+            .replace("%a", self.weekdays_short[weekday])
+            .replace("%B", self.months[month])
+            .replace("%b", self.months_short[month]);
+        date.format(&pattern).to_string()
+    }
+
+    /// Turn a lowercase date expression in this language into English
+    ///
+    /// Words and phrases are looked up in the word table, the longest phrase
+    /// first; other words are kept. "25." reads as the day 25, an hour
+    /// followed by an "o'clock" word or after a word for "at" ("um 9", "a las
+    /// 9") as that full hour, and "of" next to a number is left out ("25 de
+    /// enero" is "25 january"). English words pass through unchanged.
+    pub fn translate(&self, input: &str) -> String {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let mut english: Vec<String> = Vec::new();
+        let mut after_at = false;
+        let mut i = 0;
+        while i < words.len() {
+            let phrase = (1..=MAX_PHRASE_WORDS.min(words.len() - i)).rev().find_map(|count| {
+                let phrase = words[i..i + count].join(" ");
+                self.words
+                    .iter()
+                    .find(|(word, _)| *word == phrase)
+                    .map(|(_, translation)| (count, *translation))
+            });
+            if let Some((count, translation)) = phrase {
+                english.extend(translation.split_whitespace().map(String::from));
+                after_at = translation == "at";
+                i += count;
+                continue;
+            }
+
+            let word = words[i];
+            let at = std::mem::take(&mut after_at);
+            i += 1;
+            let hour = self
+                .hour_words
+                .iter()
+                .find_map(|suffix| word.strip_suffix(suffix))
+                .filter(|hour| hour.is_empty() || is_number(hour));
+            match hour {
+                // "15 Uhr"
+                Some("") if english.last().is_some_and(|last| is_number(last)) => {
+                    if let Some(last) = english.last_mut() {
+                        last.push_str(":00");
+                    }
+                }
+                Some("") if english.last().is_some_and(|last| last.contains(':')) => {}
+                // "15h"
+                Some(hour) if !hour.is_empty() => english.push(format!("{}:00", hour)),
+                // "um 9" is nine o'clock
+                _ if at && is_number(word) => english.push(format!("{}:00", word)),
+                _ => match word.strip_suffix('.') {
+                    Some(day) if is_number(day) => english.push(day.to_string()),
+                    _ => english.push(word.to_string()),
+                },
+            }
+        }
+
+        // "of" is dropped next to a number
+        let english: Vec<String> = english
+            .iter()
+            .enumerate()
+            .filter(|&(i, word)| {
+                word != "of"
+                    || !((i > 0 && is_number(&english[i - 1])) || english.get(i + 1).is_some_and(|next| is_number(next)))
+            })
+            .map(|(_, word)| word.clone())
+            .collect();
+
+        // Range words that are the same in the middle but not at the start:
+        // "bis Freitag" is "until friday", "desde el lunes" "since monday"
+        let mut english = english.join(" ");
+        if let Some(rest) = english.strip_prefix("to ") {
+            english = format!("until {}", rest);
+        } else if let Some(rest) = english.strip_prefix("from ")
+            && !rest.contains(" to ")
+        {
+            english = format!("since {}", rest);
+        }
+        english
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::EN
+    }
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.len() <= 4 && word.chars().all(|c| c.is_ascii_digit())
+}
+
+/// German words; "so" for Sunday is left out as it is a common word
+const GERMAN_WORDS: &[(&str, &str)] = &[
+    ("heute", "today"),
+    ("morgen", "tomorrow"),
+    ("gestern", "yesterday"),
+    ("übermorgen", "in 2 days"),
+    ("uebermorgen", "in 2 days"),
+    ("vorgestern", "-2d"),
+    ("nächste", "next"),
+    ("nächsten", "next"),
+    ("nächster", "next"),
+    ("nächstes", "next"),
+    ("naechste", "next"),
+    ("naechsten", "next"),
+    ("naechster", "next"),
+    ("kommende", "next"),
+    ("kommenden", "next"),
+    ("letzte", "last"),
+    ("letzten", "last"),
+    ("letzter", "last"),
+    ("vergangene", "last"),
+    ("vergangenen", "last"),
+    ("diese", "this"),
+    ("diesen", "this"),
+    ("dieser", "this"),
+    ("dieses", "this"),
+    ("ende der woche", "end of week"),
+    ("ende der arbeitswoche", "end of work week"),
+    ("ende des monats", "end of month"),
+    ("monatsende", "end of month"),
+    ("ende des jahres", "end of year"),
+    ("jahresende", "end of year"),
+    ("im monat", "of the month"),
+    ("des monats", "of the month"),
+    ("im nächsten monat", "of next month"),
+    ("im kommenden monat", "of next month"),
+    ("im", "of"),
+    ("tag", "day"),
+    ("tage", "days"),
+    ("tagen", "days"),
+    ("woche", "week"),
+    ("wochen", "weeks"),
+    ("monat", "month"),
+    ("monate", "months"),
+    ("monaten", "months"),
+    ("jahr", "year"),
+    ("jahre", "years"),
+    ("jahren", "years"),
+    ("werktag", "workday"),
+    ("werktage", "workdays"),
+    ("werktagen", "workdays"),
+    ("arbeitstag", "workday"),
+    ("arbeitstage", "workdays"),
+    ("arbeitstagen", "workdays"),
+    ("stunde", "hour"),
+    ("stunden", "hours"),
+    ("minute", "minute"),
+    ("minuten", "minutes"),
+    ("ein", "a"),
+    ("eine", "a"),
+    ("einem", "a"),
+    ("einer", "a"),
+    ("erste", "first"),
+    ("ersten", "first"),
+    ("erster", "first"),
+    ("zweite", "second"),
+    ("zweiten", "second"),
+    ("zweiter", "second"),
+    ("dritte", "third"),
+    ("dritten", "third"),
+    ("dritter", "third"),
+    ("vierte", "fourth"),
+    ("vierten", "fourth"),
+    ("vierter", "fourth"),
+    ("fünfte", "fifth"),
+    ("fünften", "fifth"),
+    ("fünfter", "fifth"),
+    ("montag", "monday"),
+    ("mo", "monday"),
+    ("dienstag", "tuesday"),
+    ("di", "tuesday"),
+    ("mittwoch", "wednesday"),
+    ("mi", "wednesday"),
+    ("donnerstag", "thursday"),
+    ("do", "thursday"),
+    ("freitag", "friday"),
+    ("fr", "friday"),
+    ("samstag", "saturday"),
+    ("sonnabend", "saturday"),
+    ("sa", "saturday"),
+    ("sonntag", "sunday"),
+    ("januar", "january"),
+    ("jänner", "january"),
+    ("februar", "february"),
+    ("märz", "march"),
+    ("maerz", "march"),
+    ("mär", "march"),
+    ("mai", "may"),
+    ("juni", "june"),
+    ("juli", "july"),
+    ("oktober", "october"),
+    ("okt", "october"),
+    ("dezember", "december"),
+    ("dez", "december"),
+    ("mittag", "noon"),
+    ("mitternacht", "midnight"),
+    ("um", "at"),
+    ("am", ""),
+    ("den", ""),
+    ("der", ""),
+    ("die", ""),
+    ("das", ""),
+    ("von", "from"),
+    ("ab", "since"),
+    ("seit", "since"),
+    ("bis", "to"),
+    ("vor", "before"),
+    ("nach", "after"),
+    ("zwischen", "between"),
+    ("und", "and"),
+];
+
+/// Spanish words, with and without accents
+const SPANISH_WORDS: &[(&str, &str)] = &[
+    ("hoy", "today"),
+    ("mañana", "tomorrow"),
+    ("manana", "tomorrow"),
+    ("ayer", "yesterday"),
+    ("pasado mañana", "in 2 days"),
+    ("pasado manana", "in 2 days"),
+    ("anteayer", "-2d"),
+    ("antes de ayer", "-2d"),
+    ("próximo", "next"),
+    ("próxima", "next"),
+    ("próximos", "next"),
+    ("próximas", "next"),
+    ("proximo", "next"),
+    ("proxima", "next"),
+    ("proximos", "next"),
+    ("proximas", "next"),
+    ("semana que viene", "next week"),
+    ("mes que viene", "next month"),
+    ("año que viene", "next year"),
+    ("pasado", "last"),
+    ("pasada", "last"),
+    ("último", "last"),
+    ("última", "last"),
+    ("últimos", "last"),
+    ("últimas", "last"),
+    ("ultimo", "last"),
+    ("ultima", "last"),
+    ("ultimos", "last"),
+    ("ultimas", "last"),
+    ("este", "this"),
+    ("esta", "this"),
+    ("fin de la semana", "end of week"),
+    ("fin de la semana laboral", "end of work week"),
+    ("fin de mes", "end of month"),
+    ("fin del mes", "end of month"),
+    ("fin de año", "end of year"),
+    ("fin del año", "end of year"),
+    ("del mes", "of the month"),
+    ("del próximo mes", "of next month"),
+    ("del mes que viene", "of next month"),
+    ("día", "day"),
+    ("dia", "day"),
+    ("días", "days"),
+    ("dias", "days"),
+    ("semana", "week"),
+    ("semanas", "weeks"),
+    ("mes", "month"),
+    ("meses", "months"),
+    ("año", "year"),
+    ("años", "years"),
+    ("día hábil", "workday"),
+    ("dia habil", "workday"),
+    ("días hábiles", "workdays"),
+    ("dias habiles", "workdays"),
+    ("día laborable", "workday"),
+    ("días laborables", "workdays"),
+    ("hora", "hour"),
+    ("horas", "hours"),
+    ("minuto", "minute"),
+    ("minutos", "minutes"),
+    ("un", "a"),
+    ("una", "a"),
+    ("primer", "first"),
+    ("primero", "first"),
+    ("primera", "first"),
+    ("segundo", "second"),
+    ("segunda", "second"),
+    ("tercer", "third"),
+    ("tercero", "third"),
+    ("tercera", "third"),
+    ("cuarto", "fourth"),
+    ("cuarta", "fourth"),
+    ("quinto", "fifth"),
+    ("quinta", "fifth"),
+    ("lunes", "monday"),
+    ("lun", "monday"),
+    ("martes", "tuesday"),
+    ("miércoles", "wednesday"),
+    ("miercoles", "wednesday"),
+    ("mié", "wednesday"),
+    ("mie", "wednesday"),
+    ("jueves", "thursday"),
+    ("jue", "thursday"),
+    ("viernes", "friday"),
+    ("vie", "friday"),
+    ("sábado", "saturday"),
+    ("sabado", "saturday"),
+    ("sáb", "saturday"),
+    ("sab", "saturday"),
+    ("domingo", "sunday"),
+    ("dom", "sunday"),
+    ("enero", "january"),
+    ("ene", "january"),
+    ("febrero", "february"),
+    ("marzo", "march"),
+    ("abril", "april"),
+    ("abr", "april"),
+    ("mayo", "may"),
+    ("junio", "june"),
+    ("julio", "july"),
+    ("agosto", "august"),
+    ("ago", "august"),
+    ("septiembre", "september"),
+    ("setiembre", "september"),
+    ("octubre", "october"),
+    ("noviembre", "november"),
+    ("diciembre", "december"),
+    ("dic", "december"),
+    ("mediodía", "noon"),
+    ("mediodia", "noon"),
+    ("medianoche", "midnight"),
+    ("a las", "at"),
+    ("a la", "at"),
+    ("en", "in"),
+    ("de", "of"),
+    ("del", "of"),
+    ("el", ""),
+    ("la", ""),
+    ("los", ""),
+    ("las", ""),
+    ("desde", "from"),
+    ("hasta", "to"),
+    ("antes de", "before"),
+    ("antes del", "before"),
+    ("después de", "after"),
+    ("después del", "after"),
+    ("despues de", "after"),
+    ("despues del", "after"),
+    ("entre", "between"),
+    ("y", "and"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(Locale::parse("de").unwrap(), Locale::DE);
+        assert_eq!(Locale::parse("es_MX.UTF-8").unwrap(), Locale::ES);
+        assert_eq!(Locale::parse("C").unwrap(), Locale::EN);
+        assert!(Locale::parse("").is_err());
+        assert!(Locale::parse("tlh").is_err());
+    }
+
+    #[test]
+    fn test_translate() {
+        let de = |input: &str| Locale::DE.translate(input);
+        assert_eq!(de("nächsten freitag"), "next friday");
+        assert_eq!(de("morgen um 15 uhr"), "tomorrow at 15:00");
+        assert_eq!(de("am 25. januar"), "25 january");
+        assert_eq!(de("letzter freitag im monat"), "last friday of the month");
+        assert_eq!(de("bis ende des monats"), "until end of month");
+        assert_eq!(de("von montag bis freitag"), "from monday to friday");
+
+        let es = |input: &str| Locale::ES.translate(input);
+        assert_eq!(es("mañana a las 9"), "tomorrow at 9:00");
+        assert_eq!(es("el próximo viernes"), "next friday");
+        assert_eq!(es("25 de enero de 2027"), "25 january 2027");
+        assert_eq!(es("primer lunes de marzo"), "first monday of march");
+        assert_eq!(es("desde el lunes"), "since monday");
+        assert_eq!(es("en 3 días hábiles"), "in 3 workdays");
+
+        // English words are left as they are
+        assert_eq!(Locale::EN.translate("tomorrow at 9"), "tomorrow at 9");
+        assert_eq!(de("morgen at 9"), "tomorrow at 9");
+    }
+
+    #[test]
+    fn test_format() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(Locale::EN.format(date, Locale::EN.week_format), "Mon Mar 02");
+        assert_eq!(Locale::DE.format(date, Locale::DE.week_format), "Mo, 02. Mär");
+        assert_eq!(Locale::ES.format(date, "%A %d de %B"), "lunes 02 de marzo");
+    }
+}
@@ -0,0 +1,924 @@
+//! Markdown task file format
+//!
+//! Tasks are stored one per line under category headers, subtasks indented
+//! below their parent with checklists, links and notes beneath them:
+//!
+//! ```text
+//! ## Work
+//! [ID:1] - [ ] Ship release #work !1 (Scheduled: 2026-01-25) (UID: k3x9a2mf)
+//!   - [x] Changelog
+//!   [ID:2] - [/] Tag build
+//! ```
+//!
+//! This module only turns text into tasks and back; reading and writing
+//! files is up to the caller.
+
+use chrono::{DateTime, Local, NaiveDate};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write};
+use std::sync::LazyLock;
+
+use crate::date::{format_task_date, format_timestamp, parse_task_date, parse_timestamp};
+use crate::error::{CoreError, Result};
+use crate::task::{link_name, ChecklistItem, Priority, Status, Task, TaskCollection};
+use crate::{category, field, Recurrence};
+
+// Regex pattern is validated at compile time - invalid patterns are programming errors
+static TASK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\[ID:(\d+)\] - \[(.)\] (.*)$")
+        .expect("Invalid regex pattern - this is a compile-time constant")
+});
+
+// Trailing task attribute: (Scheduled: 2026-01-25), (Repeat: weekly), (Blocked by: 3, 5),
+// (UID: k3x9a2mf), (Times: c20260125T1403 u20260126T0900 d20260127T1015)
+static ATTRIBUTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\s*\((Start|Scheduled|Deadline|Repeat|Blocked by|UID|Times): ([^()]*)\)$")
+        .expect("Invalid attribute regex pattern")
+});
+
+// Highest task number handed out, kept when ID reuse is disabled: <!-- next-id: 14 -->
+static NEXT_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^<!--\s*next-id:\s*(\d+)\s*-->$").expect("Invalid next-id regex pattern")
+});
+
+// Checklist item directly under a task: - [ ] Passport
+static CHECKLIST_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^- \[([ xX])\] (.+)$").expect("Invalid checklist regex pattern")
+});
+
+// Attached link directly under a task: - [42](https://tracker.example/issues/42),
+// with <...> around targets containing spaces or parentheses
+static LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^- \[[^\]]*\]\((?:<([^<>]+)>|([^<>\s]+))\)$").expect("Invalid link regex pattern")
+});
+
+// Category header pattern: ## Category Name, ### Subcategory, #### ...
+static CATEGORY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(#{2,})\s+(.+)$").expect("Invalid category regex pattern")
+});
+
+// Tag pattern: #word (alphanumeric and underscores)
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"#(\w+)").expect("Invalid tag regex pattern")
+});
+
+// Priority pattern: !1 (most urgent) to !4 as a standalone word
+static PRIORITY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|\s)!([1-4])(?:\s|$)").expect("Invalid priority regex pattern")
+});
+
+/// Indentation written per subtask level
+const INDENT: &str = "  ";
+
+/// Read tasks from the contents of a task file
+///
+/// With `reuse_ids` disabled the next task number stored in the file is
+/// kept. Lines that are neither tasks nor part of one are skipped and
+/// returned as errors, so the caller can warn about them.
+pub fn parse(content: &str, reuse_ids: bool) -> (TaskCollection, Vec<CoreError>) {
+    let mut collection = TaskCollection::new().with_reuse_ids(reuse_ids);
+    let mut skipped = Vec::new();
+
+    let mut current_category: Option<String> = None;
+    // Names of the headers enclosing the current line, from the top level
+    let mut category_path: Vec<String> = Vec::new();
+    // Open ancestors of the current line as (indent, task ID)
+    let mut parents: Vec<(usize, u32)> = Vec::new();
+    // Notes collected per task, and blank lines seen since the last note
+    let mut notes: HashMap<u32, Vec<String>> = HashMap::new();
+    let mut checklists: HashMap<u32, Vec<ChecklistItem>> = HashMap::new();
+    let mut links: HashMap<u32, Vec<String>> = HashMap::new();
+    let mut pending_blank_lines = 0;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            pending_blank_lines += 1;
+            continue;
+        }
+        let blank_lines = std::mem::take(&mut pending_blank_lines);
+        let indent = line.len() - line.trim_start().len();
+
+        if indent == 0
+            && let Some(caps) = NEXT_ID_REGEX.captures(trimmed)
+        {
+            if !reuse_ids
+                && let Some(next_id) = caps.get(1).and_then(|m| m.as_str().parse().ok())
+            {
+                collection.next_id = collection.next_id.max(next_id);
+            }
+            continue;
+        }
+
+        // Check for category header
+        if indent == 0
+            && let Some(caps) = CATEGORY_REGEX.captures(trimmed)
+        {
+            // Each extra '#' is one level deeper; "Uncategorized" maps to None
+            let level = caps[1].len() - 2;
+            let name = caps[2].trim();
+            category_path.truncate(level);
+            if level > 0 || !name.eq_ignore_ascii_case("uncategorized") {
+                category_path.push(name.to_string());
+            }
+            current_category = category::normalize(&category_path.join("/"));
+            parents.clear();
+            continue;
+        }
+
+        // Indented task lines are subtasks of the nearest less-indented task,
+        // other indented lines are notes of that task
+        while parents.last().is_some_and(|&(i, _)| i >= indent) {
+            parents.pop();
+        }
+        let owner = parents.last().copied();
+
+        match (parse_task_line(trimmed, current_category.clone()), owner) {
+            (Ok(task), _) => {
+                let task = task.with_parent(owner.map(|(_, id)| id));
+                parents.push((indent, task.id));
+                collection.insert(task);
+            }
+            // Checklist items come one level below the task, before any notes
+            (Err(_), Some((owner_indent, owner_id)))
+                if indent == owner_indent + INDENT.len()
+                    && !notes.contains_key(&owner_id)
+                    && CHECKLIST_REGEX.is_match(trimmed) =>
+            {
+                if let Some(caps) = CHECKLIST_REGEX.captures(trimmed) {
+                    checklists.entry(owner_id).or_default().push(
+                        ChecklistItem::new(caps[2].trim()).with_done(&caps[1] != " "),
+                    );
+                }
+            }
+            // Links come one level below the task as well, before any notes
+            (Err(_), Some((owner_indent, owner_id)))
+                if indent == owner_indent + INDENT.len()
+                    && !notes.contains_key(&owner_id)
+                    && LINK_REGEX.is_match(trimmed) =>
+            {
+                if let Some(caps) = LINK_REGEX.captures(trimmed)
+                    && let Some(target) = caps.get(1).or(caps.get(2))
+                {
+                    links.entry(owner_id).or_default().push(target.as_str().to_string());
+                }
+            }
+            (Err(_), Some((owner_indent, owner_id))) => {
+                let lines = notes.entry(owner_id).or_default();
+                if !lines.is_empty() {
+                    lines.extend(std::iter::repeat_n(String::new(), blank_lines));
+                }
+                let strip = indent.min(owner_indent + INDENT.len());
+                lines.push(line[strip..].trim_end().to_string());
+            }
+            (Err(e), None) => skipped.push(e),
+        }
+    }
+
+    // Attach notes, checklists and links directly, so loading does not
+    // mark tasks as updated
+    for (id, lines) in notes {
+        if let Some(task) = collection.tasks.get_mut(&id) {
+            task.set_notes(Some(lines.join("\n")));
+        }
+    }
+    for (id, items) in checklists {
+        if let Some(task) = collection.tasks.get_mut(&id) {
+            task.checklist = items;
+        }
+    }
+    for (id, targets) in links {
+        if let Some(task) = collection.tasks.get_mut(&id) {
+            task.links = targets;
+        }
+    }
+
+    (collection, skipped)
+}
+
+/// Whether a task file stores the next task number, i.e. was written with
+/// ID reuse disabled
+pub fn stores_next_id(content: &str) -> bool {
+    content.lines().any(|line| NEXT_ID_REGEX.is_match(line))
+}
+
+/// Write tasks as the contents of a task file
+pub fn render(collection: &TaskCollection) -> String {
+    let mut out = String::new();
+    write_tasks(&mut out, collection).expect("Writing to a String cannot fail");
+    out
+}
+
+fn write_tasks(out: &mut impl Write, collection: &TaskCollection) -> fmt::Result {
+    if !collection.reuse_ids {
+        writeln!(out, "<!-- next-id: {} -->", collection.next_id)?;
+    }
+
+    // Group tasks by category
+    let mut categorized: BTreeMap<Option<String>, Vec<&Task>> = BTreeMap::new();
+
+    for task in collection.list_all() {
+        categorized
+            .entry(task.category.clone())
+            .or_default()
+            .push(task);
+    }
+
+    // Sort categories as a tree, then Uncategorized (None) last
+    let mut categories: Vec<Option<String>> = categorized.keys().cloned().collect();
+    categories.sort_by(|a, b| match (a, b) {
+        (None, None) => std::cmp::Ordering::Equal,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (Some(a_cat), Some(b_cat)) => category::compare(a_cat, b_cat),
+    });
+
+    // Headers currently open, so subcategories only write their new levels
+    let mut open_headers: Vec<&str> = Vec::new();
+    let mut first_category = true;
+    for category in &categories {
+        // Write category header
+        if !first_category {
+            writeln!(out)?;
+        }
+        first_category = false;
+
+        match category {
+            Some(path) => {
+                let levels: Vec<&str> = category::components(path).collect();
+                let shared = open_headers
+                    .iter()
+                    .zip(&levels)
+                    .take_while(|(open, level)| open == level)
+                    .count()
+                    .min(levels.len().saturating_sub(1));
+                for (depth, name) in levels.iter().enumerate().skip(shared) {
+                    writeln!(out, "{} {}", "#".repeat(depth + 2), name)?;
+                }
+                open_headers = levels;
+            }
+            None => {
+                writeln!(out, "## Uncategorized")?;
+                open_headers.clear();
+            }
+        }
+
+        // Write tasks in this category, subtasks indented under their parent
+        if let Some(tasks) = categorized.get(category) {
+            let in_category: HashSet<u32> = tasks.iter().map(|t| t.id).collect();
+            let mut written = HashSet::new();
+
+            for task in tasks {
+                let is_root = task.parent.is_none_or(|p| !in_category.contains(&p));
+                if is_root {
+                    write_task_tree(out, collection, task, 0, &mut written)?;
+                }
+            }
+
+            // Tasks caught in a parent cycle are written as top-level tasks
+            for task in tasks {
+                if !written.contains(&task.id) {
+                    write_task_tree(out, collection, task, 0, &mut written)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Format an attached link as a markdown link named after its last segment
+fn format_link_line(target: &str) -> String {
+    let name = link_name(target).replace(['[', ']'], "");
+    if target.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("- [{}](<{}>)", name, target)
+    } else {
+        format!("- [{}]({})", name, target)
+    }
+}
+
+/// Write a task followed by its subtasks, indented one level per depth
+fn write_task_tree(
+    out: &mut impl Write,
+    collection: &TaskCollection,
+    task: &Task,
+    depth: usize,
+    written: &mut HashSet<u32>,
+) -> fmt::Result {
+    if !written.insert(task.id) {
+        return Ok(());
+    }
+
+    writeln!(out, "{}{}", INDENT.repeat(depth), format_task_line(task))?;
+
+    for item in &task.checklist {
+        let marker = if item.done { 'x' } else { ' ' };
+        writeln!(out, "{}- [{}] {}", INDENT.repeat(depth + 1), marker, item.text)?;
+    }
+
+    for link in &task.links {
+        writeln!(out, "{}{}", INDENT.repeat(depth + 1), format_link_line(link))?;
+    }
+
+    // Notes go beneath the task, one level deeper; blank lines stay blank
+    if let Some(notes) = &task.notes {
+        for line in notes.lines() {
+            if line.is_empty() {
+                writeln!(out)?;
+            } else {
+                writeln!(out, "{}{}", INDENT.repeat(depth + 1), line)?;
+            }
+        }
+    }
+
+    for child in collection.children(task.id) {
+        if child.category == task.category {
+            write_task_tree(out, collection, child, depth + 1, written)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a task line into a Task
+pub fn parse_task_line(line: &str, category: Option<String>) -> Result<Task> {
+    let caps = TASK_REGEX
+        .captures(line)
+        .ok_or_else(|| CoreError::parse(format!("Invalid task format: {}", line)))?;
+
+    let id = caps
+        .get(1)
+        .ok_or_else(|| CoreError::parse("Missing task ID"))?
+        .as_str()
+        .parse::<u32>()
+        .map_err(|e| CoreError::parse_with_source("Invalid task ID", e))?;
+
+    // Unknown checkbox characters read as open, like before statuses existed
+    let status = caps
+        .get(2)
+        .ok_or_else(|| CoreError::parse("Missing completion status"))?
+        .as_str()
+        .chars()
+        .next()
+        .and_then(Status::from_marker)
+        .unwrap_or_default();
+
+    let mut raw_title = caps
+        .get(3)
+        .ok_or_else(|| CoreError::parse("Missing task title"))?
+        .as_str();
+
+    // Peel "(Key: value)" attributes off the end of the line
+    let mut scheduled = None;
+    let mut zone = None;
+    let mut start = None;
+    let mut deadline = None;
+    let mut deadline_warning = None;
+    let mut recurrence = None;
+    let mut blocked_by = Vec::new();
+    let mut uid = String::new();
+    let mut times = Times::default();
+    while let Some(attr) = ATTRIBUTE_REGEX.captures(raw_title) {
+        let value = attr.get(2).map_or("", |m| m.as_str());
+        match attr.get(1).map_or("", |m| m.as_str()) {
+            "Scheduled" => {
+                let parsed = parse_task_date(value);
+                scheduled = parsed.map(|(dt, _)| dt);
+                zone = zone.or(parsed.and_then(|(_, zone)| zone));
+            }
+            "Start" => start = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok(),
+            "Deadline" => {
+                // "2026-03-05 17:00, warn 5d" carries the task's own lead time
+                let (date, warning) = value.split_once(',').unwrap_or((value, ""));
+                let parsed = parse_task_date(date);
+                deadline = parsed.map(|(dt, _)| dt);
+                zone = zone.or(parsed.and_then(|(_, zone)| zone));
+                deadline_warning = warning
+                    .trim()
+                    .strip_prefix("warn ")
+                    .and_then(|days| days.trim_end_matches('d').parse().ok());
+            }
+            "Repeat" => match Recurrence::parse(value) {
+                Ok(rule) => recurrence = Some(rule),
+                // Keep an unreadable rule in the title rather than losing it
+                Err(_) => break,
+            },
+            "Blocked by" => {
+                blocked_by = value
+                    .split(',')
+                    .filter_map(|id| id.trim().trim_start_matches('#').parse().ok())
+                    .collect();
+            }
+            "UID" => uid = value.trim().to_lowercase(),
+            "Times" => times = parse_times(value),
+            _ => {}
+        }
+        raw_title = &raw_title[..attr.get(0).map_or(raw_title.len(), |m| m.start())];
+    }
+
+    // Split custom key:value fields off the end, before tags so values may contain '#'
+    let (raw_title, fields) = field::extract(raw_title);
+    let raw_title = raw_title.as_str();
+
+    // Extract tags from title
+    let tags: Vec<String> = TAG_REGEX
+        .captures_iter(raw_title)
+        .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+        .collect();
+
+    // Extract priority marker from title
+    let priority = PRIORITY_REGEX
+        .captures(raw_title)
+        .and_then(|cap| cap.get(1))
+        .and_then(|m| m.as_str().parse().ok())
+        .and_then(Priority::from_level);
+
+    // Remove tags and priority marker from title to get clean title
+    let title = TAG_REGEX.replace_all(raw_title, "");
+    let title = PRIORITY_REGEX.replace_all(&title, " ").trim().to_string();
+
+    Ok(Task::new(title)
+        .with_id(id)
+        .with_status(status)
+        .with_scheduled(scheduled)
+        .with_start(start)
+        .with_deadline(deadline)
+        .with_deadline_warning(deadline_warning)
+        .with_zone(zone)
+        .with_category(category)
+        .with_tags(tags)
+        .with_priority(priority)
+        .with_fields(fields)
+        .with_recurrence(recurrence)
+        .with_blocked_by(blocked_by)
+        .with_uid(uid)
+        .with_created(times.created)
+        .with_updated(times.updated.or(times.created))
+        .with_completed(times.completed))
+}
+
+/// Format a Task as a task line
+pub fn format_task_line(task: &Task) -> String {
+    let check_mark = task.status.marker();
+
+    // Build tags string
+    let tags_str = if task.tags.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " {}",
+            task.tags
+                .iter()
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    };
+
+    let priority_str = task
+        .priority
+        .map(|p| format!(" {}", p))
+        .unwrap_or_default();
+
+    let mut line = format!(
+        "[ID:{}] - [{}] {}{}{}",
+        task.id,
+        check_mark,
+        field::escape(&task.title),
+        tags_str,
+        priority_str
+    );
+
+    for (key, value) in &task.fields {
+        line.push(' ');
+        line.push_str(&field::format(key, value));
+    }
+
+    if let Some(start) = &task.start {
+        line.push_str(&format!(" (Start: {})", start.format("%Y-%m-%d")));
+    }
+
+    if let Some(dt) = &task.scheduled {
+        line.push_str(&format!(" (Scheduled: {})", format_task_date(dt, task.zone)));
+    }
+
+    if let Some(dt) = &task.deadline {
+        let warning = task
+            .deadline_warning
+            .map(|days| format!(", warn {}d", days))
+            .unwrap_or_default();
+        line.push_str(&format!(" (Deadline: {}{})", format_task_date(dt, task.zone), warning));
+    }
+
+    if let Some(rule) = &task.recurrence {
+        line.push_str(&format!(" (Repeat: {})", rule));
+    }
+
+    if !task.blocked_by.is_empty() {
+        let ids: Vec<String> = task.blocked_by.iter().map(|id| id.to_string()).collect();
+        line.push_str(&format!(" (Blocked by: {})", ids.join(", ")));
+    }
+
+    if !task.uid.is_empty() {
+        line.push_str(&format!(" (UID: {})", task.uid));
+    }
+
+    let times = format_times(task);
+    if !times.is_empty() {
+        line.push_str(&format!(" (Times: {})", times));
+    }
+
+    line
+}
+
+/// Timestamps read from a `(Times: ...)` attribute
+#[derive(Default)]
+struct Times {
+    created: Option<DateTime<Local>>,
+    updated: Option<DateTime<Local>>,
+    completed: Option<DateTime<Local>>,
+}
+
+/// Parse "c20260125T1403 u20260126T0900 d20260127T1015" (created, updated, done)
+fn parse_times(value: &str) -> Times {
+    let mut times = Times::default();
+    for word in value.split_whitespace() {
+        let mut chars = word.chars();
+        let kind = chars.next();
+        let stamp = parse_timestamp(chars.as_str());
+        match kind {
+            Some('c') => times.created = stamp,
+            Some('u') => times.updated = stamp,
+            Some('d') => times.completed = stamp,
+            _ => {}
+        }
+    }
+    times
+}
+
+/// Format a task's timestamps, leaving out an update time equal to the creation time
+fn format_times(task: &Task) -> String {
+    let created = task.created.as_ref().map(format_timestamp);
+    let updated = task.updated.as_ref().map(format_timestamp).filter(|u| Some(u) != created.as_ref());
+    let completed = task.completed.as_ref().filter(|_| task.is_complete()).map(format_timestamp);
+
+    [("c", created), ("u", updated), ("d", completed)]
+        .into_iter()
+        .filter_map(|(kind, stamp)| stamp.map(|s| format!("{}{}", kind, s)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::zone::Zone;
+
+    #[test]
+    fn test_parse_task_line_simple() {
+        let line = "[ID:1] - [ ] Simple task";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.id, 1);
+        assert_eq!(task.title, "Simple task");
+        assert!(!task.is_complete());
+        assert!(task.scheduled.is_none());
+        assert!(task.category.is_none());
+        assert!(task.tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_task_line_completed() {
+        let line = "[ID:2] - [x] Completed task";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.id, 2);
+        assert!(task.is_complete());
+    }
+
+    #[test]
+    fn test_parse_task_line_scheduled() {
+        let line = "[ID:3] - [ ] Scheduled task (Scheduled: 2026-01-25)";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.id, 3);
+        assert!(task.scheduled.is_some());
+        assert_eq!(
+            task.scheduled.unwrap().date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 25).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_task_line_with_tags() {
+        let line = "[ID:4] - [ ] Complete report #urgent #finance";
+        let task = parse_task_line(line, Some("Work".to_string())).unwrap();
+
+        assert_eq!(task.id, 4);
+        assert_eq!(task.title, "Complete report");
+        assert_eq!(task.category, Some("Work".to_string()));
+        assert_eq!(task.tags, vec!["urgent", "finance"]);
+    }
+
+    #[test]
+    fn test_parse_task_line_with_tags_and_schedule() {
+        let line = "[ID:5] - [ ] Buy groceries #shopping (Scheduled: 2026-01-25)";
+        let task = parse_task_line(line, Some("Personal".to_string())).unwrap();
+
+        assert_eq!(task.id, 5);
+        assert_eq!(task.title, "Buy groceries");
+        assert_eq!(task.category, Some("Personal".to_string()));
+        assert_eq!(task.tags, vec!["shopping"]);
+        assert!(task.scheduled.is_some());
+    }
+
+    #[test]
+    fn test_format_task_line_simple() {
+        let task = Task::new("Simple task").with_id(1);
+        let line = format_task_line(&task);
+
+        assert_eq!(line, "[ID:1] - [ ] Simple task");
+    }
+
+    #[test]
+    fn test_format_task_line_completed() {
+        let task = Task::new("Done").with_id(2).with_complete(true);
+        let line = format_task_line(&task);
+
+        assert_eq!(line, "[ID:2] - [x] Done");
+    }
+
+    #[test]
+    fn test_format_task_line_with_tags() {
+        let task = Task::new("Complete report")
+            .with_id(3)
+            .with_tags(vec!["urgent".to_string(), "finance".to_string()]);
+        let line = format_task_line(&task);
+
+        assert_eq!(line, "[ID:3] - [ ] Complete report #urgent #finance");
+    }
+
+    #[test]
+    fn test_parse_task_line_with_priority() {
+        let line = "[ID:7] - [ ] Ship release #work !1 (Scheduled: 2026-01-25)";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.title, "Ship release");
+        assert_eq!(task.tags, vec!["work"]);
+        assert_eq!(task.priority, Some(Priority::Urgent));
+        assert!(task.scheduled.is_some());
+    }
+
+    #[test]
+    fn test_parse_task_line_exclamation_in_title() {
+        let line = "[ID:8] - [ ] Say hi!1 time";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.title, "Say hi!1 time");
+        assert!(task.priority.is_none());
+    }
+
+    #[test]
+    fn test_roundtrip_with_priority() {
+        let original = Task::new("Pay rent")
+            .with_id(9)
+            .with_tags(vec!["bills".to_string()])
+            .with_priority(Some(Priority::High));
+
+        let line = format_task_line(&original);
+        assert_eq!(line, "[ID:9] - [ ] Pay rent #bills !2");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.title, "Pay rent");
+        assert_eq!(parsed.priority, Some(Priority::High));
+    }
+
+    #[test]
+    fn test_parse_task_line_blocked_by() {
+        let line = "[ID:7] - [ ] Deploy #ops (Scheduled: 2026-01-25) (Blocked by: 3, 5)";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.title, "Deploy");
+        assert_eq!(task.blocked_by, vec![3, 5]);
+        assert_eq!(
+            task.scheduled.unwrap().date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 25).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_task_line_keeps_unknown_parentheses() {
+        let line = "[ID:8] - [ ] Call Bob (Note: after lunch)";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.title, "Call Bob (Note: after lunch)");
+    }
+
+    #[test]
+    fn test_roundtrip_blocked_by() {
+        let original = Task::new("Deploy").with_id(7).with_blocked_by(vec![3, 5]);
+
+        let line = format_task_line(&original);
+        assert_eq!(line, "[ID:7] - [ ] Deploy (Blocked by: 3, 5)");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.blocked_by, vec![3, 5]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let original = Task::new("Test task").with_id(5).with_complete(true);
+
+        let line = format_task_line(&original);
+        let parsed = parse_task_line(&line, None).unwrap();
+
+        assert_eq!(original.id, parsed.id);
+        assert_eq!(original.title, parsed.title);
+        assert_eq!(original.is_complete(), parsed.is_complete());
+    }
+
+    #[test]
+    fn test_roundtrip_with_tags() {
+        let original = Task::new("Test task")
+            .with_id(6)
+            .with_tags(vec!["urgent".to_string(), "work".to_string()])
+            .with_category(Some("Work".to_string()));
+
+        let line = format_task_line(&original);
+        let parsed = parse_task_line(&line, original.category.clone()).unwrap();
+
+        assert_eq!(original.id, parsed.id);
+        assert_eq!(original.title, parsed.title);
+        assert_eq!(original.tags, parsed.tags);
+        assert_eq!(original.category, parsed.category);
+    }
+
+    #[test]
+    fn test_roundtrip_recurrence() {
+        let original = Task::new("Pay bills")
+            .with_id(10)
+            .with_recurrence(Some(Recurrence::parse("every 2nd tuesday").unwrap()))
+            .with_blocked_by(vec![4]);
+
+        let line = format_task_line(&original);
+        assert_eq!(line, "[ID:10] - [ ] Pay bills (Repeat: every 2nd tue) (Blocked by: 4)");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.title, "Pay bills");
+        assert_eq!(parsed.recurrence, original.recurrence);
+        assert_eq!(parsed.blocked_by, vec![4]);
+    }
+
+    #[test]
+    fn test_parse_task_line_invalid_recurrence() {
+        let line = "[ID:11] - [ ] Odd one (Repeat: every blue moon)";
+        let task = parse_task_line(line, None).unwrap();
+
+        assert_eq!(task.title, "Odd one (Repeat: every blue moon)");
+        assert!(task.recurrence.is_none());
+    }
+
+    #[test]
+    fn test_roundtrip_scheduled_time() {
+        let berlin = Zone::parse("Europe/Berlin").unwrap();
+        let three_pm = NaiveDate::from_ymd_opt(2026, 1, 25).unwrap().and_hms_opt(15, 0, 0).unwrap();
+        let at_three = berlin.from_naive(&three_pm).unwrap();
+        let task = Task::new("Dentist").with_id(12).with_scheduled(Some(at_three)).with_zone(Some(berlin));
+
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:12] - [ ] Dentist (Scheduled: 2026-01-25 15:00 Europe/Berlin)");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.scheduled, Some(at_three));
+        assert_eq!(parsed.zone, Some(berlin));
+
+        // Times written before zones are read in the installed zone
+        let parsed = parse_task_line("[ID:12] - [ ] Dentist (Scheduled: 2026-01-25 15:00)", None).unwrap();
+        assert_eq!(parsed.scheduled, Local.from_local_datetime(&three_pm).earliest());
+        assert_eq!(parsed.zone, None);
+
+        // Date-only schedules stay date-only
+        let midnight = Local.with_ymd_and_hms(2026, 1, 25, 0, 0, 0).unwrap();
+        let task = Task::new("Report").with_id(13).with_scheduled(Some(midnight));
+        assert_eq!(format_task_line(&task), "[ID:13] - [ ] Report (Scheduled: 2026-01-25)");
+    }
+
+    #[test]
+    fn test_roundtrip_start() {
+        let due = Local.with_ymd_and_hms(2026, 4, 15, 0, 0, 0).unwrap();
+        let task = Task::new("File taxes")
+            .with_id(14)
+            .with_start(NaiveDate::from_ymd_opt(2026, 3, 1))
+            .with_scheduled(Some(due));
+
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:14] - [ ] File taxes (Start: 2026-03-01) (Scheduled: 2026-04-15)");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.title, "File taxes");
+        assert_eq!(parsed.start, task.start);
+        assert_eq!(parsed.scheduled, Some(due));
+    }
+
+    #[test]
+    fn test_roundtrip_deadline() {
+        let utc = Zone::parse("UTC").unwrap();
+        let due = utc.from_naive(&NaiveDate::from_ymd_opt(2026, 3, 5).unwrap().and_hms_opt(17, 0, 0).unwrap()).unwrap();
+        let task = Task::new("Grant application").with_id(15).with_deadline(Some(due)).with_zone(Some(utc));
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:15] - [ ] Grant application (Deadline: 2026-03-05 17:00 UTC)");
+        assert_eq!(parse_task_line(&line, None).unwrap().deadline, Some(due));
+
+        let task = task.with_deadline_warning(Some(10));
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:15] - [ ] Grant application (Deadline: 2026-03-05 17:00 UTC, warn 10d)");
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.title, "Grant application");
+        assert_eq!(parsed.deadline, Some(due));
+        assert_eq!(parsed.deadline_warning, Some(10));
+    }
+
+    #[test]
+    fn test_roundtrip_fields() {
+        let mut task = Task::new("Fix login")
+            .with_id(16)
+            .with_tags(vec!["bug".to_string()])
+            .with_priority(Some(Priority::High));
+        task.set_field("ticket", Some("OPS-42")).unwrap();
+        task.set_field("sprint", Some("Sprint 12")).unwrap();
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:16] - [ ] Fix login #bug !2 sprint:\"Sprint 12\" ticket:OPS-42");
+
+        let parsed = parse_task_line(&format!("{} (Scheduled: 2026-03-05)", line), None).unwrap();
+        assert_eq!(parsed.title, "Fix login");
+        assert_eq!(parsed.tags, vec!["bug"]);
+        assert_eq!(parsed.priority, Some(Priority::High));
+        assert_eq!(parsed.fields, task.fields);
+    }
+
+    #[test]
+    fn test_roundtrip_timestamps() {
+        let created = Local.with_ymd_and_hms(2026, 1, 25, 14, 3, 0).unwrap();
+        let done = Local.with_ymd_and_hms(2026, 1, 27, 10, 15, 0).unwrap();
+        let task = Task::new("Ship")
+            .with_id(14)
+            .with_complete(true)
+            .with_created(Some(created))
+            .with_updated(Some(done))
+            .with_completed(Some(done));
+
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:14] - [x] Ship (Times: c20260125T1403 u20260127T1015 d20260127T1015)");
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.created, Some(created));
+        assert_eq!(parsed.updated, Some(done));
+        assert_eq!(parsed.completed, Some(done));
+
+        // An unchanged task only records its creation time
+        let fresh = Task::new("New").with_id(15).with_created(Some(created)).with_updated(Some(created));
+        let line = format_task_line(&fresh);
+        assert_eq!(line, "[ID:15] - [ ] New (Times: c20260125T1403)");
+        assert_eq!(parse_task_line(&line, None).unwrap().updated, Some(created));
+
+        // Legacy lines carry no timestamps
+        let legacy = parse_task_line("[ID:16] - [ ] Old", None).unwrap();
+        assert!(legacy.created.is_none() && legacy.updated.is_none());
+    }
+
+    #[test]
+    fn test_roundtrip_uid() {
+        let task = Task::new("Deploy").with_id(4).with_uid("k3x9a2mf");
+        let line = format_task_line(&task);
+        assert_eq!(line, "[ID:4] - [ ] Deploy (UID: k3x9a2mf)");
+        assert_eq!(parse_task_line(&line, None).unwrap().uid, "k3x9a2mf");
+    }
+
+    #[test]
+    fn test_roundtrip_statuses() {
+        for status in Status::ALL {
+            let line = format_task_line(&Task::new("Task").with_id(1).with_status(status));
+            assert_eq!(line, format!("[ID:1] - [{}] Task", status.marker()));
+            assert_eq!(parse_task_line(&line, None).unwrap().status, status);
+        }
+        assert_eq!(parse_task_line("[ID:1] - [X] Task", None).unwrap().status, Status::Done);
+    }
+
+    #[test]
+    fn test_roundtrip_title_like_field() {
+        let task = Task::new("Watch Star Trek:TNG")
+            .with_id(1)
+            .with_tags(vec!["tv".to_string()])
+            .with_fields([("ticket".to_string(), "JIRA:123".to_string())].into());
+        let line = format_task_line(&task);
+        assert!(line.starts_with("[ID:1] - [ ] Watch Star Trek\\:TNG #tv ticket:JIRA:123"));
+
+        let parsed = parse_task_line(&line, None).unwrap();
+        assert_eq!(parsed.title, "Watch Star Trek:TNG");
+        assert_eq!(parsed.tags, vec!["tv"]);
+        assert_eq!(parsed.fields, task.fields);
+    }
+}
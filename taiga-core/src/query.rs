@@ -0,0 +1,724 @@
+//! Boolean query language for filtering tasks
+//!
+//! A query combines conditions with `and`, `or`, `not` and parentheses:
+//!
+//! ```text
+//! status:open and (tag:work or tag:urgent) and not category:Personal and due<+3d
+//! ```
+//!
+//! `and` binds tighter than `or` and may be left out, so `tag:work due:today`
+//! needs both; `-tag:work` is short for `not tag:work`. Keywords and keys are
+//! case-insensitive, values with spaces are quoted: `due:"this week"`.
+//!
+//! Conditions:
+//! - `status:STATUS`: open, in-progress, waiting, done or cancelled
+//! - `is:STATE`: open (not done or cancelled), closed, done, overdue,
+//!   deferred, blocked, actionable or recurring
+//! - `tag:TAG`, `category:PATH` (including subcategories, `none` for
+//!   uncategorized tasks)
+//! - `priority:LEVEL` or `priority:none`; `<`, `<=`, `>` and `>=` compare
+//!   levels, so `priority<=high` is urgent or high
+//! - `due:RANGE` (or `scheduled`, `date`), `start`, `deadline`, `created`,
+//!   `updated` and `completed`: the day is within a date range such as
+//!   `today`, `+3d` or `"next week"`, or `none` for tasks without the date.
+//!   Comparisons are against the range: `due<+3d` is before the day in three
+//!   days, `created>="last week"` on or after its Monday
+//! - `has:KEY`: the task has a date, priority, category, tags, notes or a
+//!   custom field of that name
+//! - `title:TEXT`, `text:TEXT` (title, notes or checklist); a bare word is a
+//!   text search
+//! - `field:KEY`, `field:KEY=VALUE`, `field:KEY>VALUE`: a custom field,
+//!   compared numerically when both sides are numbers
+//!
+//! Tasks without a date or priority never match a comparison on it.
+
+use chrono::NaiveDate;
+use std::cmp::Ordering;
+
+use crate::category;
+use crate::date::{parse_date_range, DateRange};
+use crate::error::{CoreError, Result};
+use crate::field;
+use crate::task::{Priority, Status, Task};
+use crate::zone::Zone;
+
+/// Keys a condition may start with, for error messages
+const KEYS: &str = "status, is, tag, category, priority, due, scheduled, start, deadline, created, updated, completed, has, title, text or field";
+
+/// A task date a query can test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateField {
+    Scheduled,
+    Start,
+    Deadline,
+    Created,
+    Updated,
+    Completed,
+}
+
+impl DateField {
+    /// Look up a date by its query key
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "due" | "scheduled" | "date" => Some(Self::Scheduled),
+            "start" => Some(Self::Start),
+            "deadline" => Some(Self::Deadline),
+            "created" => Some(Self::Created),
+            "updated" => Some(Self::Updated),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+
+    /// Day of this date of a task in the installed [`Zone`]
+    fn of(self, task: &Task) -> Option<NaiveDate> {
+        let moment = match self {
+            DateField::Scheduled => task.scheduled,
+            DateField::Start => return task.start,
+            DateField::Deadline => task.deadline,
+            DateField::Created => task.created,
+            DateField::Updated => task.updated,
+            DateField::Completed => task.completed,
+        };
+        moment.map(|dt| Zone::current().date_of(&dt))
+    }
+}
+
+/// A parsed query, see the [module documentation](self) for the syntax
+#[derive(Debug, Clone)]
+pub struct Query {
+    root: Node,
+}
+
+impl Query {
+    /// Parse a query
+    ///
+    /// Errors name the column (counting from 1) where the problem is.
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(error(1, "the query is empty"));
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: input.chars().count() + 1,
+        };
+        let root = parser.parse_or()?;
+        // Parsing stops early only at a ')' without a '('
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(error(token.column, "')' without a matching '('"));
+        }
+        Ok(Self { root })
+    }
+
+    /// Check if a task matches the query
+    ///
+    /// Without the other tasks no task counts as blocked; see
+    /// [`Query::matches_with`].
+    pub fn matches(&self, task: &Task) -> bool {
+        self.matches_with(task, |_| false)
+    }
+
+    /// Check if a task matches the query, with `is_blocked` telling whether
+    /// a task (by ID) waits on an incomplete blocker
+    pub fn matches_with(&self, task: &Task, is_blocked: impl Fn(u32) -> bool) -> bool {
+        self.root.eval(task, &is_blocked)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    All(Vec<Node>),
+    Any(Vec<Node>),
+    Not(Box<Node>),
+    Condition(Condition),
+}
+
+impl Node {
+    fn eval(&self, task: &Task, is_blocked: &dyn Fn(u32) -> bool) -> bool {
+        match self {
+            Node::All(nodes) => nodes.iter().all(|node| node.eval(task, is_blocked)),
+            Node::Any(nodes) => nodes.iter().any(|node| node.eval(task, is_blocked)),
+            Node::Not(node) => !node.eval(task, is_blocked),
+            Node::Condition(condition) => condition.eval(task, is_blocked),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    Text(String),
+    /// Lowercase text in the title
+    Title(String),
+    Status(Status),
+    Is(State),
+    /// Lowercase tag
+    Tag(String),
+    /// Lowercase category path (None = uncategorized)
+    Category(Option<String>),
+    /// Priority compared to a level (None = no priority)
+    Priority(Comparison, Option<Priority>),
+    /// Date compared to a range (None = no date)
+    Date(DateField, Comparison, Option<DateRange>),
+    Has(Attribute),
+    /// Custom field, optionally compared to a value
+    Field(String, Option<(Comparison, String)>),
+}
+
+impl Condition {
+    fn eval(&self, task: &Task, is_blocked: &dyn Fn(u32) -> bool) -> bool {
+        match self {
+            Condition::Text(term) => task.matches_text(term),
+            Condition::Title(term) => task.title.to_lowercase().contains(term),
+            Condition::Status(status) => task.status == *status,
+            Condition::Is(state) => match state {
+                State::Open => !task.status.is_closed(),
+                State::Closed => task.status.is_closed(),
+                State::Done => task.status == Status::Done,
+                State::Overdue => task.is_overdue(),
+                State::Deferred => task.is_deferred(),
+                State::Blocked => is_blocked(task.id),
+                State::Actionable => !is_blocked(task.id),
+                State::Recurring => task.recurrence.is_some(),
+            },
+            Condition::Tag(tag) => task.tags.iter().any(|t| t.to_lowercase() == *tag),
+            Condition::Category(None) => task.category.is_none(),
+            Condition::Category(Some(ancestor)) => task
+                .category
+                .as_deref()
+                .is_some_and(|path| category::is_within(&path.to_lowercase(), ancestor)),
+            Condition::Priority(comparison, expected) => match (task.priority, expected) {
+                (actual, None) => actual.is_none(),
+                (Some(actual), Some(expected)) => comparison.accepts(actual.cmp(expected)),
+                (None, Some(_)) => false,
+            },
+            Condition::Date(field, comparison, range) => match (field.of(task), range) {
+                (day, None) => day.is_none(),
+                (Some(day), Some(range)) => match comparison {
+                    Comparison::Equal => range.contains(day),
+                    Comparison::Less => range.start.is_some_and(|start| day < start),
+                    Comparison::LessOrEqual => range.end.is_some_and(|end| day <= end),
+                    Comparison::Greater => range.end.is_some_and(|end| day > end),
+                    Comparison::GreaterOrEqual => range.start.is_some_and(|start| day >= start),
+                },
+                (None, Some(_)) => false,
+            },
+            Condition::Has(attribute) => match attribute {
+                Attribute::Date(field) => field.of(task).is_some(),
+                Attribute::Priority => task.priority.is_some(),
+                Attribute::Category => task.category.is_some(),
+                Attribute::Tags => !task.tags.is_empty(),
+                Attribute::Notes => task.notes.is_some(),
+                Attribute::Field(key) => task.field(key).is_some(),
+            },
+            Condition::Field(key, test) => match (task.field(key), test) {
+                (Some(actual), Some((comparison, expected))) => {
+                    comparison.accepts(field::compare_values(actual, expected))
+                }
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Equal => ":",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        }
+    }
+
+    /// Check if the ordering of a task's value against the expected one satisfies this
+    fn accepts(self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+/// Value of an `is:` condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Open,
+    Closed,
+    Done,
+    Overdue,
+    Deferred,
+    Blocked,
+    Actionable,
+    Recurring,
+}
+
+impl State {
+    fn parse(input: &str) -> Option<Self> {
+        match input {
+            "open" => Some(State::Open),
+            "closed" => Some(State::Closed),
+            "done" | "complete" | "completed" => Some(State::Done),
+            "overdue" => Some(State::Overdue),
+            "deferred" => Some(State::Deferred),
+            "blocked" => Some(State::Blocked),
+            "actionable" => Some(State::Actionable),
+            "recurring" => Some(State::Recurring),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a `has:` condition
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Attribute {
+    Date(DateField),
+    Priority,
+    Category,
+    Tags,
+    Notes,
+    Field(String),
+}
+
+/// A word or parenthesis of a query, with the column it starts at
+#[derive(Debug, Clone)]
+struct Token {
+    /// Text as written, quotes included
+    text: String,
+    column: usize,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+/// Split a query into words and parentheses, keeping quoted text together
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut start = 0;
+    let mut open_quote = None;
+
+    for (index, c) in input.chars().enumerate() {
+        let column = index + 1;
+        if open_quote.is_some() {
+            if c == '"' {
+                open_quote = None;
+            }
+            word.push(c);
+            continue;
+        }
+        match c {
+            '(' | ')' => {
+                push_word(&mut tokens, &mut word, start);
+                tokens.push(Token { text: c.to_string(), column });
+            }
+            c if c.is_whitespace() => push_word(&mut tokens, &mut word, start),
+            c => {
+                if word.is_empty() {
+                    start = column;
+                }
+                if c == '"' {
+                    open_quote = Some(column);
+                }
+                word.push(c);
+            }
+        }
+    }
+
+    if let Some(column) = open_quote {
+        return Err(error(column, "'\"' is never closed"));
+    }
+    push_word(&mut tokens, &mut word, start);
+    Ok(tokens)
+}
+
+fn push_word(tokens: &mut Vec<Token>, word: &mut String, column: usize) {
+    if !word.is_empty() {
+        tokens.push(Token { text: std::mem::take(word), column });
+    }
+}
+
+/// Recursive descent over the tokens: `or` of `and` of `not` of conditions
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Column just past the input, for errors at its end
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_and()?];
+        while self.peek().is_some_and(|token| token.is_keyword("or")) {
+            self.pos += 1;
+            nodes.push(self.parse_and()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::Any(nodes) })
+    }
+
+    /// Conditions joined by `and`, written or implied
+    fn parse_and(&mut self) -> Result<Node> {
+        let mut nodes = vec![self.parse_not()?];
+        loop {
+            match self.peek() {
+                None => break,
+                Some(token) if token.text == ")" || token.is_keyword("or") => break,
+                Some(token) if token.is_keyword("and") => self.pos += 1,
+                Some(_) => {}
+            }
+            nodes.push(self.parse_not()?);
+        }
+        Ok(if nodes.len() == 1 { nodes.remove(0) } else { Node::All(nodes) })
+    }
+
+    fn parse_not(&mut self) -> Result<Node> {
+        let Some(token) = self.peek() else {
+            return self.parse_primary();
+        };
+        if token.is_keyword("not") || token.text == "-" {
+            self.pos += 1;
+            return Ok(Node::Not(Box::new(self.parse_not()?)));
+        }
+        if let Some(rest) = token.text.strip_prefix('-') {
+            let condition = parse_condition(rest, token.column + 1)?;
+            self.pos += 1;
+            return Ok(Node::Not(Box::new(Node::Condition(condition))));
+        }
+        self.parse_primary()
+    }
+
+    /// A condition or a query in parentheses
+    fn parse_primary(&mut self) -> Result<Node> {
+        let Some(token) = self.peek().cloned() else {
+            let last = &self.tokens[self.pos - 1];
+            return Err(error(self.end, format!("expected a condition after '{}'", last.text)));
+        };
+        self.pos += 1;
+
+        if token.text == "(" {
+            let node = self.parse_or()?;
+            return match self.peek() {
+                Some(close) if close.text == ")" => {
+                    self.pos += 1;
+                    Ok(node)
+                }
+                _ => Err(error(token.column, "'(' is never closed")),
+            };
+        }
+        if token.text == ")" || token.is_keyword("and") || token.is_keyword("or") {
+            return Err(error(token.column, format!("expected a condition, found '{}'", token.text)));
+        }
+        parse_condition(&token.text, token.column).map(Node::Condition)
+    }
+}
+
+/// Parse one condition word starting at `column`
+fn parse_condition(text: &str, column: usize) -> Result<Condition> {
+    let Some((key, comparison, raw_value)) = split_condition(text) else {
+        return Ok(Condition::Text(unquote(text)));
+    };
+    let key = key.to_lowercase();
+    let operator_column = column + key.chars().count();
+    let value_column = operator_column + comparison.symbol().len();
+    let value = unquote(raw_value);
+    let lower = value.to_lowercase();
+    if value.trim().is_empty() {
+        return Err(error(value_column, format!("'{}' needs a value", key)));
+    }
+
+    let require_equal = || {
+        if comparison == Comparison::Equal {
+            Ok(())
+        } else {
+            Err(error(
+                operator_column,
+                format!(
+                    "'{}' cannot be compared with '{}', only dates, priority and fields can",
+                    key,
+                    comparison.symbol()
+                ),
+            ))
+        }
+    };
+
+    match key.as_str() {
+        "status" => {
+            require_equal()?;
+            Status::parse(&value).map(Condition::Status).map_err(|e| invalid(value_column, e))
+        }
+        "is" => {
+            require_equal()?;
+            State::parse(&lower).map(Condition::Is).ok_or_else(|| {
+                error(
+                    value_column,
+                    format!(
+                        "'{}' is not a state (use open, closed, done, overdue, deferred, blocked, actionable or recurring)",
+                        value
+                    ),
+                )
+            })
+        }
+        "tag" => {
+            require_equal()?;
+            Ok(Condition::Tag(lower.trim_start_matches('#').to_string()))
+        }
+        "category" => {
+            require_equal()?;
+            Ok(Condition::Category(match lower.as_str() {
+                "none" => None,
+                path => category::normalize(path),
+            }))
+        }
+        "priority" => {
+            if lower == "none" {
+                require_equal()?;
+                return Ok(Condition::Priority(comparison, None));
+            }
+            Priority::parse(&value)
+                .map(|priority| Condition::Priority(comparison, Some(priority)))
+                .map_err(|e| invalid(value_column, e))
+        }
+        "has" => {
+            require_equal()?;
+            parse_attribute(&lower).map(Condition::Has).ok_or_else(|| {
+                error(
+                    value_column,
+                    format!("'{}' is not a date, priority, category, tags, notes or a field name", value),
+                )
+            })
+        }
+        "title" => {
+            require_equal()?;
+            Ok(Condition::Title(lower))
+        }
+        "text" => {
+            require_equal()?;
+            Ok(Condition::Text(value))
+        }
+        "field" => {
+            require_equal()?;
+            parse_field_condition(raw_value, value_column)
+        }
+        key => {
+            let Some(field) = DateField::from_key(key) else {
+                return Err(error(column, format!("unknown key '{}' (use {})", key, KEYS)));
+            };
+            if lower == "none" {
+                require_equal()?;
+                return Ok(Condition::Date(field, comparison, None));
+            }
+            let range = parse_date_range(&value).map_err(|_| {
+                error(
+                    value_column,
+                    format!(
+                        "'{}' is not a date or date range (e.g. today, +3d, 2026-11-01 or \"this week\")",
+                        value
+                    ),
+                )
+            })?;
+            Ok(Condition::Date(field, comparison, Some(range)))
+        }
+    }
+}
+
+/// Parse the `KEY`, `KEY=VALUE` or `KEY<VALUE` after `field:`
+fn parse_field_condition(text: &str, column: usize) -> Result<Condition> {
+    let (key, test) = match split_condition(text) {
+        Some((key, comparison, value)) => (key, Some((comparison, unquote(value)))),
+        None => (text, None),
+    };
+    let key = field::normalize_key(key).map_err(|e| invalid(column, e))?;
+    if test.as_ref().is_some_and(|(_, value)| value.trim().is_empty()) {
+        return Err(error(column, format!("field '{}' needs a value to compare with", key)));
+    }
+    Ok(Condition::Field(key, test))
+}
+
+fn parse_attribute(input: &str) -> Option<Attribute> {
+    match input {
+        "priority" => Some(Attribute::Priority),
+        "category" => Some(Attribute::Category),
+        "tags" | "tag" => Some(Attribute::Tags),
+        "notes" => Some(Attribute::Notes),
+        key => DateField::from_key(key)
+            .map(Attribute::Date)
+            .or_else(|| field::normalize_key(key).ok().map(Attribute::Field)),
+    }
+}
+
+/// Split `key:value`, `key<value` and the like at the first operator
+/// outside quotes; None when the text is not a condition with a key
+fn split_condition(text: &str) -> Option<(&str, Comparison, &str)> {
+    let mut quoted = false;
+    for (index, c) in text.char_indices() {
+        let comparison = match c {
+            '"' => {
+                quoted = !quoted;
+                continue;
+            }
+            _ if quoted => continue,
+            ':' | '=' => Comparison::Equal,
+            '<' if text[index + 1..].starts_with('=') => Comparison::LessOrEqual,
+            '<' => Comparison::Less,
+            '>' if text[index + 1..].starts_with('=') => Comparison::GreaterOrEqual,
+            '>' => Comparison::Greater,
+            _ => continue,
+        };
+        let key = &text[..index];
+        // Text such as "10:30" or "\"a:b\"" is searched for, not a condition
+        let is_key = key.starts_with(|c: char| c.is_ascii_alphabetic())
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        return is_key.then(|| (key, comparison, &text[index + comparison.symbol().len()..]));
+    }
+    None
+}
+
+fn unquote(text: &str) -> String {
+    text.replace('"', "")
+}
+
+fn error(column: usize, message: impl std::fmt::Display) -> CoreError {
+    CoreError::parse(format!("Invalid query at column {}: {}", column, message))
+}
+
+/// Report a value error at a column, without its kind prefix
+fn invalid(column: usize, err: CoreError) -> CoreError {
+    let message = match err {
+        CoreError::Parse { message, .. } | CoreError::Validation { message, .. } => message,
+        other => other.to_string(),
+    };
+    error(column, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TaskCollection;
+    use chrono::{Duration, Local};
+
+    fn sample() -> TaskCollection {
+        let in_days = |days| Some(Local::now() + Duration::days(days));
+        let mut collection = TaskCollection::new();
+        collection.insert(
+            Task::new("Write report")
+                .with_id(1)
+                .with_tags(vec!["work".to_string()])
+                .with_category(Some("Work/ClientA".to_string()))
+                .with_scheduled(in_days(1))
+                .with_priority(Some(Priority::High)),
+        );
+        collection.insert(
+            Task::new("Fix outage")
+                .with_id(2)
+                .with_tags(vec!["Urgent".to_string()])
+                .with_category(Some("Work".to_string()))
+                .with_scheduled(in_days(10))
+                .with_priority(Some(Priority::Urgent)),
+        );
+        collection.insert(
+            Task::new("Buy milk")
+                .with_id(3)
+                .with_tags(vec!["urgent".to_string()])
+                .with_category(Some("Personal".to_string()))
+                .with_scheduled(in_days(0)),
+        );
+        collection.insert(Task::new("Old idea").with_id(4).with_status(Status::Done));
+        collection
+    }
+
+    fn ids(collection: &TaskCollection, query: &str) -> Vec<u32> {
+        let query = Query::parse(query).unwrap();
+        let mut ids: Vec<u32> = collection
+            .tasks
+            .values()
+            .filter(|task| query.matches_with(task, |id| collection.is_blocked(id)))
+            .map(|task| task.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_query_conditions() {
+        let collection = sample();
+        assert_eq!(
+            ids(&collection, "status:open and (tag:work or tag:urgent) and not category:Personal and due<+3d"),
+            vec![1]
+        );
+        assert_eq!(ids(&collection, "tag:URGENT"), vec![2, 3]);
+        assert_eq!(ids(&collection, "category:work"), vec![1, 2]);
+        assert_eq!(ids(&collection, "category:none"), vec![4]);
+        assert_eq!(ids(&collection, "priority<=high"), vec![1, 2]);
+        assert_eq!(ids(&collection, "priority:none"), vec![3, 4]);
+        assert_eq!(ids(&collection, "due:none"), vec![4]);
+        assert_eq!(ids(&collection, "due>=tomorrow"), vec![1, 2]);
+        assert_eq!(ids(&collection, "due:\"next 7 days\""), vec![1, 3]);
+        assert_eq!(ids(&collection, "is:closed"), vec![4]);
+        assert_eq!(ids(&collection, "milk"), vec![3]);
+        assert_eq!(ids(&collection, "title:\"old idea\""), vec![4]);
+        assert_eq!(ids(&collection, "has:priority -tag:work"), vec![2]);
+    }
+
+    #[test]
+    fn test_query_precedence() {
+        let collection = sample();
+        // "and" binds tighter than "or", juxtaposition means "and"
+        assert_eq!(ids(&collection, "tag:work or tag:urgent category:personal"), vec![1, 3]);
+        assert_eq!(ids(&collection, "(tag:work or tag:urgent) category:work"), vec![1, 2]);
+        assert_eq!(ids(&collection, "not not is:done"), vec![4]);
+        assert_eq!(ids(&collection, "-(tag:work OR is:done)"), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_query_fields_and_blocked() {
+        let mut collection = sample();
+        collection.get_mut(1).unwrap().set_field("estimate", Some("10")).unwrap();
+        collection.get_mut(2).unwrap().set_field("estimate", Some("9")).unwrap();
+        collection.add_dependency(2, 3).unwrap();
+
+        assert_eq!(ids(&collection, "field:estimate"), vec![1, 2]);
+        assert_eq!(ids(&collection, "field:estimate>9"), vec![1]);
+        assert_eq!(ids(&collection, "field:Estimate=9"), vec![2]);
+        assert_eq!(ids(&collection, "is:blocked"), vec![2]);
+        assert_eq!(ids(&collection, "is:actionable is:open"), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_query_errors() {
+        let message = |query: &str| Query::parse(query).unwrap_err().to_string();
+        assert!(message("").contains("empty"));
+        assert!(message("(tag:work or tag:home").contains("column 1: '(' is never closed"));
+        assert!(message("tag:work)").contains("column 9: ')' without a matching '('"));
+        assert!(message("tag:work and").contains("column 13: expected a condition after 'and'"));
+        assert!(message("or tag:work").contains("column 1: expected a condition, found 'or'"));
+        assert!(message("tag:\"x").contains("column 5: '\"' is never closed"));
+        assert!(message("colour:red").contains("column 1: unknown key 'colour'"));
+        assert!(message("status:later").contains("column 8: 'later' is not a status"));
+        assert!(message("due<someday").contains("column 5: 'someday' is not a date"));
+        assert!(message("tag<work").contains("column 4: 'tag' cannot be compared with '<'"));
+        assert!(message("due>=none").contains("column 4:"));
+        assert!(message("priority:").contains("'priority' needs a value"));
+    }
+}
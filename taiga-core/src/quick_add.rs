@@ -0,0 +1,180 @@
+//! Quick-add syntax
+//!
+//! Reads task details from a single line of free text, the way a task is
+//! typed into a quick-add box:
+//!
+//! ```text
+//! Pay rent tomorrow #bills @Home !1
+//! ```
+//!
+//! - `#tag` adds a tag
+//! - `@Category` sets the category (nested with slashes: `@Work/ClientA`)
+//! - `!1` to `!4` or `!urgent`, `!high`, ... sets the priority
+//! - a date, optionally with a time, at the end or the start of the text
+//!   sets the schedule; anything [`crate::date::parse_datetime`] accepts
+//!   works ("tomorrow", "next friday 3pm", "in 3 days"), and a leading
+//!   "on", "at" or "by" goes with it
+//!
+//! Dates in the middle of the text are left alone, so "Review the friday
+//! report" stays as typed. Words that cannot be read keep their place in the
+//! title.
+
+use chrono::{DateTime, Local};
+
+use crate::category;
+use crate::date::parse_datetime;
+use crate::task::{Priority, Task};
+
+/// Longest run of words tried as a date ("first monday of march 2027 9am")
+const MAX_DATE_WORDS: usize = 7;
+
+/// Words that may introduce a trailing date: "Call mom on friday"
+const DATE_CONNECTORS: [&str; 3] = ["on", "at", "by"];
+
+/// Task details read from a quick-add line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuickAdd {
+    /// The text with the recognised parts removed
+    pub title: String,
+    pub scheduled: Option<DateTime<Local>>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+}
+
+impl QuickAdd {
+    /// Parse a quick-add line
+    ///
+    /// Never fails: anything that is not recognised stays in the title. A
+    /// date is not taken when it is all there is, so "tomorrow" alone is a
+    /// title.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, true)
+    }
+
+    /// Parse a quick-add line whose date is given separately, leaving date
+    /// words in the title
+    pub fn parse_undated(input: &str) -> Self {
+        Self::parse_with(input, false)
+    }
+
+    fn parse_with(input: &str, read_date: bool) -> Self {
+        let mut quick = Self::default();
+        let mut words = Vec::new();
+
+        for word in input.split_whitespace() {
+            if let Some(tag) = word.strip_prefix('#')
+                && !tag.is_empty()
+                && tag.chars().all(|c| c.is_alphanumeric() || c == '_')
+            {
+                if !quick.tags.iter().any(|t| t == tag) {
+                    quick.tags.push(tag.to_string());
+                }
+            } else if let Some(path) = word.strip_prefix('@').and_then(category::normalize) {
+                quick.category = Some(path);
+            } else if let Some(priority) = word.strip_prefix('!').and_then(|p| Priority::parse(p).ok()) {
+                quick.priority = Some(priority);
+            } else {
+                words.push(word);
+            }
+        }
+
+        if read_date {
+            quick.scheduled = take_trailing_date(&mut words).or_else(|| take_leading_date(&mut words));
+        }
+        quick.title = words.join(" ");
+        quick
+    }
+
+    /// Create a task with the parsed details
+    pub fn into_task(self) -> Task {
+        Task::new(self.title)
+            .with_scheduled(self.scheduled)
+            .with_category(self.category)
+            .with_tags(self.tags)
+            .with_priority(self.priority)
+    }
+}
+
+/// Remove the longest date at the end of the words, with a word such as
+/// "on" before it, keeping at least one word
+fn take_trailing_date(words: &mut Vec<&str>) -> Option<DateTime<Local>> {
+    let longest = MAX_DATE_WORDS.min(words.len().saturating_sub(1));
+    let (count, date) = (1..=longest).rev().find_map(|count| {
+        let start = words.len() - count;
+        parse_datetime(&words[start..].join(" ")).ok().map(|date| (count, date))
+    })?;
+
+    words.truncate(words.len() - count);
+    if words.len() > 1
+        && words
+            .last()
+            .is_some_and(|word| DATE_CONNECTORS.contains(&word.to_lowercase().as_str()))
+    {
+        words.pop();
+    }
+    Some(date)
+}
+
+/// Remove the longest date at the start of the words, keeping at least one word
+fn take_leading_date(words: &mut Vec<&str>) -> Option<DateTime<Local>> {
+    let longest = MAX_DATE_WORDS.min(words.len().saturating_sub(1));
+    let (count, date) = (1..=longest)
+        .rev()
+        .find_map(|count| parse_datetime(&words[..count].join(" ")).ok().map(|date| (count, date)))?;
+
+    words.drain(..count);
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Duration, NaiveTime, Weekday};
+
+    #[test]
+    fn test_quick_add() {
+        let tomorrow = Local::now().date_naive() + Duration::days(1);
+
+        let quick = QuickAdd::parse("Pay rent tomorrow #bills @Home !1");
+        assert_eq!(quick.title, "Pay rent");
+        assert_eq!(quick.scheduled.map(|dt| dt.date_naive()), Some(tomorrow));
+        assert_eq!(quick.tags, vec!["bills"]);
+        assert_eq!(quick.category.as_deref(), Some("Home"));
+        assert_eq!(quick.priority, Some(Priority::Urgent));
+
+        let quick = QuickAdd::parse("Call the bank on friday at 3pm @Work/Admin !high");
+        assert_eq!(quick.title, "Call the bank");
+        assert_eq!(quick.scheduled.map(|dt| dt.time()), NaiveTime::from_hms_opt(15, 0, 0));
+        assert_eq!(quick.category.as_deref(), Some("Work/Admin"));
+        assert_eq!(quick.priority, Some(Priority::High));
+
+        let quick = QuickAdd::parse("Plan the offsite sat noon");
+        assert_eq!(quick.title, "Plan the offsite");
+        assert_eq!(quick.scheduled.map(|dt| dt.weekday()), Some(Weekday::Sat));
+        assert_eq!(quick.scheduled.map(|dt| dt.time()), NaiveTime::from_hms_opt(12, 0, 0));
+
+        let quick = QuickAdd::parse("in 3 days renew passport");
+        assert_eq!(quick.title, "renew passport");
+        assert!(quick.scheduled.is_some());
+    }
+
+    #[test]
+    fn test_quick_add_leaves_plain_text() {
+        for text in ["Review the friday report", "Buy 2 eggs", "tomorrow", "Fix issue#4 at 100% !!"] {
+            let quick = QuickAdd::parse(text);
+            assert_eq!(quick.title, text);
+            assert_eq!(quick, QuickAdd { title: text.to_string(), ..QuickAdd::default() });
+        }
+
+        let quick = QuickAdd::parse_undated("Prepare demo friday #work");
+        assert_eq!(quick.title, "Prepare demo friday");
+        assert_eq!(quick.tags, vec!["work"]);
+        assert_eq!(quick.scheduled, None);
+
+        let task = QuickAdd::parse("Water plants #home !4").into_task();
+        assert_eq!(task.title, "Water plants");
+        assert_eq!(task.tags, vec!["home"]);
+        assert_eq!(task.priority, Some(Priority::Low));
+    }
+}
@@ -0,0 +1,464 @@
+//! Recurrence rules for repeating tasks
+//!
+//! Supports rules such as:
+//! - Shorthands: "daily", "weekly", "monthly", "yearly", "weekdays"
+//! - Intervals: "every 3 days", "every 2 weeks", "every other month"
+//! - Weekdays: "every monday", "every mon, wed and fri", "every 2 weeks on tue"
+//! - Weekday of the month: "every 2nd tuesday", "every last friday"
+//!
+//! Adding "from completion" advances from the day the task was completed
+//! instead of from its scheduled date.
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CoreError, Result};
+
+/// Unit a recurrence repeats in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn unit(self) -> &'static str {
+        match self {
+            Frequency::Daily => "day",
+            Frequency::Weekly => "week",
+            Frequency::Monthly => "month",
+            Frequency::Yearly => "year",
+        }
+    }
+
+    fn adverb(self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+            Frequency::Yearly => "yearly",
+        }
+    }
+}
+
+/// A rule describing when a task repeats
+///
+/// Persisted as its display form, e.g. `(Repeat: every 2 weeks on mon, fri)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    /// Repeat every N units (at least 1)
+    pub interval: u32,
+    /// Days of the week for weekly rules (empty = same weekday as before)
+    pub weekdays: Vec<Weekday>,
+    /// Weekday of the month for monthly rules, e.g. (2, Tue); -1 means the last one
+    pub nth_weekday: Option<(i8, Weekday)>,
+    /// Advance from the completion date instead of the scheduled date
+    pub from_completion: bool,
+}
+
+impl Recurrence {
+    /// Create a rule repeating every `interval` units
+    pub fn new(frequency: Frequency, interval: u32) -> Self {
+        Self {
+            frequency,
+            interval: interval.max(1),
+            weekdays: Vec::new(),
+            nth_weekday: None,
+            from_completion: false,
+        }
+    }
+
+    /// Builder method to repeat on specific weekdays (weekly rules)
+    pub fn with_weekdays(mut self, mut weekdays: Vec<Weekday>) -> Self {
+        weekdays.sort_by_key(|d| d.num_days_from_monday());
+        weekdays.dedup();
+        self.weekdays = weekdays;
+        self
+    }
+
+    /// Builder method to repeat on the nth weekday of the month (monthly rules)
+    pub fn with_nth_weekday(mut self, nth: i8, weekday: Weekday) -> Self {
+        self.nth_weekday = Some((nth, weekday));
+        self
+    }
+
+    /// Builder method to advance from the completion date
+    pub fn with_from_completion(mut self, from_completion: bool) -> Self {
+        self.from_completion = from_completion;
+        self
+    }
+
+    /// Parse a recurrence rule from user input
+    pub fn parse(input: &str) -> Result<Self> {
+        let normalized = input.trim().to_lowercase().replace(',', " , ");
+        let mut words: Vec<&str> = normalized.split_whitespace().collect();
+
+        let from_completion = matches!(
+            words.as_slice(),
+            [.., "from" | "after", "completion" | "done"]
+        );
+        if from_completion {
+            words.truncate(words.len() - 2);
+        }
+
+        parse_rule(&words)
+            .map(|rule| rule.with_from_completion(from_completion))
+            .ok_or_else(|| {
+                CoreError::parse(format!(
+                    "Could not parse repeat rule '{}'. Try formats like: 'daily', 'every 2 weeks', 'every mon, fri', 'every 2nd tuesday', 'monthly from completion'",
+                    input.trim()
+                ))
+            })
+    }
+
+    /// Get the next occurrence strictly after `date`
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match self.frequency {
+            Frequency::Daily => date.checked_add_days(Days::new(self.interval as u64)),
+            Frequency::Weekly if self.weekdays.is_empty() => {
+                date.checked_add_days(Days::new(7 * self.interval as u64))
+            }
+            Frequency::Weekly => {
+                let week_start =
+                    date.checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))?;
+
+                // A later matching day in the same week comes first
+                let mut rest_of_week = (1..7 - date.weekday().num_days_from_monday() as u64)
+                    .filter_map(|offset| date.checked_add_days(Days::new(offset)));
+                if let Some(day) = rest_of_week.find(|d| self.weekdays.contains(&d.weekday())) {
+                    return Some(day);
+                }
+
+                let next_week = week_start.checked_add_days(Days::new(7 * self.interval as u64))?;
+                (0..7)
+                    .filter_map(|offset| next_week.checked_add_days(Days::new(offset)))
+                    .find(|d| self.weekdays.contains(&d.weekday()))
+            }
+            Frequency::Monthly => match self.nth_weekday {
+                Some((nth, weekday)) => {
+                    let mut month = date.with_day(1)?;
+                    // Some months have no 5th weekday; give up after a few years
+                    for _ in 0..60 {
+                        if let Some(day) = nth_weekday_of_month(month, nth, weekday)
+                            && day > date
+                        {
+                            return Some(day);
+                        }
+                        month = month.checked_add_months(Months::new(self.interval))?;
+                    }
+                    None
+                }
+                None => date.checked_add_months(Months::new(self.interval)),
+            },
+            Frequency::Yearly => date.checked_add_months(Months::new(12 * self.interval)),
+        }
+    }
+
+    /// Get the first occurrence on or after `date`
+    ///
+    /// Rules tied to specific weekdays snap forward to the next matching day;
+    /// other rules start on `date` itself.
+    pub fn first_on_or_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        if self.weekdays.is_empty() && self.nth_weekday.is_none() {
+            return Some(date);
+        }
+        let day_before = date.pred_opt()?;
+        let first_rule = Recurrence {
+            interval: 1,
+            ..self.clone()
+        };
+        first_rule.next_after(day_before)
+    }
+}
+
+impl std::fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let every = if self.interval == 1 {
+            None
+        } else {
+            Some(format!("every {} {}s", self.interval, self.frequency.unit()))
+        };
+
+        match (&self.nth_weekday, self.weekdays.is_empty()) {
+            (Some((nth, weekday)), _) => match every {
+                Some(every) => write!(f, "{} on the {} {}", every, ordinal(*nth), weekday_name(*weekday))?,
+                None => write!(f, "every {} {}", ordinal(*nth), weekday_name(*weekday))?,
+            },
+            (None, false) => {
+                let days: Vec<&str> = self.weekdays.iter().map(|d| weekday_name(*d)).collect();
+                match every {
+                    Some(every) => write!(f, "{} on {}", every, days.join(", "))?,
+                    None => write!(f, "every {}", days.join(", "))?,
+                }
+            }
+            (None, true) => match every {
+                Some(every) => write!(f, "{}", every)?,
+                None => write!(f, "{}", self.frequency.adverb())?,
+            },
+        }
+
+        if self.from_completion {
+            write!(f, " from completion")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a rule without the "from completion" suffix
+fn parse_rule(words: &[&str]) -> Option<Recurrence> {
+    match words {
+        ["daily"] => return Some(Recurrence::new(Frequency::Daily, 1)),
+        ["weekly"] => return Some(Recurrence::new(Frequency::Weekly, 1)),
+        ["monthly"] => return Some(Recurrence::new(Frequency::Monthly, 1)),
+        ["yearly" | "annually"] => return Some(Recurrence::new(Frequency::Yearly, 1)),
+        ["weekdays"] => return parse_rule(&["every", "weekday"]),
+        _ => {}
+    }
+
+    let rest = words.strip_prefix(&["every"])?;
+
+    // "every 2nd tuesday", "every last friday of the month"
+    if let [nth, day, tail @ ..] = rest
+        && let (Some(nth), Some(weekday)) = (parse_ordinal(nth), parse_weekday(day))
+        && matches!(tail, [] | ["of", "the", "month"] | ["of", "month"])
+    {
+        return Some(Recurrence::new(Frequency::Monthly, 1).with_nth_weekday(nth, weekday));
+    }
+
+    // "every mon, wed and fri", "every weekday"
+    if let Some(weekdays) = parse_weekday_list(rest) {
+        return Some(Recurrence::new(Frequency::Weekly, 1).with_weekdays(weekdays));
+    }
+
+    // "every 3 days", "every other week", "every month", optionally "on ..."
+    let (interval, rest) = match rest {
+        ["other", rest @ ..] => (2, rest),
+        [n, rest @ ..] if n.parse::<u32>().is_ok_and(|n| n > 0) => (n.parse().ok()?, rest),
+        _ => (1, rest),
+    };
+    let (unit, on) = rest.split_first()?;
+    let frequency = match unit.trim_end_matches('s') {
+        "day" => Frequency::Daily,
+        "week" => Frequency::Weekly,
+        "month" => Frequency::Monthly,
+        "year" => Frequency::Yearly,
+        _ => return None,
+    };
+    if interval == 1 && unit.ends_with('s') {
+        return None;
+    }
+
+    let rule = Recurrence::new(frequency, interval);
+    match (frequency, on) {
+        (_, []) => Some(rule),
+        (Frequency::Weekly, ["on", days @ ..]) => Some(rule.with_weekdays(parse_weekday_list(days)?)),
+        (Frequency::Monthly, ["on", "the", nth, day] | ["on", nth, day]) => {
+            Some(rule.with_nth_weekday(parse_ordinal(nth)?, parse_weekday(day)?))
+        }
+        _ => None,
+    }
+}
+
+/// Parse "mon , wed and fri", "weekday" or "weekend"
+fn parse_weekday_list(words: &[&str]) -> Option<Vec<Weekday>> {
+    match words {
+        ["weekday" | "weekdays"] => {
+            return Some(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]);
+        }
+        ["weekend" | "weekends"] => return Some(vec![Weekday::Sat, Weekday::Sun]),
+        _ => {}
+    }
+
+    let days: Vec<Weekday> = words
+        .iter()
+        .filter(|w| !matches!(**w, "," | "and"))
+        .map(|w| parse_weekday(w))
+        .collect::<Option<_>>()?;
+
+    if days.is_empty() { None } else { Some(days) }
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input.trim_end_matches('s') {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thur" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_ordinal(input: &str) -> Option<i8> {
+    match input {
+        "1st" | "first" => Some(1),
+        "2nd" | "second" => Some(2),
+        "3rd" | "third" => Some(3),
+        "4th" | "fourth" => Some(4),
+        "5th" | "fifth" => Some(5),
+        "last" => Some(-1),
+        _ => None,
+    }
+}
+
+fn ordinal(nth: i8) -> &'static str {
+    match nth {
+        1 => "1st",
+        2 => "2nd",
+        3 => "3rd",
+        4 => "4th",
+        5 => "5th",
+        _ => "last",
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "mon",
+        Weekday::Tue => "tue",
+        Weekday::Wed => "wed",
+        Weekday::Thu => "thu",
+        Weekday::Fri => "fri",
+        Weekday::Sat => "sat",
+        Weekday::Sun => "sun",
+    }
+}
+
+/// Find the nth (or last, for -1) weekday in the month starting at `month`
+fn nth_weekday_of_month(month: NaiveDate, nth: i8, weekday: Weekday) -> Option<NaiveDate> {
+    if nth > 0 {
+        return NaiveDate::from_weekday_of_month_opt(month.year(), month.month(), weekday, nth as u8);
+    }
+
+    let last_day = month.checked_add_months(Months::new(1))?.pred_opt()?;
+    let back = (7 + last_day.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    last_day.checked_sub_days(Days::new(back as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_parse_shorthands() {
+        assert_eq!(Recurrence::parse("daily").unwrap(), Recurrence::new(Frequency::Daily, 1));
+        assert_eq!(Recurrence::parse("Weekly").unwrap(), Recurrence::new(Frequency::Weekly, 1));
+        assert_eq!(Recurrence::parse("every month").unwrap(), Recurrence::new(Frequency::Monthly, 1));
+        assert_eq!(Recurrence::parse("every 3 days").unwrap(), Recurrence::new(Frequency::Daily, 3));
+        assert_eq!(Recurrence::parse("every other year").unwrap(), Recurrence::new(Frequency::Yearly, 2));
+    }
+
+    #[test]
+    fn test_parse_weekdays() {
+        let rule = Recurrence::parse("every mon, wed and Friday").unwrap();
+        assert_eq!(rule.frequency, Frequency::Weekly);
+        assert_eq!(rule.weekdays, vec![Weekday::Mon, Weekday::Wed, Weekday::Fri]);
+
+        let rule = Recurrence::parse("every 2 weeks on tue").unwrap();
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.weekdays, vec![Weekday::Tue]);
+
+        assert_eq!(Recurrence::parse("weekdays").unwrap().weekdays.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_nth_weekday() {
+        let rule = Recurrence::parse("every 2nd tuesday").unwrap();
+        assert_eq!(rule.frequency, Frequency::Monthly);
+        assert_eq!(rule.nth_weekday, Some((2, Weekday::Tue)));
+
+        let rule = Recurrence::parse("every last friday of the month").unwrap();
+        assert_eq!(rule.nth_weekday, Some((-1, Weekday::Fri)));
+    }
+
+    #[test]
+    fn test_parse_from_completion() {
+        let rule = Recurrence::parse("every 2 weeks from completion").unwrap();
+        assert!(rule.from_completion);
+        assert_eq!(rule.interval, 2);
+        assert!(!Recurrence::parse("weekly").unwrap().from_completion);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Recurrence::parse("").is_err());
+        assert!(Recurrence::parse("every").is_err());
+        assert!(Recurrence::parse("every 0 days").is_err());
+        assert!(Recurrence::parse("every fortnight").is_err());
+        assert!(Recurrence::parse("every 2 days on mon").is_err());
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for input in [
+            "daily",
+            "every 3 days",
+            "weekly",
+            "every mon, wed, fri",
+            "every 2 weeks on tue, thu",
+            "monthly",
+            "every 2nd tue",
+            "every 3 months on the last fri",
+            "every 2 years from completion",
+        ] {
+            let rule = Recurrence::parse(input).unwrap();
+            assert_eq!(rule.to_string(), input);
+            assert_eq!(Recurrence::parse(&rule.to_string()).unwrap(), rule);
+        }
+    }
+
+    #[test]
+    fn test_next_after_intervals() {
+        let start = date(2026, 1, 31);
+        assert_eq!(Recurrence::new(Frequency::Daily, 2).next_after(start), Some(date(2026, 2, 2)));
+        assert_eq!(Recurrence::new(Frequency::Weekly, 1).next_after(start), Some(date(2026, 2, 7)));
+        // Month ends clamp to the last day of the shorter month
+        assert_eq!(Recurrence::new(Frequency::Monthly, 1).next_after(start), Some(date(2026, 2, 28)));
+        assert_eq!(Recurrence::new(Frequency::Yearly, 1).next_after(start), Some(date(2027, 1, 31)));
+    }
+
+    #[test]
+    fn test_next_after_weekdays() {
+        // 2026-01-14 is a Wednesday
+        let rule = Recurrence::parse("every mon, fri").unwrap();
+        assert_eq!(rule.next_after(date(2026, 1, 14)), Some(date(2026, 1, 16)));
+        assert_eq!(rule.next_after(date(2026, 1, 16)), Some(date(2026, 1, 19)));
+
+        let rule = Recurrence::parse("every 2 weeks on mon, fri").unwrap();
+        assert_eq!(rule.next_after(date(2026, 1, 12)), Some(date(2026, 1, 16)));
+        assert_eq!(rule.next_after(date(2026, 1, 16)), Some(date(2026, 1, 26)));
+    }
+
+    #[test]
+    fn test_next_after_nth_weekday() {
+        let rule = Recurrence::parse("every 2nd tuesday").unwrap();
+        // 2nd Tuesday of Jan 2026 is the 13th
+        assert_eq!(rule.next_after(date(2026, 1, 1)), Some(date(2026, 1, 13)));
+        assert_eq!(rule.next_after(date(2026, 1, 13)), Some(date(2026, 2, 10)));
+
+        let rule = Recurrence::parse("every last friday").unwrap();
+        assert_eq!(rule.next_after(date(2026, 1, 30)), Some(date(2026, 2, 27)));
+    }
+
+    #[test]
+    fn test_first_on_or_after() {
+        // 2026-01-14 is a Wednesday
+        let rule = Recurrence::parse("every 2 weeks on fri").unwrap();
+        assert_eq!(rule.first_on_or_after(date(2026, 1, 14)), Some(date(2026, 1, 16)));
+
+        let rule = Recurrence::parse("every wed").unwrap();
+        assert_eq!(rule.first_on_or_after(date(2026, 1, 14)), Some(date(2026, 1, 14)));
+
+        let rule = Recurrence::parse("monthly").unwrap();
+        assert_eq!(rule.first_on_or_after(date(2026, 1, 14)), Some(date(2026, 1, 14)));
+    }
+}
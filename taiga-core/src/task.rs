@@ -2,11 +2,17 @@
 //!
 //! Pure domain logic for task management with no I/O operations.
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use crate::category;
+use crate::date::{floating_date, has_time_of_day, wall_time};
 use crate::error::{CoreError, Result};
+use crate::field;
+use crate::recurrence::Recurrence;
+use crate::uid;
+use crate::zone::Zone;
 
 /// Newtype wrapper for task IDs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -30,17 +36,324 @@ impl std::fmt::Display for TaskId {
     }
 }
 
+/// A reference to a task given by the user: its number or its UID
+///
+/// Parses "12" and "#12" as task numbers, anything else as a (possibly
+/// shortened) UID. Resolve it with [`TaskCollection::resolve`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TaskRef {
+    Id(u32),
+    Uid(String),
+}
+
+impl std::str::FromStr for TaskRef {
+    type Err = CoreError;
+
+    fn from_str(input: &str) -> Result<Self> {
+        let input = input.trim();
+        match input.trim_start_matches('#').parse() {
+            Ok(id) => Ok(TaskRef::Id(id)),
+            Err(_) => uid::parse_prefix(input).map(TaskRef::Uid),
+        }
+    }
+}
+
+impl From<u32> for TaskRef {
+    fn from(id: u32) -> Self {
+        TaskRef::Id(id)
+    }
+}
+
+impl std::fmt::Display for TaskRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskRef::Id(id) => write!(f, "#{}", id),
+            TaskRef::Uid(uid) => write!(f, "{}", uid),
+        }
+    }
+}
+
+/// Task priority, from most to least important
+///
+/// Persisted as `!1`..`!4` markers, where `!1` is the most urgent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Priority {
+    Urgent,
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    /// All priorities, most important first
+    pub const ALL: [Priority; 4] = [
+        Priority::Urgent,
+        Priority::High,
+        Priority::Medium,
+        Priority::Low,
+    ];
+
+    /// Numeric level (1 = urgent, 4 = low)
+    pub fn level(self) -> u8 {
+        match self {
+            Priority::Urgent => 1,
+            Priority::High => 2,
+            Priority::Medium => 3,
+            Priority::Low => 4,
+        }
+    }
+
+    /// Create from a numeric level (1-4)
+    pub fn from_level(level: u8) -> Option<Self> {
+        match level {
+            1 => Some(Priority::Urgent),
+            2 => Some(Priority::High),
+            3 => Some(Priority::Medium),
+            4 => Some(Priority::Low),
+            _ => None,
+        }
+    }
+
+    /// Parse a priority from user input
+    ///
+    /// Accepts levels ("1", "!1", "p1") and names ("urgent", "high", "medium", "low").
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim().to_lowercase();
+        let level = input.trim_start_matches('!').trim_start_matches('p');
+
+        if let Some(priority) = level.parse().ok().and_then(Self::from_level) {
+            return Ok(priority);
+        }
+
+        match input.as_str() {
+            "urgent" => Ok(Priority::Urgent),
+            "high" => Ok(Priority::High),
+            "medium" | "med" => Ok(Priority::Medium),
+            "low" => Ok(Priority::Low),
+            _ => Err(CoreError::validation(
+                "priority",
+                format!("'{}' is not a priority (use 1-4 or urgent/high/medium/low)", input),
+            )),
+        }
+    }
+
+    /// Lowercase display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Priority::Urgent => "urgent",
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+}
+
+impl std::fmt::Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "!{}", self.level())
+    }
+}
+
+/// Lifecycle state of a task
+///
+/// Persisted as the markdown checkbox character: `[ ]` open, `[/]` in progress,
+/// `[?]` waiting, `[x]` done and `[-]` cancelled. Variants are ordered by how
+/// much attention they need, which is the order used when sorting by status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub enum Status {
+    InProgress,
+    #[default]
+    Open,
+    Waiting,
+    Done,
+    Cancelled,
+}
+
+impl Status {
+    /// All states, in sort order
+    pub const ALL: [Status; 5] = [
+        Status::InProgress,
+        Status::Open,
+        Status::Waiting,
+        Status::Done,
+        Status::Cancelled,
+    ];
+
+    /// Checkbox character used in markdown
+    pub fn marker(self) -> char {
+        match self {
+            Status::Open => ' ',
+            Status::InProgress => '/',
+            Status::Waiting => '?',
+            Status::Done => 'x',
+            Status::Cancelled => '-',
+        }
+    }
+
+    /// Create from a markdown checkbox character
+    pub fn from_marker(marker: char) -> Option<Self> {
+        match marker {
+            ' ' => Some(Status::Open),
+            '/' => Some(Status::InProgress),
+            '?' => Some(Status::Waiting),
+            'x' | 'X' => Some(Status::Done),
+            '-' => Some(Status::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Parse a status from user input
+    ///
+    /// Accepts names ("open", "in-progress", "waiting", "done", "cancelled"),
+    /// common synonyms and the checkbox characters.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim().to_lowercase();
+        if let Some(status) = input.chars().next().filter(|_| input.chars().count() == 1)
+            .and_then(Self::from_marker)
+        {
+            return Ok(status);
+        }
+
+        match input.replace(['_', ' '], "-").as_str() {
+            "open" | "todo" => Ok(Status::Open),
+            "in-progress" | "started" | "active" | "doing" => Ok(Status::InProgress),
+            "waiting" | "wait" | "blocked-on" => Ok(Status::Waiting),
+            "done" | "complete" | "completed" => Ok(Status::Done),
+            "cancelled" | "canceled" | "cancel" | "wont-do" => Ok(Status::Cancelled),
+            _ => Err(CoreError::validation(
+                "status",
+                format!(
+                    "'{}' is not a status (use open, in-progress, waiting, done or cancelled)",
+                    input
+                ),
+            )),
+        }
+    }
+
+    /// Lowercase display name
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Open => "open",
+            Status::InProgress => "in progress",
+            Status::Waiting => "waiting",
+            Status::Done => "done",
+            Status::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether no more work is expected (done or cancelled)
+    pub fn is_closed(self) -> bool {
+        matches!(self, Status::Done | Status::Cancelled)
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A lightweight checklist entry of a task, without an ID of its own
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+impl ChecklistItem {
+    /// Create an unchecked item
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            done: false,
+        }
+    }
+
+    /// Builder method to set whether the item is checked
+    pub fn with_done(mut self, done: bool) -> Self {
+        self.done = done;
+        self
+    }
+}
+
+/// Days before its deadline a task starts warning, unless set on the task
+pub const DEFAULT_DEADLINE_WARNING_DAYS: u32 = 3;
+
+/// How close an open task is to its deadline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlineState {
+    /// Further away than the warning lead time
+    Upcoming,
+    /// Within the warning lead time
+    Approaching,
+    /// The deadline has passed
+    Passed,
+}
+
+/// Short name for a link: the last segment of a URL or path
+///
+/// `https://tracker.example/issues/42` becomes `42`, `/home/me/spec.pdf`
+/// becomes `spec.pdf`.
+pub fn link_name(target: &str) -> &str {
+    let trimmed = target.trim_end_matches(['/', '\\']);
+    let without_scheme = trimmed.split_once("://").map_or(trimmed, |(_, rest)| rest);
+    match without_scheme.rsplit(['/', '\\']).next() {
+        Some(name) if !name.is_empty() => name,
+        _ => target,
+    }
+}
+
 /// A single task
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Task {
     pub id: u32,
+    /// Immutable unique identifier, unaffected by reindexing (empty until assigned)
+    pub uid: String,
     pub title: String,
-    pub is_complete: bool,
+    pub status: Status,
     pub scheduled: Option<DateTime<Local>>,
+    /// Day the task becomes relevant; it is deferred until then
+    #[serde(default)]
+    pub start: Option<NaiveDate>,
+    /// When the task must be done by, unlike `scheduled` which is when it is planned
+    #[serde(default)]
+    pub deadline: Option<DateTime<Local>>,
+    /// Days before the deadline to start warning (None = the caller's default)
+    #[serde(default)]
+    pub deadline_warning: Option<u32>,
+    /// Zone the scheduled time and deadline were set in (None = the
+    /// installed zone); floating dates without a time ignore it
+    #[serde(default)]
+    pub zone: Option<Zone>,
     /// Category this task belongs to (None = "Uncategorized")
     pub category: Option<String>,
     /// Tags associated with this task (without # prefix)
     pub tags: Vec<String>,
+    /// Priority of this task (None = no priority)
+    pub priority: Option<Priority>,
+    /// Parent task ID if this is a subtask (None = top-level task)
+    pub parent: Option<u32>,
+    /// IDs of tasks that must be completed before this one can start
+    pub blocked_by: Vec<u32>,
+    /// Rule for spawning the next occurrence when completed
+    pub recurrence: Option<Recurrence>,
+    /// Free-form notes, possibly spanning several lines
+    pub notes: Option<String>,
+    /// Checklist items, in order
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    /// Attached URLs and file paths
+    #[serde(default)]
+    pub links: Vec<String>,
+    /// Custom `key:value` fields, keyed by lowercase name
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// When the task was added (None for tasks predating timestamps)
+    pub created: Option<DateTime<Local>>,
+    /// When the task was last changed
+    pub updated: Option<DateTime<Local>>,
+    /// When the task was completed (None while open)
+    pub completed: Option<DateTime<Local>>,
 }
 
 impl Task {
@@ -48,11 +361,27 @@ impl Task {
     pub fn new(title: impl Into<String>) -> Self {
         Self {
             id: 0,
+            uid: String::new(),
             title: title.into(),
-            is_complete: false,
+            status: Status::Open,
             scheduled: None,
+            start: None,
+            deadline: None,
+            deadline_warning: None,
+            zone: None,
             category: None,
             tags: Vec::new(),
+            priority: None,
+            parent: None,
+            blocked_by: Vec::new(),
+            recurrence: None,
+            notes: None,
+            checklist: Vec::new(),
+            links: Vec::new(),
+            fields: BTreeMap::new(),
+            created: None,
+            updated: None,
+            completed: None,
         }
     }
 
@@ -62,15 +391,51 @@ impl Task {
         self
     }
 
+    /// Builder method to set the start date
+    pub fn with_start(mut self, start: Option<NaiveDate>) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Builder method to set the deadline
+    pub fn with_deadline(mut self, deadline: Option<DateTime<Local>>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Builder method to set the deadline warning lead time in days
+    pub fn with_deadline_warning(mut self, days: Option<u32>) -> Self {
+        self.deadline_warning = days;
+        self
+    }
+
+    /// Builder method to set the zone of the task's times
+    pub fn with_zone(mut self, zone: Option<Zone>) -> Self {
+        self.zone = zone;
+        self
+    }
+
     /// Builder method to set task ID
     pub fn with_id(mut self, id: u32) -> Self {
         self.id = id;
         self
     }
 
+    /// Builder method to set the unique identifier
+    pub fn with_uid(mut self, uid: impl Into<String>) -> Self {
+        self.uid = uid.into();
+        self
+    }
+
     /// Builder method to set completion status
     pub fn with_complete(mut self, complete: bool) -> Self {
-        self.is_complete = complete;
+        self.status = if complete { Status::Done } else { Status::Open };
+        self
+    }
+
+    /// Builder method to set the lifecycle status
+    pub fn with_status(mut self, status: Status) -> Self {
+        self.status = status;
         self
     }
 
@@ -86,6 +451,190 @@ impl Task {
         self
     }
 
+    /// Builder method to set priority
+    pub fn with_priority(mut self, priority: Option<Priority>) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Builder method to set parent task
+    pub fn with_parent(mut self, parent: Option<u32>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Builder method to set blocking task IDs
+    pub fn with_blocked_by(mut self, blocked_by: Vec<u32>) -> Self {
+        self.blocked_by = blocked_by;
+        self
+    }
+
+    /// Builder method to set recurrence rule
+    pub fn with_recurrence(mut self, recurrence: Option<Recurrence>) -> Self {
+        self.recurrence = recurrence;
+        self
+    }
+
+    /// Builder method to set notes
+    pub fn with_notes(mut self, notes: Option<String>) -> Self {
+        self.set_notes(notes);
+        self
+    }
+
+    /// Builder method to set checklist items
+    pub fn with_checklist(mut self, checklist: Vec<ChecklistItem>) -> Self {
+        self.checklist = checklist;
+        self
+    }
+
+    /// Builder method to set attached links
+    pub fn with_links(mut self, links: Vec<String>) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Builder method to set custom fields
+    pub fn with_fields(mut self, fields: BTreeMap<String, String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Builder method to set the creation time
+    pub fn with_created(mut self, created: Option<DateTime<Local>>) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Builder method to set the last update time
+    pub fn with_updated(mut self, updated: Option<DateTime<Local>>) -> Self {
+        self.updated = updated;
+        self
+    }
+
+    /// Builder method to set the completion time
+    pub fn with_completed(mut self, completed: Option<DateTime<Local>>) -> Self {
+        self.completed = completed;
+        self
+    }
+
+    /// Record that the task changed just now
+    pub fn touch(&mut self) {
+        self.updated = Some(Local::now());
+    }
+
+    /// Set or clear notes, treating blank text as no notes
+    ///
+    /// Trailing whitespace on each line and surrounding blank lines are dropped.
+    pub fn set_notes(&mut self, notes: Option<String>) {
+        self.notes = notes
+            .map(|text| {
+                let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+                lines.join("\n").trim_matches('\n').to_string()
+            })
+            .filter(|text| !text.is_empty());
+    }
+
+    /// Check if the title, notes or checklist contain a term (case-insensitive)
+    pub fn matches_text(&self, term: &str) -> bool {
+        let term = term.to_lowercase();
+        self.title.to_lowercase().contains(&term)
+            || self
+                .notes
+                .as_ref()
+                .is_some_and(|notes| notes.to_lowercase().contains(&term))
+            || self
+                .checklist
+                .iter()
+                .any(|item| item.text.to_lowercase().contains(&term))
+    }
+
+    /// Append a checklist item and return its number (starting at 1)
+    pub fn add_checklist_item(&mut self, text: &str) -> Result<usize> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(CoreError::validation("checklist", "Item text cannot be empty"));
+        }
+        self.checklist.push(ChecklistItem::new(text));
+        Ok(self.checklist.len())
+    }
+
+    /// Check or uncheck a checklist item by number, returning whether it is
+    /// now checked
+    pub fn toggle_checklist_item(&mut self, number: usize) -> Result<bool> {
+        let index = self.checklist_index(number)?;
+        let item = &mut self.checklist[index];
+        item.done = !item.done;
+        Ok(item.done)
+    }
+
+    /// Remove a checklist item by number
+    pub fn remove_checklist_item(&mut self, number: usize) -> Result<ChecklistItem> {
+        let index = self.checklist_index(number)?;
+        Ok(self.checklist.remove(index))
+    }
+
+    /// Checked and total number of checklist items (None without a checklist)
+    pub fn checklist_progress(&self) -> Option<(usize, usize)> {
+        if self.checklist.is_empty() {
+            return None;
+        }
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        Some((done, self.checklist.len()))
+    }
+
+    fn checklist_index(&self, number: usize) -> Result<usize> {
+        if number == 0 || number > self.checklist.len() {
+            return Err(CoreError::validation(
+                "checklist",
+                format!("Task #{} has no checklist item {}", self.id, number),
+            ));
+        }
+        Ok(number - 1)
+    }
+
+    /// Attach a URL or file path, returns false if it was already attached
+    pub fn add_link(&mut self, target: &str) -> Result<bool> {
+        let target = target.trim();
+        if target.is_empty() {
+            return Err(CoreError::validation("link", "Link cannot be empty"));
+        }
+        if self.links.iter().any(|link| link == target) {
+            return Ok(false);
+        }
+        self.links.push(target.to_string());
+        Ok(true)
+    }
+
+    /// Detach a link, given as its number (starting at 1) or its exact target
+    pub fn remove_link(&mut self, link: &str) -> Result<String> {
+        let index = match link.trim().parse::<usize>() {
+            Ok(number) if (1..=self.links.len()).contains(&number) => Some(number - 1),
+            _ => self.links.iter().position(|l| l == link.trim()),
+        };
+        match index {
+            Some(index) => Ok(self.links.remove(index)),
+            None => Err(CoreError::validation(
+                "link",
+                format!("Task #{} has no link '{}'", self.id, link.trim()),
+            )),
+        }
+    }
+
+    /// Value of a custom field (the name is case-insensitive)
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields.get(&key.to_lowercase()).map(String::as_str)
+    }
+
+    /// Set a custom field, or remove it when `value` is None; returns the
+    /// previous value
+    pub fn set_field(&mut self, key: &str, value: Option<&str>) -> Result<Option<String>> {
+        let key = field::normalize_key(key)?;
+        match value {
+            Some(value) => Ok(self.fields.insert(key, field::normalize_value(value)?)),
+            None => Ok(self.fields.remove(&key)),
+        }
+    }
+
     /// Add a tag to this task
     pub fn add_tag(&mut self, tag: &str) {
         let tag = tag.trim_start_matches('#').to_string();
@@ -105,29 +654,148 @@ impl Task {
         }
     }
 
-    /// Toggle completion status
+    /// Check if the task is done
+    pub fn is_complete(&self) -> bool {
+        self.status == Status::Done
+    }
+
+    /// Toggle completion status, recording when the task was completed
+    ///
+    /// A done task is reopened, any other task is marked done.
     pub fn toggle_complete(&mut self) {
-        self.is_complete = !self.is_complete;
+        let status = if self.is_complete() { Status::Open } else { Status::Done };
+        self.set_status(status);
+    }
+
+    /// Change the lifecycle status, recording when the task was completed
+    pub fn set_status(&mut self, status: Status) {
+        if status == self.status {
+            return;
+        }
+        self.completed = (status == Status::Done).then(Local::now);
+        self.status = status;
+        self.touch();
+    }
+
+    /// Get the scheduled date of this task's next occurrence
+    ///
+    /// Advances from the scheduled date, or from `completed_on` when the rule
+    /// says so or the task has no date. Returns None for non-recurring tasks.
+    pub fn next_occurrence(&self, completed_on: NaiveDate) -> Option<DateTime<Local>> {
+        let rule = self.recurrence.as_ref()?;
+        // Times repeat on the clock of the zone they were set in
+        let zone = self.zone.unwrap_or_else(Zone::current);
+        let anchor = match self.scheduled {
+            Some(dt) if !rule.from_completion && has_time_of_day(&dt) => zone.date_of(&dt),
+            Some(dt) if !rule.from_completion => wall_time(&dt).date(),
+            _ => completed_on,
+        };
+        let next = rule.next_after(anchor)?;
+        match self.scheduled.filter(has_time_of_day) {
+            Some(dt) => zone.from_naive(&next.and_time(zone.to_naive(&dt).time())),
+            None => floating_date(next),
+        }
+    }
+
+    /// Check if the task is deferred, i.e. its start date is still ahead
+    pub fn is_deferred(&self) -> bool {
+        self.is_deferred_on(Zone::current().today())
+    }
+
+    /// Check if the task is deferred on the given day
+    ///
+    /// Closed tasks are never deferred.
+    pub fn is_deferred_on(&self, today: NaiveDate) -> bool {
+        !self.status.is_closed() && self.start.is_some_and(|start| start > today)
     }
 
     /// Check if task is overdue
+    ///
+    /// Tasks with a time of day are overdue once that minute has passed;
+    /// tasks scheduled for a whole day only once the day is over in the
+    /// installed [`Zone`].
     pub fn is_overdue(&self) -> bool {
-        if let Some(dt) = self.scheduled {
-            dt.date_naive() < Local::now().date_naive() && !self.is_complete
+        self.is_overdue_at(Local::now())
+    }
+
+    /// Check if task is overdue at the given moment
+    pub fn is_overdue_at(&self, now: DateTime<Local>) -> bool {
+        self.is_overdue_in(now, &Zone::current())
+    }
+
+    /// Check if task is overdue at the given moment, with days ending in the given zone
+    pub fn is_overdue_in(&self, now: DateTime<Local>, zone: &Zone) -> bool {
+        match self.scheduled {
+            Some(_) if self.status.is_closed() => false,
+            Some(dt) => is_past(dt, now, zone),
+            None => false,
+        }
+    }
+
+    /// Check if an open task has missed its deadline
+    pub fn is_past_deadline(&self) -> bool {
+        self.is_past_deadline_at(Local::now())
+    }
+
+    /// Check if an open task has missed its deadline at the given moment
+    pub fn is_past_deadline_at(&self, now: DateTime<Local>) -> bool {
+        self.deadline_state_at(now, 0) == Some(DeadlineState::Passed)
+    }
+
+    /// How close the task is to its deadline, warning the task's own number
+    /// of days ahead or else `default_warning_days`
+    ///
+    /// Returns None for tasks without a deadline and for closed tasks.
+    pub fn deadline_state_at(&self, now: DateTime<Local>, default_warning_days: u32) -> Option<DeadlineState> {
+        let deadline = self.deadline.filter(|_| !self.status.is_closed())?;
+        let zone = Zone::current();
+        if is_past(deadline, now, &zone) {
+            return Some(DeadlineState::Passed);
+        }
+        let days_left = zone.date_of(&deadline).signed_duration_since(zone.to_naive(&now).date()).num_days();
+        let warning_days = self.deadline_warning.unwrap_or(default_warning_days);
+        if days_left <= i64::from(warning_days) {
+            Some(DeadlineState::Approaching)
         } else {
-            false
+            Some(DeadlineState::Upcoming)
         }
     }
 }
 
+/// Check if a date has passed: to the minute when it has a time of day,
+/// otherwise once the whole day is over in the zone
+fn is_past(dt: DateTime<Local>, now: DateTime<Local>, zone: &Zone) -> bool {
+    if has_time_of_day(&dt) {
+        dt < now
+    } else {
+        wall_time(&dt).date() < zone.to_naive(&now).date()
+    }
+}
+
 /// In-memory collection of tasks
 ///
 /// This is a pure domain model with no I/O operations.
 /// Persistence is handled by storage adapters in consuming crates.
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TaskCollection {
     pub tasks: HashMap<u32, Task>,
     pub next_id: u32,
+    /// Fill gaps left by removed tasks when numbering new ones
+    ///
+    /// When disabled, `next_id` only ever grows, so a number always refers
+    /// to the same task.
+    #[serde(default = "default_reuse_ids")]
+    pub reuse_ids: bool,
+}
+
+fn default_reuse_ids() -> bool {
+    true
+}
+
+impl Default for TaskCollection {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TaskCollection {
@@ -136,9 +804,16 @@ impl TaskCollection {
         TaskCollection {
             tasks: HashMap::new(),
             next_id: 1,
+            reuse_ids: true,
         }
     }
 
+    /// Builder method to enable or disable reusing IDs of removed tasks
+    pub fn with_reuse_ids(mut self, reuse_ids: bool) -> Self {
+        self.reuse_ids = reuse_ids;
+        self
+    }
+
     /// Add a new task with the given title and optional scheduled date
     pub fn add(&mut self, title: impl Into<String>, scheduled: Option<DateTime<Local>>) -> u32 {
         self.add_with_category_tags(title, scheduled, None, Vec::new())
@@ -152,32 +827,54 @@ impl TaskCollection {
         category: Option<String>,
         tags: Vec<String>,
     ) -> u32 {
+        let task = Task::new(title)
+            .with_scheduled(scheduled)
+            .with_category(category)
+            .with_tags(tags);
+
+        self.add_task(task)
+    }
+
+    /// Add a task built by the caller, assigning it the next available ID
+    ///
+    /// The task is stamped as created now, and given a fresh UID unless it
+    /// already has one that is not taken.
+    pub fn add_task(&mut self, mut task: Task) -> u32 {
         let id = self.find_next_id();
+        task.id = id;
+        while task.uid.is_empty() || self.find_by_uid(&task.uid).is_some() {
+            task.uid = uid::generate();
+        }
 
-        let task = Task {
-            id,
-            title: title.into(),
-            is_complete: false,
-            scheduled,
-            category,
-            tags,
-        };
+        let now = Local::now();
+        task.created = Some(now);
+        task.updated = Some(now);
+        task.completed = if task.is_complete() { Some(now) } else { None };
 
         self.tasks.insert(id, task);
         self.update_next_id();
         id
     }
 
-    /// Add an existing task to the collection
-    pub fn insert(&mut self, task: Task) {
+    /// Add an existing task to the collection, keeping its ID and timestamps
+    ///
+    /// A task without a UID, such as one stored before UIDs existed, gets one
+    /// derived from its ID and title, so it is the same on every load.
+    pub fn insert(&mut self, mut task: Task) {
+        if task.uid.is_empty() {
+            task.uid = uid::derive(&format!("{} {}", task.id, task.title));
+        }
         if task.id >= self.next_id {
             self.next_id = task.id + 1;
         }
         self.tasks.insert(task.id, task);
     }
 
-    /// Find the next available ID (reuses gaps)
+    /// Find the next available ID (reuses gaps unless disabled)
     fn find_next_id(&self) -> u32 {
+        if !self.reuse_ids {
+            return self.next_id;
+        }
         for id in 1..=self.next_id {
             if !self.tasks.contains_key(&id) {
                 return id;
@@ -187,11 +884,70 @@ impl TaskCollection {
     }
 
     /// Update next_id to be one more than the maximum used ID
+    ///
+    /// Without ID reuse, next_id never goes down.
     fn update_next_id(&mut self) {
-        if let Some(&max_id) = self.tasks.keys().max() {
-            self.next_id = max_id + 1;
+        let next_id = self.tasks.keys().max().map_or(1, |&max_id| max_id + 1);
+        self.next_id = if self.reuse_ids {
+            next_id
         } else {
-            self.next_id = 1;
+            self.next_id.max(next_id)
+        };
+    }
+
+    /// Find a task by its UID
+    pub fn find_by_uid(&self, uid: &str) -> Option<&Task> {
+        self.tasks.values().find(|t| t.uid == uid)
+    }
+
+    /// Resolve a task number or UID to the task's current ID
+    ///
+    /// UIDs may be shortened to any unique prefix of at least
+    /// [`uid::MIN_PREFIX_LEN`] characters.
+    pub fn resolve(&self, task: &TaskRef) -> Result<u32> {
+        let prefix = match task {
+            TaskRef::Id(id) => return self.get_or_err(*id).map(|t| t.id),
+            TaskRef::Uid(prefix) => prefix,
+        };
+
+        if let Some(task) = self.find_by_uid(prefix) {
+            return Ok(task.id);
+        }
+        if prefix.len() < uid::MIN_PREFIX_LEN {
+            return Err(CoreError::validation(
+                "id",
+                format!(
+                    "UID prefix '{}' is too short (use at least {} characters)",
+                    prefix,
+                    uid::MIN_PREFIX_LEN
+                ),
+            ));
+        }
+
+        let mut matches: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|t| t.uid.starts_with(prefix.as_str()))
+            .collect();
+        matches.sort_by_key(|t| t.id);
+        match matches.as_slice() {
+            [task] => Ok(task.id),
+            [] => Err(CoreError::validation(
+                "id",
+                format!("No task with UID '{}'", prefix),
+            )),
+            _ => Err(CoreError::validation(
+                "id",
+                format!(
+                    "UID prefix '{}' matches several tasks: {}",
+                    prefix,
+                    matches
+                        .iter()
+                        .map(|t| format!("{} (#{})", t.uid, t.id))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )),
         }
     }
 
@@ -201,13 +957,28 @@ impl TaskCollection {
     }
 
     /// Get a mutable reference to a task by ID
+    ///
+    /// Callers that change the task mark it as updated with [`Task::touch`].
     pub fn get_mut(&mut self, id: u32) -> Option<&mut Task> {
         self.tasks.get_mut(&id)
     }
 
     /// Remove a task by ID
+    ///
+    /// Subtasks of the removed task are moved up to its parent.
     pub fn remove(&mut self, id: u32) -> Option<Task> {
-        self.tasks.remove(&id)
+        let removed = self.tasks.remove(&id)?;
+        for task in self.tasks.values_mut() {
+            let blockers = task.blocked_by.len();
+            task.blocked_by.retain(|&b| b != id);
+            if task.parent == Some(id) {
+                task.parent = removed.parent;
+                task.touch();
+            } else if task.blocked_by.len() != blockers {
+                task.touch();
+            }
+        }
+        Some(removed)
     }
 
     /// Get all tasks sorted by ID
@@ -218,31 +989,105 @@ impl TaskCollection {
     }
 
     /// Reindex all tasks to sequential IDs starting from 1
+    ///
+    /// UIDs are kept, so references by UID stay valid.
     pub fn reindex(&mut self) {
         let mut tasks: Vec<Task> = self.tasks.drain().map(|(_, t)| t).collect();
         tasks.sort_by_key(|t| t.id);
 
-        for (new_id, task) in tasks.into_iter().enumerate() {
-            let mut task = task;
-            task.id = (new_id + 1) as u32;
+        let new_ids: HashMap<u32, u32> = tasks
+            .iter()
+            .enumerate()
+            .map(|(new_id, task)| (task.id, (new_id + 1) as u32))
+            .collect();
+
+        for mut task in tasks {
+            task.id = new_ids[&task.id];
+            task.parent = task.parent.and_then(|p| new_ids.get(&p).copied());
+            task.blocked_by = task
+                .blocked_by
+                .iter()
+                .filter_map(|b| new_ids.get(b).copied())
+                .collect();
             self.tasks.insert(task.id, task);
         }
 
         self.update_next_id();
     }
 
-    /// Remove all checked/completed tasks, returns count of removed tasks
+    /// Get IDs of done and cancelled tasks left untouched since before a time, sorted
+    ///
+    /// Tasks are judged by their last update, or their completion time when
+    /// that is missing. Tasks without timestamps only match when `before`
+    /// is None, which selects every closed task.
+    pub fn closed_ids(&self, before: Option<DateTime<Local>>) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .tasks
+            .values()
+            .filter(|t| t.status.is_closed())
+            .filter(|t| match before {
+                Some(before) => t.updated.or(t.completed).is_some_and(|dt| dt < before),
+                None => true,
+            })
+            .map(|t| t.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Move tasks into another collection, such as an archive
+    ///
+    /// Moved tasks get the next free IDs in `target` but keep their UIDs and
+    /// timestamps. Parent and dependency links survive between tasks moved
+    /// together, other links are dropped. Returns the new IDs, in order.
+    pub fn move_tasks(&mut self, target: &mut TaskCollection, ids: &[u32]) -> Vec<u32> {
+        // Take copies first, so removing one task does not rewrite the links
+        // of another that is moved along with it
+        let moved: Vec<Task> = ids.iter().filter_map(|id| self.get(*id).cloned()).collect();
+        for task in &moved {
+            self.remove(task.id);
+        }
+
+        let mut new_ids = HashMap::new();
+        let mut candidate = if target.reuse_ids { 1 } else { target.next_id };
+        for task in &moved {
+            while target.tasks.contains_key(&candidate) {
+                candidate += 1;
+            }
+            new_ids.insert(task.id, candidate);
+            candidate += 1;
+        }
+
+        for mut task in moved {
+            task.id = new_ids[&task.id];
+            task.parent = task.parent.and_then(|p| new_ids.get(&p).copied());
+            task.blocked_by = task
+                .blocked_by
+                .iter()
+                .filter_map(|b| new_ids.get(b).copied())
+                .collect();
+            while task.uid.is_empty() || target.find_by_uid(&task.uid).is_some() {
+                task.uid = uid::generate();
+            }
+            target.tasks.insert(task.id, task);
+        }
+        target.update_next_id();
+
+        ids.iter().filter_map(|id| new_ids.get(id).copied()).collect()
+    }
+
+    /// Remove all done and cancelled tasks, returns count of removed tasks
     pub fn remove_checked(&mut self) -> usize {
         let to_remove: Vec<u32> = self
             .tasks
             .iter()
-            .filter(|(_, task)| task.is_complete)
+            .filter(|(_, task)| task.status.is_closed())
             .map(|(id, _)| *id)
             .collect();
 
         let count = to_remove.len();
         for id in to_remove {
-            self.tasks.remove(&id);
+            self.remove(id);
         }
 
         count
@@ -265,7 +1110,12 @@ impl TaskCollection {
 
     /// Count completed tasks
     pub fn count_completed(&self) -> usize {
-        self.tasks.values().filter(|task| task.is_complete).count()
+        self.count_with_status(Status::Done)
+    }
+
+    /// Count tasks in the given state
+    pub fn count_with_status(&self, status: Status) -> usize {
+        self.tasks.values().filter(|task| task.status == status).count()
     }
 
     /// Get or return error if task not found
@@ -278,16 +1128,9 @@ impl TaskCollection {
         self.get_mut(id).ok_or(CoreError::TaskNotFound(id))
     }
 
-    /// Get unique categories sorted alphabetically
+    /// Get unique categories, including the parents of nested ones, in tree order
     pub fn get_categories(&self) -> Vec<String> {
-        let mut categories: Vec<String> = self
-            .tasks
-            .values()
-            .filter_map(|t| t.category.clone())
-            .collect();
-        categories.sort();
-        categories.dedup();
-        categories
+        category::with_ancestors(self.tasks.values().filter_map(|t| t.category.as_deref()))
     }
 
     /// Get all unique tags sorted alphabetically
@@ -302,18 +1145,300 @@ impl TaskCollection {
         tags
     }
 
-    /// Move a task to a different category
+    /// Move a task and all of its subtasks to a different category
     pub fn move_to_category(&mut self, id: u32, category: Option<String>) -> Result<()> {
-        let task = self.get_mut_or_err(id)?;
-        task.category = category;
+        self.get_or_err(id)?;
+        for task_id in std::iter::once(id).chain(self.descendant_ids(id)) {
+            if let Some(task) = self.get_mut(task_id)
+                && task.category != category
+            {
+                task.category = category.clone();
+                task.touch();
+            }
+        }
         Ok(())
     }
 
-    /// Get tasks in a specific category (None = uncategorized)
-    pub fn tasks_in_category(&self, category: Option<&str>) -> Vec<&Task> {
-        self.tasks
-            .values()
-            .filter(|t| t.category.as_deref() == category)
+    /// Make a task a subtask of another, or top-level with `None`
+    ///
+    /// The subtask (and its own subtasks) join the parent's category.
+    /// Fails if the new parent is the task itself or one of its descendants.
+    pub fn set_parent(&mut self, id: u32, parent: Option<u32>) -> Result<()> {
+        self.get_or_err(id)?;
+
+        if let Some(parent_id) = parent {
+            let parent_task = self.get_or_err(parent_id)?;
+            if parent_id == id || self.ancestor_ids(parent_id).contains(&id) {
+                return Err(CoreError::validation(
+                    "parent",
+                    format!("Task #{} cannot be a subtask of #{} (would create a cycle)", id, parent_id),
+                ));
+            }
+            let category = parent_task.category.clone();
+            self.move_to_category(id, category)?;
+        }
+
+        let task = self.get_mut_or_err(id)?;
+        if task.parent != parent {
+            task.parent = parent;
+            task.touch();
+        }
+        Ok(())
+    }
+
+    /// Get direct subtasks of a task, sorted by ID
+    pub fn children(&self, id: u32) -> Vec<&Task> {
+        let mut children: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|t| t.parent == Some(id))
+            .collect();
+        children.sort_by_key(|t| t.id);
+        children
+    }
+
+    /// Get IDs of all subtasks below a task, depth-first
+    pub fn descendant_ids(&self, id: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut stack: Vec<u32> = self.children(id).iter().rev().map(|t| t.id).collect();
+        let mut seen = HashSet::new();
+
+        while let Some(child_id) = stack.pop() {
+            if !seen.insert(child_id) {
+                continue;
+            }
+            result.push(child_id);
+            stack.extend(self.children(child_id).iter().rev().map(|t| t.id));
+        }
+
+        result
+    }
+
+    /// Get IDs of all ancestors of a task, nearest first
+    pub fn ancestor_ids(&self, id: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut current = self.get(id).and_then(|t| t.parent);
+
+        while let Some(parent_id) = current {
+            if result.contains(&parent_id) {
+                break;
+            }
+            result.push(parent_id);
+            current = self.get(parent_id).and_then(|t| t.parent);
+        }
+
+        result
+    }
+
+    /// Nesting depth of a task (0 = top-level)
+    pub fn depth(&self, id: u32) -> usize {
+        self.ancestor_ids(id).len()
+    }
+
+    /// Completion percentage of a task rolled up from its subtasks
+    ///
+    /// Each direct subtask contributes equally: completed subtasks count fully,
+    /// open subtasks with their own subtasks count by their own progress.
+    /// Cancelled subtasks are left out. Returns None for tasks without subtasks.
+    pub fn progress(&self, id: u32) -> Option<u8> {
+        self.progress_ratio(id, &mut HashSet::new())
+            .map(|ratio| (ratio * 100.0).round() as u8)
+    }
+
+    fn progress_ratio(&self, id: u32, visited: &mut HashSet<u32>) -> Option<f64> {
+        if !visited.insert(id) {
+            return None;
+        }
+
+        let children: Vec<&Task> = self
+            .children(id)
+            .into_iter()
+            .filter(|child| child.status != Status::Cancelled)
+            .collect();
+        if children.is_empty() {
+            return None;
+        }
+
+        let total: f64 = children
+            .iter()
+            .map(|child| {
+                if child.is_complete() {
+                    1.0
+                } else {
+                    self.progress_ratio(child.id, visited).unwrap_or(0.0)
+                }
+            })
+            .sum();
+
+        Some(total / children.len() as f64)
+    }
+
+    /// Toggle a task's completion, spawning the next occurrence of a recurring task
+    ///
+    /// `today` is the completion date. The recurrence rule moves to the new task,
+    /// so reopening and completing the old one again does not spawn a duplicate.
+    /// Returns the ID of the spawned task, if any.
+    pub fn toggle_complete(&mut self, id: u32, today: NaiveDate) -> Result<Option<u32>> {
+        let status = if self.get_or_err(id)?.is_complete() {
+            Status::Open
+        } else {
+            Status::Done
+        };
+        self.set_status(id, status, today)
+    }
+
+    /// Change a task's status, spawning the next occurrence of a recurring task
+    ///
+    /// Closing a recurring task, whether done or cancelled, moves its rule to a
+    /// new occurrence, so cancelling skips just this one. `today` is the closing
+    /// date. Returns the ID of the spawned task, if any.
+    pub fn set_status(&mut self, id: u32, status: Status, today: NaiveDate) -> Result<Option<u32>> {
+        let task = self.get_mut_or_err(id)?;
+        let was_closed = task.status.is_closed();
+        task.set_status(status);
+
+        if was_closed || !status.is_closed() {
+            return Ok(None);
+        }
+        let Some(scheduled) = task.next_occurrence(today) else {
+            return Ok(None);
+        };
+
+        let mut next = task.clone();
+        task.recurrence = None;
+        next.uid.clear();
+        next.status = Status::Open;
+        // Keep the same lead time between start and scheduled date
+        next.start = match (task.start, task.scheduled) {
+            (Some(start), Some(old)) => {
+                Some(wall_time(&scheduled).date() - (wall_time(&old).date() - start))
+            }
+            _ => None,
+        };
+        // Move the deadline as far as the schedule moved
+        let moved = wall_time(&scheduled).date() - task.scheduled.map_or(today, |old| wall_time(&old).date());
+        next.deadline = task.deadline.map(|deadline| deadline + moved);
+        next.scheduled = Some(scheduled);
+        for item in &mut next.checklist {
+            item.done = false;
+        }
+        // add_task stamps the new occurrence as created now
+        Ok(Some(self.add_task(next)))
+    }
+
+    /// Set or clear the priority of a task
+    pub fn set_priority(&mut self, id: u32, priority: Option<Priority>) -> Result<()> {
+        let task = self.get_mut_or_err(id)?;
+        if task.priority != priority {
+            task.priority = priority;
+            task.touch();
+        }
+        Ok(())
+    }
+
+    /// Get tasks with a specific priority (None = no priority)
+    pub fn tasks_with_priority(&self, priority: Option<Priority>) -> Vec<&Task> {
+        self.tasks
+            .values()
+            .filter(|t| t.priority == priority)
+            .collect()
+    }
+
+    /// Record that a task cannot start until `blocker` is complete
+    ///
+    /// Fails if either task is missing or if the link would create a cycle.
+    pub fn add_dependency(&mut self, id: u32, blocker: u32) -> Result<()> {
+        self.get_or_err(id)?;
+        self.get_or_err(blocker)?;
+
+        if id == blocker || self.blocker_ids(blocker).contains(&id) {
+            return Err(CoreError::validation(
+                "dependency",
+                format!("Task #{} cannot be blocked by #{} (would create a cycle)", id, blocker),
+            ));
+        }
+
+        let task = self.get_mut_or_err(id)?;
+        if !task.blocked_by.contains(&blocker) {
+            task.blocked_by.push(blocker);
+            task.blocked_by.sort_unstable();
+            task.touch();
+        }
+        Ok(())
+    }
+
+    /// Remove a dependency link, returns whether it existed
+    pub fn remove_dependency(&mut self, id: u32, blocker: u32) -> Result<bool> {
+        let task = self.get_mut_or_err(id)?;
+        let before = task.blocked_by.len();
+        task.blocked_by.retain(|&b| b != blocker);
+        let removed = task.blocked_by.len() != before;
+        if removed {
+            task.touch();
+        }
+        Ok(removed)
+    }
+
+    /// Get IDs of every task this one transitively depends on
+    pub fn blocker_ids(&self, id: u32) -> Vec<u32> {
+        let mut result = Vec::new();
+        let mut stack: Vec<u32> = self.get(id).map(|t| t.blocked_by.clone()).unwrap_or_default();
+
+        while let Some(blocker) = stack.pop() {
+            if result.contains(&blocker) {
+                continue;
+            }
+            result.push(blocker);
+            if let Some(task) = self.get(blocker) {
+                stack.extend(task.blocked_by.iter().copied());
+            }
+        }
+
+        result
+    }
+
+    /// Get the direct blockers of a task that are not yet done or cancelled
+    pub fn open_blockers(&self, id: u32) -> Vec<&Task> {
+        self.get(id)
+            .map(|task| {
+                task.blocked_by
+                    .iter()
+                    .filter_map(|b| self.get(*b))
+                    .filter(|b| !b.status.is_closed())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check if a task is waiting on an incomplete blocker
+    pub fn is_blocked(&self, id: u32) -> bool {
+        !self.open_blockers(id).is_empty()
+    }
+
+    /// Get tasks that directly depend on the given task
+    pub fn dependents(&self, id: u32) -> Vec<&Task> {
+        let mut dependents: Vec<&Task> = self
+            .tasks
+            .values()
+            .filter(|t| t.blocked_by.contains(&id))
+            .collect();
+        dependents.sort_by_key(|t| t.id);
+        dependents
+    }
+
+    /// Get tasks in a specific category (None = uncategorized)
+    pub fn tasks_in_category(&self, category: Option<&str>) -> Vec<&Task> {
+        self.tasks
+            .values()
+            .filter(|t| t.category.as_deref() == category)
+            .collect()
+    }
+
+    /// Get tasks in a category or any of its subcategories
+    pub fn tasks_within_category(&self, path: &str) -> Vec<&Task> {
+        self.tasks
+            .values()
+            .filter(|t| t.category.as_deref().is_some_and(|c| category::is_within(c, path)))
             .collect()
     }
 
@@ -329,6 +1454,7 @@ impl TaskCollection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_task_creation() {
@@ -338,20 +1464,20 @@ mod tests {
 
         assert_eq!(task.id, 1);
         assert_eq!(task.title, "Test task");
-        assert!(!task.is_complete);
+        assert!(!task.is_complete());
         assert!(task.scheduled.is_none());
     }
 
     #[test]
     fn test_task_toggle() {
         let mut task = Task::new("Test");
-        assert!(!task.is_complete);
+        assert!(!task.is_complete());
 
         task.toggle_complete();
-        assert!(task.is_complete);
+        assert!(task.is_complete());
 
         task.toggle_complete();
-        assert!(!task.is_complete);
+        assert!(!task.is_complete());
     }
 
     #[test]
@@ -403,6 +1529,177 @@ mod tests {
         assert_eq!(tasks[1].id, 2);
     }
 
+    #[test]
+    fn test_priority_parse() {
+        assert_eq!(Priority::parse("1").unwrap(), Priority::Urgent);
+        assert_eq!(Priority::parse("!2").unwrap(), Priority::High);
+        assert_eq!(Priority::parse("P3").unwrap(), Priority::Medium);
+        assert_eq!(Priority::parse("low").unwrap(), Priority::Low);
+        assert!(Priority::parse("5").is_err());
+        assert!(Priority::parse("whenever").is_err());
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        assert!(Priority::Urgent < Priority::High);
+        assert!(Priority::Medium < Priority::Low);
+        assert_eq!(Priority::High.to_string(), "!2");
+    }
+
+    #[test]
+    fn test_collection_add_task_with_priority() {
+        let mut collection = TaskCollection::new();
+        collection.add("Task 1", None);
+
+        let id = collection.add_task(Task::new("Task 2").with_priority(Some(Priority::High)));
+
+        assert_eq!(id, 2);
+        assert_eq!(collection.get(id).unwrap().priority, Some(Priority::High));
+        assert_eq!(collection.tasks_with_priority(Some(Priority::High)).len(), 1);
+        assert_eq!(collection.tasks_with_priority(None).len(), 1);
+    }
+
+    #[test]
+    fn test_set_parent_prevents_cycles() {
+        let mut collection = TaskCollection::new();
+        let a = collection.add("A", None);
+        let b = collection.add("B", None);
+        let c = collection.add("C", None);
+
+        collection.set_parent(b, Some(a)).unwrap();
+        collection.set_parent(c, Some(b)).unwrap();
+
+        assert!(collection.set_parent(a, Some(c)).is_err());
+        assert!(collection.set_parent(a, Some(a)).is_err());
+        assert!(collection.set_parent(a, Some(99)).is_err());
+        assert_eq!(collection.descendant_ids(a), vec![b, c]);
+        assert_eq!(collection.ancestor_ids(c), vec![b, a]);
+        assert_eq!(collection.depth(c), 2);
+    }
+
+    #[test]
+    fn test_set_parent_inherits_category() {
+        let mut collection = TaskCollection::new();
+        let parent = collection.add_with_category_tags("Parent", None, Some("Work".to_string()), Vec::new());
+        let child = collection.add("Child", None);
+
+        collection.set_parent(child, Some(parent)).unwrap();
+        assert_eq!(collection.get(child).unwrap().category, Some("Work".to_string()));
+
+        collection.move_to_category(parent, Some("Home".to_string())).unwrap();
+        assert_eq!(collection.get(child).unwrap().category, Some("Home".to_string()));
+    }
+
+    #[test]
+    fn test_nested_categories() {
+        let mut collection = TaskCollection::new();
+        collection.add_with_category_tags("Deploy", None, Some("Work/ClientA/Backend".to_string()), Vec::new());
+        collection.add_with_category_tags("Plan", None, Some("Work".to_string()), Vec::new());
+        collection.add_with_category_tags("Shop", None, Some("Home".to_string()), Vec::new());
+
+        assert_eq!(
+            collection.get_categories(),
+            vec!["Home", "Work", "Work/ClientA", "Work/ClientA/Backend"]
+        );
+        assert_eq!(collection.tasks_within_category("Work").len(), 2);
+        assert_eq!(collection.tasks_within_category("Work/ClientA").len(), 1);
+        assert_eq!(collection.tasks_in_category(Some("Work")).len(), 1);
+    }
+
+    #[test]
+    fn test_progress_rollup() {
+        let mut collection = TaskCollection::new();
+        let parent = collection.add("Parent", None);
+        let done = collection.add_task(Task::new("Done").with_parent(Some(parent)));
+        let nested = collection.add_task(Task::new("Nested").with_parent(Some(parent)));
+        let leaf_done = collection.add_task(Task::new("Leaf done").with_parent(Some(nested)));
+        collection.add_task(Task::new("Leaf open").with_parent(Some(nested)));
+
+        collection.get_mut(done).unwrap().status = Status::Done;
+        collection.get_mut(leaf_done).unwrap().status = Status::Done;
+
+        assert_eq!(collection.progress(nested), Some(50));
+        assert_eq!(collection.progress(parent), Some(75));
+        assert_eq!(collection.progress(done), None);
+    }
+
+    #[test]
+    fn test_remove_reparents_children() {
+        let mut collection = TaskCollection::new();
+        let root = collection.add("Root", None);
+        let middle = collection.add_task(Task::new("Middle").with_parent(Some(root)));
+        let leaf = collection.add_task(Task::new("Leaf").with_parent(Some(middle)));
+
+        collection.remove(middle);
+        assert_eq!(collection.get(leaf).unwrap().parent, Some(root));
+    }
+
+    #[test]
+    fn test_reindex_remaps_parents() {
+        let mut collection = TaskCollection::new();
+        collection.add("Task 1", None);
+        collection.add("Task 2", None);
+        let parent = collection.add("Task 3", None);
+        collection.add_task(Task::new("Task 4").with_parent(Some(parent)));
+
+        collection.remove(2);
+        collection.reindex();
+
+        assert_eq!(collection.get(3).unwrap().parent, Some(2));
+    }
+
+    #[test]
+    fn test_dependencies_detect_cycles() {
+        let mut collection = TaskCollection::new();
+        let a = collection.add("A", None);
+        let b = collection.add("B", None);
+        let c = collection.add("C", None);
+
+        collection.add_dependency(c, b).unwrap();
+        collection.add_dependency(b, a).unwrap();
+
+        assert!(collection.add_dependency(a, c).is_err());
+        assert!(collection.add_dependency(a, a).is_err());
+        assert!(collection.add_dependency(a, 42).is_err());
+        assert_eq!(collection.blocker_ids(c), vec![b, a]);
+        assert_eq!(collection.dependents(b)[0].id, c);
+    }
+
+    #[test]
+    fn test_is_blocked_until_blockers_complete() {
+        let mut collection = TaskCollection::new();
+        let blocker1 = collection.add("Blocker 1", None);
+        let blocker2 = collection.add("Blocker 2", None);
+        let task = collection.add("Task", None);
+        collection.add_dependency(task, blocker1).unwrap();
+        collection.add_dependency(task, blocker2).unwrap();
+
+        assert!(collection.is_blocked(task));
+        collection.get_mut(blocker1).unwrap().status = Status::Done;
+        assert!(collection.is_blocked(task));
+        collection.get_mut(blocker2).unwrap().status = Status::Done;
+        assert!(!collection.is_blocked(task));
+
+        assert!(collection.remove_dependency(task, blocker1).unwrap());
+        assert!(!collection.remove_dependency(task, blocker1).unwrap());
+    }
+
+    #[test]
+    fn test_remove_and_reindex_update_dependencies() {
+        let mut collection = TaskCollection::new();
+        let gone = collection.add("Gone", None);
+        let blocker = collection.add("Blocker", None);
+        let task = collection.add("Task", None);
+        collection.add_dependency(task, gone).unwrap();
+        collection.add_dependency(task, blocker).unwrap();
+
+        collection.remove(gone);
+        assert_eq!(collection.get(task).unwrap().blocked_by, vec![blocker]);
+
+        collection.reindex();
+        assert_eq!(collection.get(2).unwrap().blocked_by, vec![1]);
+    }
+
     #[test]
     fn test_remove_checked() {
         let mut collection = TaskCollection::new();
@@ -412,8 +1709,8 @@ mod tests {
         collection.add("Task 3", None);
 
         // Mark tasks 1 and 3 as complete
-        collection.get_mut(1).unwrap().is_complete = true;
-        collection.get_mut(3).unwrap().is_complete = true;
+        collection.get_mut(1).unwrap().status = Status::Done;
+        collection.get_mut(3).unwrap().status = Status::Done;
 
         let removed = collection.remove_checked();
 
@@ -421,4 +1718,507 @@ mod tests {
         assert_eq!(collection.len(), 1);
         assert!(collection.get(2).is_some());
     }
+
+    #[test]
+    fn test_toggle_recurring_from_due_date() {
+        let mut collection = TaskCollection::new();
+        let due = Local.with_ymd_and_hms(2026, 1, 5, 9, 30, 0).unwrap();
+        let id = collection.add_task(
+            Task::new("Weekly report")
+                .with_scheduled(Some(due))
+                .with_tags(vec!["work".to_string()])
+                .with_recurrence(Some(Recurrence::parse("weekly").unwrap())),
+        );
+
+        let completed_on = NaiveDate::from_ymd_opt(2026, 1, 8).unwrap();
+        let next_id = collection.toggle_complete(id, completed_on).unwrap().unwrap();
+
+        let done = collection.get(id).unwrap();
+        assert!(done.is_complete());
+        assert!(done.recurrence.is_none());
+
+        let next = collection.get(next_id).unwrap();
+        assert!(!next.is_complete());
+        assert_eq!(next.title, "Weekly report");
+        assert_eq!(next.tags, vec!["work"]);
+        assert_eq!(next.scheduled, Some(Local.with_ymd_and_hms(2026, 1, 12, 9, 30, 0).unwrap()));
+        assert!(next.recurrence.is_some());
+
+        // Reopening and completing again does not spawn another occurrence
+        collection.toggle_complete(id, completed_on).unwrap();
+        assert_eq!(collection.toggle_complete(id, completed_on).unwrap(), None);
+        assert_eq!(collection.len(), 2);
+    }
+
+    #[test]
+    fn test_toggle_recurring_from_completion() {
+        let mut collection = TaskCollection::new();
+        let due = Local.with_ymd_and_hms(2026, 1, 5, 0, 0, 0).unwrap();
+        let id = collection.add_task(
+            Task::new("Water plants")
+                .with_scheduled(Some(due))
+                .with_recurrence(Some(Recurrence::parse("every 3 days from completion").unwrap())),
+        );
+
+        let completed_on = NaiveDate::from_ymd_opt(2026, 1, 8).unwrap();
+        let next_id = collection.toggle_complete(id, completed_on).unwrap().unwrap();
+        assert_eq!(
+            collection.get(next_id).unwrap().scheduled,
+            Some(Local.with_ymd_and_hms(2026, 1, 11, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_toggle_non_recurring() {
+        let mut collection = TaskCollection::new();
+        let id = collection.add("Once", None);
+        let today = NaiveDate::from_ymd_opt(2026, 1, 8).unwrap();
+
+        assert_eq!(collection.toggle_complete(id, today).unwrap(), None);
+        assert!(collection.get(id).unwrap().is_complete());
+        assert_eq!(collection.len(), 1);
+    }
+
+    #[test]
+    fn test_is_overdue_minute_precision() {
+        let now = Local.with_ymd_and_hms(2026, 1, 25, 15, 30, 0).unwrap();
+
+        let timed = Task::new("Call").with_scheduled(Some(Local.with_ymd_and_hms(2026, 1, 25, 15, 29, 0).unwrap()));
+        assert!(timed.is_overdue_at(now));
+
+        let later = Task::new("Call").with_scheduled(Some(Local.with_ymd_and_hms(2026, 1, 25, 15, 31, 0).unwrap()));
+        assert!(!later.is_overdue_at(now));
+
+        // Whole-day tasks are not overdue until the day is over
+        let all_day = Task::new("Report").with_scheduled(Some(Local.with_ymd_and_hms(2026, 1, 25, 0, 0, 0).unwrap()));
+        assert!(!all_day.is_overdue_at(now));
+        assert!(all_day.is_overdue_at(now + chrono::Duration::days(1)));
+
+        assert!(!timed.with_complete(true).is_overdue_at(now));
+    }
+
+    #[test]
+    fn test_time_zones() {
+        let utc = Zone::parse("UTC").unwrap();
+        let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+        let berlin = Zone::parse("Europe/Berlin").unwrap();
+        let at = |zone: Zone, date: (i32, u32, u32), hour| {
+            let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+            zone.from_naive(&date.and_hms_opt(hour, 30, 0).unwrap()).unwrap()
+        };
+
+        // 23:30 UTC is already the next morning in Tokyo, so a floating date
+        // is over there but not in UTC
+        let now = at(utc, (2026, 1, 25), 23);
+        let all_day = Task::new("Report").with_scheduled(Some(Local.with_ymd_and_hms(2026, 1, 25, 0, 0, 0).unwrap()));
+        assert!(!all_day.is_overdue_in(now, &utc));
+        assert!(all_day.is_overdue_in(now, &tokyo));
+
+        // A zoned time is the same moment everywhere
+        let call = Task::new("Call").with_scheduled(Some(at(berlin, (2026, 1, 25), 15))).with_zone(Some(berlin));
+        assert!(call.is_overdue_in(now, &utc) && call.is_overdue_in(now, &tokyo));
+
+        // Repeating times keep the clock time of their zone across daylight saving
+        let standup = call
+            .with_scheduled(Some(at(berlin, (2026, 3, 23), 9)))
+            .with_recurrence(Some(Recurrence::parse("weekly").unwrap()));
+        let next = standup.next_occurrence(NaiveDate::from_ymd_opt(2026, 3, 23).unwrap());
+        assert_eq!(next, Some(at(berlin, (2026, 3, 30), 9)));
+    }
+
+    #[test]
+    fn test_notes() {
+        let task = Task::new("Release")
+            .with_notes(Some("\nAcceptance criteria:  \n\n- changelog updated\n\n".to_string()));
+        assert_eq!(task.notes.as_deref(), Some("Acceptance criteria:\n\n- changelog updated"));
+
+        assert!(task.matches_text("CHANGELOG"));
+        assert!(task.matches_text("release"));
+        assert!(!task.matches_text("deploy"));
+
+        let task = task.with_notes(Some("  \n ".to_string()));
+        assert!(task.notes.is_none());
+    }
+
+    #[test]
+    fn test_links() {
+        let mut task = Task::new("Fix login").with_id(7);
+        assert!(task.add_link("https://tracker.example/issues/42").unwrap());
+        assert!(task.add_link("/home/me/spec.pdf").unwrap());
+        assert!(!task.add_link("/home/me/spec.pdf").unwrap());
+        assert!(task.add_link(" ").is_err());
+
+        assert_eq!(task.remove_link("2").unwrap(), "/home/me/spec.pdf");
+        assert!(task.remove_link("2").is_err());
+        assert_eq!(task.remove_link("https://tracker.example/issues/42").unwrap(), "https://tracker.example/issues/42");
+        assert!(task.links.is_empty());
+    }
+
+    #[test]
+    fn test_fields() {
+        let mut task = Task::new("Fix login");
+        assert_eq!(task.set_field("Customer", Some(" Acme ")).unwrap(), None);
+        assert_eq!(task.field("customer"), Some("Acme"));
+        assert_eq!(task.set_field("customer", Some("Globex")).unwrap(), Some("Acme".to_string()));
+        assert!(task.set_field("bad key", Some("x")).is_err());
+        assert!(task.set_field("ticket", Some("")).is_err());
+
+        assert_eq!(task.set_field("CUSTOMER", None).unwrap(), Some("Globex".to_string()));
+        assert!(task.fields.is_empty());
+    }
+
+    #[test]
+    fn test_link_name() {
+        assert_eq!(link_name("https://tracker.example/issues/42"), "42");
+        assert_eq!(link_name("https://example.com/"), "example.com");
+        assert_eq!(link_name("/home/me/spec.pdf"), "spec.pdf");
+        assert_eq!(link_name(r"C:\docs\plan.docx"), "plan.docx");
+    }
+
+    #[test]
+    fn test_checklist() {
+        let mut task = Task::new("Pack").with_id(4);
+        assert_eq!(task.checklist_progress(), None);
+        assert_eq!(task.add_checklist_item(" passport ").unwrap(), 1);
+        assert_eq!(task.add_checklist_item("charger").unwrap(), 2);
+        assert!(task.add_checklist_item("  ").is_err());
+
+        assert!(task.toggle_checklist_item(1).unwrap());
+        assert_eq!(task.checklist_progress(), Some((1, 2)));
+        assert!(task.matches_text("PASSPORT"));
+
+        assert_eq!(task.remove_checklist_item(2).unwrap().text, "charger");
+        assert_eq!(task.checklist_progress(), Some((1, 1)));
+        assert!(task.toggle_checklist_item(0).is_err());
+        assert!(task.remove_checklist_item(2).is_err());
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let mut collection = TaskCollection::new();
+        let before = Local::now();
+        let id = collection.add("Task", None);
+
+        let task = collection.get(id).unwrap();
+        assert!(task.created.is_some_and(|dt| dt >= before));
+        assert_eq!(task.updated, task.created);
+        assert!(task.completed.is_none());
+
+        collection.toggle_complete(id, Local::now().date_naive()).unwrap();
+        let task = collection.get(id).unwrap();
+        assert!(task.completed.is_some_and(|dt| dt >= before));
+        assert!(task.updated >= task.created);
+
+        collection.toggle_complete(id, Local::now().date_naive()).unwrap();
+        assert!(collection.get(id).unwrap().completed.is_none());
+    }
+
+    #[test]
+    fn test_mutation_marks_updated() {
+        let mut collection = TaskCollection::new();
+        let old = Local.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        collection.insert(Task::new("Task").with_id(1).with_created(Some(old)).with_updated(Some(old)));
+        collection.insert(Task::new("Other").with_id(2).with_updated(Some(old)));
+
+        // Reading, borrowing and no-op changes do not touch the task
+        collection.get(1).unwrap();
+        collection.get_mut(1).unwrap();
+        collection.remove_dependency(1, 2).unwrap();
+        collection.set_priority(1, None).unwrap();
+        collection.set_status(1, Status::Open, old.date_naive()).unwrap();
+        assert_eq!(collection.get(1).unwrap().updated, Some(old));
+
+        collection.set_priority(1, Some(Priority::High)).unwrap();
+        let task = collection.get(1).unwrap();
+        assert!(task.updated.is_some_and(|dt| dt > old));
+        assert_eq!(task.created, Some(old));
+        assert_eq!(collection.get(2).unwrap().updated, Some(old));
+    }
+
+    #[test]
+    fn test_recurring_occurrence_gets_fresh_timestamps() {
+        let mut collection = TaskCollection::new();
+        let old = Local.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+        collection.insert(
+            Task::new("Daily")
+                .with_id(1)
+                .with_created(Some(old))
+                .with_scheduled(Some(old))
+                .with_recurrence(Some(Recurrence::parse("daily").unwrap())),
+        );
+
+        let next_id = collection.toggle_complete(1, old.date_naive()).unwrap().unwrap();
+        let next = collection.get(next_id).unwrap();
+        assert!(next.created.is_some_and(|dt| dt > old));
+        assert!(next.completed.is_none());
+    }
+
+    #[test]
+    fn test_uids_assigned_and_kept_on_reindex() {
+        let mut collection = TaskCollection::new();
+        collection.add("Task 1", None);
+        let id = collection.add("Task 2", None);
+        let uid = collection.get(id).unwrap().uid.clone();
+        assert_eq!(uid.len(), uid::UID_LEN);
+        assert_ne!(collection.get(1).unwrap().uid, uid);
+
+        collection.remove(1);
+        collection.reindex();
+        assert_eq!(collection.find_by_uid(&uid).unwrap().id, 1);
+    }
+
+    #[test]
+    fn test_insert_derives_missing_uid() {
+        let mut first = TaskCollection::new();
+        first.insert(Task::new("Old task").with_id(3));
+        let mut second = TaskCollection::new();
+        second.insert(Task::new("Old task").with_id(3));
+        assert!(!first.get(3).unwrap().uid.is_empty());
+        assert_eq!(first.get(3).unwrap().uid, second.get(3).unwrap().uid);
+    }
+
+    #[test]
+    fn test_resolve_task_ref() {
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("A").with_id(1).with_uid("abcd1234"));
+        collection.insert(Task::new("B").with_id(2).with_uid("abcd5678"));
+        collection.insert(Task::new("C").with_id(7).with_uid("zz99yy88"));
+
+        let resolve = |input: &str| collection.resolve(&input.parse().unwrap());
+        assert_eq!(resolve("#7").unwrap(), 7);
+        assert_eq!(resolve("ZZ99").unwrap(), 7);
+        assert_eq!(resolve("abcd5678").unwrap(), 2);
+        assert!(resolve("abcd").is_err()); // ambiguous
+        assert!(resolve("zz9").is_err()); // too short
+        assert!(resolve("4").is_err());
+        assert!("hello".parse::<TaskRef>().is_err());
+    }
+
+    #[test]
+    fn test_disabled_id_reuse() {
+        let mut collection = TaskCollection::new().with_reuse_ids(false);
+        collection.add("Task 1", None);
+        collection.add("Task 2", None);
+        collection.remove(2);
+        collection.remove(1);
+        assert_eq!(collection.add("Task 3", None), 3);
+
+        let mut reusing = TaskCollection::new();
+        reusing.add("Task 1", None);
+        reusing.add("Task 2", None);
+        reusing.remove(1);
+        assert_eq!(reusing.add("Task 3", None), 1);
+    }
+
+    #[test]
+    fn test_recurring_occurrence_gets_new_uid() {
+        let mut collection = TaskCollection::new();
+        let today = Local::now().date_naive();
+        let id = collection.add_task(
+            Task::new("Water plants").with_recurrence(Some(Recurrence::parse("daily").unwrap())),
+        );
+        let next_id = collection.toggle_complete(id, today).unwrap().unwrap();
+        assert_ne!(collection.get(id).unwrap().uid, collection.get(next_id).unwrap().uid);
+    }
+
+    #[test]
+    fn test_recurring_occurrence_resets_checklist() {
+        let mut collection = TaskCollection::new();
+        let today = Local::now().date_naive();
+        let id = collection.add_task(
+            Task::new("Weekly review")
+                .with_recurrence(Some(Recurrence::parse("weekly").unwrap()))
+                .with_checklist(vec![ChecklistItem::new("Inbox zero").with_done(true)]),
+        );
+        let next_id = collection.toggle_complete(id, today).unwrap().unwrap();
+        assert!(collection.get(id).unwrap().checklist[0].done);
+        assert!(!collection.get(next_id).unwrap().checklist[0].done);
+    }
+
+    #[test]
+    fn test_deferred() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let task = Task::new("File taxes").with_start(Some(day(10)));
+        assert!(task.is_deferred_on(day(9)));
+        assert!(!task.is_deferred_on(day(10)));
+        assert!(!task.clone().with_status(Status::Done).is_deferred_on(day(9)));
+        assert!(!Task::new("Now").is_deferred_on(day(9)));
+    }
+
+    #[test]
+    fn test_deadline_state() {
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let due = |d, h| Some(Local.with_ymd_and_hms(2026, 3, d, h, 0, 0).unwrap());
+        let state = |task: &Task| task.deadline_state_at(now, 3);
+
+        assert_eq!(state(&Task::new("Far").with_deadline(due(20, 0))), Some(DeadlineState::Upcoming));
+        assert_eq!(state(&Task::new("Soon").with_deadline(due(13, 0))), Some(DeadlineState::Approaching));
+        assert_eq!(
+            state(&Task::new("Own lead time").with_deadline(due(20, 0)).with_deadline_warning(Some(14))),
+            Some(DeadlineState::Approaching)
+        );
+        // Whole-day deadlines pass at the end of the day, timed ones to the minute
+        assert_eq!(state(&Task::new("Today").with_deadline(due(10, 0))), Some(DeadlineState::Approaching));
+        assert_eq!(state(&Task::new("This morning").with_deadline(due(10, 9))), Some(DeadlineState::Passed));
+        assert!(Task::new("Missed").with_deadline(due(9, 0)).is_past_deadline_at(now));
+
+        let done = Task::new("Done").with_deadline(due(9, 0)).with_status(Status::Done);
+        assert_eq!(state(&done), None);
+        assert_eq!(state(&Task::new("None")), None);
+    }
+
+    #[test]
+    fn test_recurring_occurrence_moves_deadline() {
+        let mut collection = TaskCollection::new();
+        let id = collection.add_task(
+            Task::new("Send invoice")
+                .with_scheduled(Some(Local.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap()))
+                .with_deadline(Some(Local.with_ymd_and_hms(2026, 3, 5, 17, 0, 0).unwrap()))
+                .with_recurrence(Some(Recurrence::parse("weekly").unwrap())),
+        );
+        let completed_on = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let next_id = collection.toggle_complete(id, completed_on).unwrap().unwrap();
+        assert_eq!(
+            collection.get(next_id).unwrap().deadline,
+            Some(Local.with_ymd_and_hms(2026, 3, 12, 17, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_recurring_occurrence_keeps_start_lead_time() {
+        let mut collection = TaskCollection::new();
+        let due = Local.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap();
+        let id = collection.add_task(
+            Task::new("Pay rent")
+                .with_scheduled(Some(due))
+                .with_start(NaiveDate::from_ymd_opt(2026, 3, 25))
+                .with_recurrence(Some(Recurrence::parse("monthly").unwrap())),
+        );
+        let completed_on = NaiveDate::from_ymd_opt(2026, 3, 28).unwrap();
+        let next_id = collection.toggle_complete(id, completed_on).unwrap().unwrap();
+        assert_eq!(collection.get(next_id).unwrap().start, NaiveDate::from_ymd_opt(2026, 4, 24));
+    }
+
+    #[test]
+    fn test_status_markers_and_parse() {
+        for status in Status::ALL {
+            assert_eq!(Status::from_marker(status.marker()), Some(status));
+        }
+        assert_eq!(Status::from_marker('X'), Some(Status::Done));
+        assert_eq!(Status::from_marker('!'), None);
+        assert_eq!(Status::parse("In Progress").unwrap(), Status::InProgress);
+        assert_eq!(Status::parse("canceled").unwrap(), Status::Cancelled);
+        assert_eq!(Status::parse("?").unwrap(), Status::Waiting);
+        assert!(Status::parse("later").is_err());
+    }
+
+    #[test]
+    fn test_task_set_status() {
+        let mut task = Task::new("Test");
+        task.set_status(Status::InProgress);
+        assert!(!task.is_complete());
+        assert!(task.completed.is_none());
+
+        task.set_status(Status::Done);
+        assert!(task.is_complete() && task.completed.is_some());
+
+        task.set_status(Status::Cancelled);
+        assert!(!task.is_complete() && task.completed.is_none());
+
+        // Toggling a cancelled task marks it done
+        task.toggle_complete();
+        assert!(task.is_complete());
+    }
+
+    #[test]
+    fn test_cancelled_tasks_close_out() {
+        let mut collection = TaskCollection::new();
+        let parent = collection.add("Parent", None);
+        let done = collection.add("Done", None);
+        let dropped = collection.add("Dropped", None);
+        let open = collection.add("Open", None);
+        for child in [done, dropped, open] {
+            collection.set_parent(child, Some(parent)).unwrap();
+        }
+        collection.get_mut(done).unwrap().status = Status::Done;
+        collection.get_mut(dropped).unwrap().status = Status::Cancelled;
+        assert_eq!(collection.progress(parent), Some(50));
+
+        let blocked = collection.add_task(Task::new("Blocked").with_blocked_by(vec![dropped]));
+        assert!(!collection.is_blocked(blocked));
+
+        let past = Local::now() - chrono::Duration::days(2);
+        let late = collection.add_task(Task::new("Late").with_scheduled(Some(past)));
+        collection.get_mut(late).unwrap().status = Status::Cancelled;
+        assert!(!collection.get(late).unwrap().is_overdue());
+
+        assert_eq!(collection.remove_checked(), 3);
+    }
+
+    #[test]
+    fn test_cancel_recurring_skips_occurrence() {
+        let mut collection = TaskCollection::new();
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let due = Local.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let id = collection.add_task(
+            Task::new("Standup")
+                .with_scheduled(Some(due))
+                .with_recurrence(Some(Recurrence::parse("daily").unwrap())),
+        );
+
+        assert_eq!(collection.set_status(id, Status::InProgress, today).unwrap(), None);
+        let next_id = collection.set_status(id, Status::Cancelled, today).unwrap().unwrap();
+        let next = collection.get(next_id).unwrap();
+        assert_eq!(next.status, Status::Open);
+        assert_eq!(next.scheduled.unwrap().date_naive(), today.succ_opt().unwrap());
+
+        // Moving between closed states does not spawn again
+        assert_eq!(collection.set_status(id, Status::Done, today).unwrap(), None);
+    }
+
+    #[test]
+    fn test_move_tasks_to_archive_and_back() {
+        let mut collection = TaskCollection::new();
+        let parent = collection.add("Parent", None);
+        let child = collection.add("Child", None);
+        let other = collection.add("Other", None);
+        collection.set_parent(child, Some(parent)).unwrap();
+        collection.add_dependency(other, parent).unwrap();
+        collection.get_mut(parent).unwrap().status = Status::Done;
+        collection.get_mut(child).unwrap().status = Status::Cancelled;
+        let uid = collection.get(child).unwrap().uid.clone();
+
+        let mut archive = TaskCollection::new();
+        archive.insert(Task::new("Archived earlier").with_id(1));
+        let ids = collection.closed_ids(None);
+        assert_eq!(ids, vec![parent, child]);
+
+        let archived = collection.move_tasks(&mut archive, &ids);
+        assert_eq!(archived, vec![2, 3]);
+        assert_eq!(collection.len(), 1);
+        assert!(collection.get(other).unwrap().blocked_by.is_empty());
+        assert_eq!(archive.get(3).unwrap().parent, Some(2));
+        assert_eq!(archive.get(3).unwrap().uid, uid);
+
+        // Restored tasks lose links to tasks left behind
+        let restored = archive.move_tasks(&mut collection, &[3]);
+        assert_eq!(restored, vec![1]);
+        let task = collection.get(1).unwrap();
+        assert_eq!((task.parent, task.uid.as_str()), (None, uid.as_str()));
+    }
+
+    #[test]
+    fn test_closed_ids_before() {
+        let old = Local::now() - chrono::Duration::days(40);
+        let mut collection = TaskCollection::new();
+        collection.insert(Task::new("Old").with_id(1).with_complete(true).with_completed(Some(old)));
+        collection.insert(Task::new("New").with_id(2).with_complete(true).with_completed(Some(Local::now())));
+        collection.insert(Task::new("Dropped").with_id(3).with_status(Status::Cancelled).with_updated(Some(old)));
+        collection.insert(Task::new("Legacy").with_id(4).with_complete(true));
+        collection.insert(Task::new("Open").with_id(5).with_updated(Some(old)));
+
+        let cutoff = Local::now() - chrono::Duration::days(30);
+        assert_eq!(collection.closed_ids(Some(cutoff)), vec![1, 3]);
+        assert_eq!(collection.closed_ids(None), vec![1, 2, 3, 4]);
+    }
 }
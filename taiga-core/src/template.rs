@@ -0,0 +1,432 @@
+//! Task templates
+//!
+//! A template is a reusable bundle of task definitions. Dates are written as
+//! offsets from the day the template is applied ("+3d", "+1w 09:00"), and
+//! text may contain `{{variables}}` that are filled in when it is applied.
+//! Templates are plain data; reading and writing them is up to the caller.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+use crate::category;
+use crate::date::{floating_date, has_time_of_day, wall_time};
+use crate::error::{CoreError, Result};
+use crate::recurrence::Recurrence;
+use crate::task::{ChecklistItem, Priority, Task, TaskCollection};
+use crate::zone::Zone;
+
+// Placeholder in template text: {{client}}
+static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\{\{\s*(\w+)\s*\}\}").expect("Invalid variable regex pattern")
+});
+
+/// A named bundle of task definitions
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Template {
+    /// What the template is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Task definitions, parents before their subtasks
+    #[serde(default, rename = "task")]
+    pub tasks: Vec<TemplateTask>,
+}
+
+/// A task definition in a template
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct TemplateTask {
+    pub title: String,
+    /// Position of the parent task in the template, counting from 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,
+    /// Category path; subtasks always take their parent's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Priority level, 1 (urgent) to 4 (low)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Offset of the scheduled date, e.g. "+3d" or "+1w 09:00"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled: Option<String>,
+    /// Offset of the start date
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    /// Offset of the deadline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<String>,
+    /// Repeat rule, e.g. "weekly"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checklist: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    /// Custom fields, e.g. `fields = { customer = "{{client}}" }`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Template {
+    /// Names of the variables used anywhere in the template, sorted
+    pub fn variables(&self) -> BTreeSet<String> {
+        self.tasks
+            .iter()
+            .flat_map(TemplateTask::texts)
+            .flat_map(|text| VARIABLE_REGEX.captures_iter(text))
+            .map(|caps| caps[1].to_string())
+            .collect()
+    }
+
+    /// Build the tasks of the template, with dates counted from `base`
+    ///
+    /// Returns each task with the position of its parent in the list. Fails
+    /// without building anything if a variable has no value, an offset or
+    /// repeat rule does not parse, or a parent reference is out of order.
+    pub fn instantiate(
+        &self,
+        base: NaiveDate,
+        vars: &HashMap<String, String>,
+    ) -> Result<Vec<(Task, Option<usize>)>> {
+        let missing: Vec<String> = self
+            .variables()
+            .into_iter()
+            .filter(|name| !vars.contains_key(name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(CoreError::validation(
+                "template",
+                format!("No value for variable(s): {}", missing.join(", ")),
+            ));
+        }
+
+        self.tasks
+            .iter()
+            .enumerate()
+            .map(|(index, def)| {
+                let parent = match def.parent {
+                    Some(position) if position == 0 || position > index => {
+                        return Err(CoreError::validation(
+                            "template",
+                            format!(
+                                "Task {} ('{}') refers to parent {}, which must come before it",
+                                index + 1,
+                                def.title,
+                                position
+                            ),
+                        ));
+                    }
+                    parent => parent.map(|position| position - 1),
+                };
+                Ok((def.instantiate(base, vars)?, parent))
+            })
+            .collect()
+    }
+
+    /// Add the tasks of the template to a collection and return their IDs
+    pub fn apply(
+        &self,
+        collection: &mut TaskCollection,
+        base: NaiveDate,
+        vars: &HashMap<String, String>,
+    ) -> Result<Vec<u32>> {
+        let mut ids: Vec<u32> = Vec::new();
+        for (mut task, parent) in self.instantiate(base, vars)? {
+            let parent_id = parent.map(|index| ids[index]);
+            if let Some(parent_id) = parent_id {
+                task.category = collection.get_or_err(parent_id)?.category.clone();
+            }
+            let id = collection.add_task(task);
+            if parent_id.is_some() {
+                collection.set_parent(id, parent_id)?;
+            }
+            ids.push(id);
+        }
+        Ok(ids)
+    }
+
+    /// Capture existing tasks as a template, with dates relative to `base`
+    ///
+    /// Parent links are kept between tasks that are both captured; pass
+    /// parents before their subtasks.
+    pub fn capture(tasks: &[&Task], base: NaiveDate) -> Self {
+        let position = |id: u32| tasks.iter().position(|t| t.id == id).map(|i| i + 1);
+        let tasks = tasks
+            .iter()
+            .map(|task| {
+                let parent = task.parent.and_then(position);
+                TemplateTask {
+                    title: task.title.clone(),
+                    parent,
+                    // Subtasks take their parent's category when applied
+                    category: task.category.clone().filter(|_| parent.is_none()),
+                    tags: task.tags.clone(),
+                    priority: task.priority.map(|p| p.level()),
+                    scheduled: task.scheduled.map(|dt| datetime_offset(dt, base)),
+                    start: task.start.map(|date| format_offset(date.signed_duration_since(base).num_days(), None)),
+                    deadline: task.deadline.map(|dt| datetime_offset(dt, base)),
+                    repeat: task.recurrence.as_ref().map(|rule| rule.to_string()),
+                    notes: task.notes.clone(),
+                    checklist: task.checklist.iter().map(|item| item.text.clone()).collect(),
+                    links: task.links.clone(),
+                    fields: task.fields.clone(),
+                }
+            })
+            .collect();
+        Self { description: None, tasks }
+    }
+}
+
+impl TemplateTask {
+    /// All text fields that may contain variables
+    fn texts(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.title.as_str())
+            .chain(self.category.as_deref())
+            .chain(self.notes.as_deref())
+            .chain(self.tags.iter().map(String::as_str))
+            .chain(self.checklist.iter().map(String::as_str))
+            .chain(self.links.iter().map(String::as_str))
+            .chain(self.fields.values().map(String::as_str))
+    }
+
+    fn instantiate(&self, base: NaiveDate, vars: &HashMap<String, String>) -> Result<Task> {
+        let fill = |text: &str| -> String {
+            VARIABLE_REGEX
+                .replace_all(text, |caps: &regex::Captures| vars[&caps[1]].clone())
+                .into_owned()
+        };
+        let datetime = |offset: &Option<String>| -> Result<Option<DateTime<Local>>> {
+            offset
+                .as_deref()
+                .map(|offset| {
+                    let (date, time) = resolve_offset(offset, base)?;
+                    match time {
+                        Some(time) => Zone::current().from_naive(&date.and_time(time)),
+                        None => floating_date(date),
+                    }
+                    .ok_or_else(|| CoreError::validation("template", format!("Invalid local time for '{}'", offset)))
+                })
+                .transpose()
+        };
+
+        let priority = self
+            .priority
+            .map(|level| {
+                Priority::from_level(level).ok_or_else(|| {
+                    CoreError::validation("template", format!("Invalid priority {} (use 1-4)", level))
+                })
+            })
+            .transpose()?;
+        let start = self
+            .start
+            .as_deref()
+            .map(|offset| resolve_offset(offset, base).map(|(date, _)| date))
+            .transpose()?;
+        let recurrence = self.repeat.as_deref().map(Recurrence::parse).transpose()?;
+
+        let mut task = Task::new(fill(&self.title))
+            .with_category(self.category.as_deref().map(fill).and_then(|c| category::normalize(&c)))
+            .with_tags(self.tags.iter().map(|tag| fill(tag)).collect())
+            .with_priority(priority)
+            .with_scheduled(datetime(&self.scheduled)?)
+            .with_start(start)
+            .with_deadline(datetime(&self.deadline)?)
+            .with_recurrence(recurrence)
+            .with_checklist(self.checklist.iter().map(|text| ChecklistItem::new(fill(text))).collect())
+            .with_links(self.links.iter().map(|link| fill(link)).collect());
+        task.set_notes(self.notes.as_deref().map(fill));
+        for (key, value) in &self.fields {
+            task.set_field(key, Some(&fill(value)))?;
+        }
+        Ok(task)
+    }
+}
+
+/// Resolve an offset such as "+3d", "-1w" or "+2d 09:30" against a base date
+pub fn resolve_offset(input: &str, base: NaiveDate) -> Result<(NaiveDate, Option<NaiveTime>)> {
+    let invalid = || {
+        CoreError::parse(format!(
+            "Invalid date offset '{}'. Use forms like '+3d', '-1w' or '+2d 09:30'",
+            input
+        ))
+    };
+
+    let mut words = input.split_whitespace();
+    let amount = words.next().ok_or_else(invalid)?;
+    let time = words
+        .next()
+        .map(|time| NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| invalid()))
+        .transpose()?;
+    if words.next().is_some() {
+        return Err(invalid());
+    }
+
+    let (number, days_per_unit) = if let Some(number) = amount.strip_suffix('d') {
+        (number, 1)
+    } else if let Some(number) = amount.strip_suffix('w') {
+        (number, 7)
+    } else {
+        return Err(invalid());
+    };
+    let number: i64 = number.strip_prefix('+').unwrap_or(number).parse().map_err(|_| invalid())?;
+    let date = number
+        .checked_mul(days_per_unit)
+        .and_then(Duration::try_days)
+        .and_then(|offset| base.checked_add_signed(offset))
+        .ok_or_else(invalid)?;
+    Ok((date, time))
+}
+
+/// Write an offset in days, with a time of day if any: "+3d", "-2d 09:30"
+fn format_offset(days: i64, time: Option<NaiveTime>) -> String {
+    let sign = if days < 0 { "-" } else { "+" };
+    match time {
+        Some(time) => format!("{}{}d {}", sign, days.abs(), time.format("%H:%M")),
+        None => format!("{}{}d", sign, days.abs()),
+    }
+}
+
+fn datetime_offset(dt: DateTime<Local>, base: NaiveDate) -> String {
+    let wall_time = wall_time(&dt);
+    let days = wall_time.date().signed_duration_since(base).num_days();
+    format_offset(days, has_time_of_day(&dt).then(|| wall_time.time()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn base() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_resolve_offset() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        assert_eq!(resolve_offset("+3d", base()).unwrap(), (day(5), None));
+        assert_eq!(resolve_offset("1w", base()).unwrap(), (day(9), None));
+        assert_eq!(resolve_offset("-1d", base()).unwrap().0, day(1));
+        assert_eq!(
+            resolve_offset("+0d 09:30", base()).unwrap(),
+            (day(2), NaiveTime::from_hms_opt(9, 30, 0))
+        );
+        assert!(resolve_offset("+3", base()).is_err());
+        assert!(resolve_offset("soon", base()).is_err());
+        assert!(resolve_offset("+1d 9am", base()).is_err());
+        assert!(resolve_offset("+3ä", base()).is_err());
+        assert!(resolve_offset("ä", base()).is_err());
+    }
+
+    #[test]
+    fn test_resolve_offset_out_of_range() {
+        assert!(resolve_offset("+99999999999999d", base()).is_err());
+        assert!(resolve_offset("-99999999999999d", base()).is_err());
+        assert!(resolve_offset(&format!("+{}w", i64::MAX), base()).is_err());
+        assert!(resolve_offset("+999999999w", base()).is_err());
+    }
+
+    #[test]
+    fn test_apply_template() {
+        let template = Template {
+            description: Some("Client onboarding".to_string()),
+            tasks: vec![
+                TemplateTask {
+                    title: "Kick-off with {{client}}".to_string(),
+                    category: Some("Clients/{{client}}".to_string()),
+                    tags: vec!["onboarding".to_string()],
+                    priority: Some(2),
+                    scheduled: Some("+1d 10:00".to_string()),
+                    deadline: Some("+1w".to_string()),
+                    fields: BTreeMap::from([("customer".to_string(), "{{client}}".to_string())]),
+                    ..TemplateTask::default()
+                },
+                TemplateTask {
+                    title: "Send contract".to_string(),
+                    parent: Some(1),
+                    start: Some("+2d".to_string()),
+                    checklist: vec!["Signed by {{client}}".to_string()],
+                    ..TemplateTask::default()
+                },
+            ],
+        };
+        assert_eq!(template.variables().into_iter().collect::<Vec<_>>(), vec!["client"]);
+
+        let mut collection = TaskCollection::new();
+        let ids = template.apply(&mut collection, base(), &vars(&[("client", "Acme")])).unwrap();
+        assert_eq!(ids.len(), 2);
+
+        let kickoff = collection.get(ids[0]).unwrap();
+        assert_eq!(kickoff.title, "Kick-off with Acme");
+        assert_eq!(kickoff.category.as_deref(), Some("Clients/Acme"));
+        assert_eq!(kickoff.priority, Some(Priority::High));
+        assert_eq!(kickoff.scheduled, Some(Local.with_ymd_and_hms(2026, 3, 3, 10, 0, 0).unwrap()));
+        assert_eq!(kickoff.deadline, Some(Local.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap()));
+        assert_eq!(kickoff.field("customer"), Some("Acme"));
+
+        let contract = collection.get(ids[1]).unwrap();
+        assert_eq!(contract.parent, Some(ids[0]));
+        assert_eq!(contract.category.as_deref(), Some("Clients/Acme"));
+        assert_eq!(contract.start, NaiveDate::from_ymd_opt(2026, 3, 4));
+        assert_eq!(contract.checklist[0].text, "Signed by Acme");
+    }
+
+    #[test]
+    fn test_apply_template_errors() {
+        let template = Template {
+            description: None,
+            tasks: vec![TemplateTask {
+                title: "Call {{client}} about {{topic}}".to_string(),
+                ..TemplateTask::default()
+            }],
+        };
+        let mut collection = TaskCollection::new();
+        let err = template.apply(&mut collection, base(), &vars(&[("client", "Acme")])).unwrap_err();
+        assert!(err.to_string().contains("topic"));
+
+        let template = Template {
+            description: None,
+            tasks: vec![
+                TemplateTask { title: "Fine".to_string(), ..TemplateTask::default() },
+                TemplateTask { title: "Own parent".to_string(), parent: Some(2), ..TemplateTask::default() },
+            ],
+        };
+        assert!(template.apply(&mut collection, base(), &HashMap::new()).is_err());
+        // Nothing is added when the template is invalid
+        assert!(collection.is_empty());
+    }
+
+    #[test]
+    fn test_capture_roundtrip() {
+        let mut collection = TaskCollection::new();
+        let parent = collection.add_task(
+            Task::new("Release")
+                .with_scheduled(Some(Local.with_ymd_and_hms(2026, 3, 6, 15, 0, 0).unwrap()))
+                .with_tags(vec!["ship".to_string()]),
+        );
+        let child = collection.add_task(Task::new("Changelog").with_start(NaiveDate::from_ymd_opt(2026, 3, 4)));
+        collection.set_parent(child, Some(parent)).unwrap();
+
+        let tasks = [collection.get(parent).unwrap(), collection.get(child).unwrap()];
+        let template = Template::capture(&tasks, base());
+        assert_eq!(template.tasks[0].scheduled.as_deref(), Some("+4d 15:00"));
+        assert_eq!(template.tasks[1].start.as_deref(), Some("+2d"));
+        assert_eq!(template.tasks[1].parent, Some(1));
+        assert_eq!(template.tasks[1].category, None);
+
+        let built = template.instantiate(base(), &HashMap::new()).unwrap();
+        assert_eq!(built[0].0.scheduled, tasks[0].scheduled);
+        assert_eq!(built[0].0.tags, tasks[0].tags);
+        assert_eq!(built[1].0.start, tasks[1].start);
+        assert_eq!(built[1].1, Some(0));
+    }
+}
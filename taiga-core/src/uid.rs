@@ -0,0 +1,128 @@
+//! Stable unique task identifiers
+//!
+//! Task numbers are short but can change on reindex or be recycled once a
+//! task is removed. Every task therefore also carries a UID: eight lowercase
+//! Crockford base32 characters (no i, l, o or u), e.g. `k3x9a2mf`, which never
+//! changes once assigned. UIDs always contain a letter, so they cannot be
+//! mistaken for task numbers.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{CoreError, Result};
+
+/// Characters used in UIDs
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Length of a UID
+pub const UID_LEN: usize = 8;
+
+/// Shortest UID prefix accepted when looking up a task
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// Generate a new random UID
+pub fn generate() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    loop {
+        // RandomState is randomly keyed per process, mixing in the clock and
+        // a counter keeps UIDs generated in quick succession apart
+        let mut hasher = RandomState::new().build_hasher();
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        hasher.write_u128(nanos);
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+
+        let uid = encode(hasher.finish());
+        if !is_numeric(&uid) {
+            return uid;
+        }
+    }
+}
+
+/// Derive a UID deterministically from a seed
+///
+/// Used for tasks stored before UIDs existed, so they get the same UID on
+/// every load until the file is saved with it.
+pub fn derive(seed: &str) -> String {
+    // FNV-1a, which unlike the std hashers is guaranteed to be stable
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in seed.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    let mut uid = encode(hash);
+    while is_numeric(&uid) {
+        hash = hash.rotate_left(5);
+        uid = encode(hash);
+    }
+    uid
+}
+
+/// Check whether a string looks like a (possibly shortened) UID
+pub fn is_valid_prefix(input: &str) -> bool {
+    !input.is_empty()
+        && input.len() <= UID_LEN
+        && !is_numeric(input)
+        && input.bytes().all(|b| ALPHABET.contains(&b.to_ascii_lowercase()))
+}
+
+/// Normalize user input to a UID prefix
+///
+/// Lowercases the input and validates that it only uses UID characters.
+pub fn parse_prefix(input: &str) -> Result<String> {
+    let input = input.trim();
+    if !is_valid_prefix(input) {
+        return Err(CoreError::validation(
+            "id",
+            format!("'{}' is neither a task number nor a task UID", input),
+        ));
+    }
+    Ok(input.to_ascii_lowercase())
+}
+
+fn encode(mut value: u64) -> String {
+    let mut uid = String::with_capacity(UID_LEN);
+    for _ in 0..UID_LEN {
+        uid.push(ALPHABET[(value & 0x1f) as usize] as char);
+        value >>= 5;
+    }
+    uid
+}
+
+fn is_numeric(input: &str) -> bool {
+    input.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_format() {
+        let uid = generate();
+        assert_eq!(uid.len(), UID_LEN);
+        assert!(is_valid_prefix(&uid));
+        assert_ne!(generate(), generate());
+    }
+
+    #[test]
+    fn test_derive_is_stable() {
+        assert_eq!(derive("1 Buy milk"), derive("1 Buy milk"));
+        assert_ne!(derive("1 Buy milk"), derive("2 Buy milk"));
+        assert!(is_valid_prefix(&derive("")));
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        assert_eq!(parse_prefix("K3X9").unwrap(), "k3x9");
+        assert!(parse_prefix("1234").is_err());
+        assert!(parse_prefix("hello").is_err()); // 'l' and 'o' are not used
+        assert!(parse_prefix("k3x9a2mfz").is_err());
+    }
+}
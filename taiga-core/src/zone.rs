@@ -0,0 +1,209 @@
+//! Time zones for task dates
+//!
+//! Task dates come in two kinds:
+//! - Floating dates have no time of day ("2026-01-25"). They mean the same
+//!   calendar day wherever the task list is opened.
+//! - Zoned date-times have a time of day and are stored with the zone they
+//!   were set in ("2026-01-25 15:00 Europe/Berlin"), so they stay the same
+//!   moment on a laptop that travels or for a team member elsewhere.
+//!
+//! The installed [`Zone`] (the local zone until replaced) is where "today"
+//! is, where overdue tasks are judged and in which times are shown. Times
+//! stored without a zone, by versions before zones, are read in it too.
+//! Finding the name of the system's zone is up to the application, which
+//! installs it as a named zone so times are stored under that name.
+//! While a task list is open, a floating date is held as midnight in the
+//! installed zone, so a zoned time falling exactly on that midnight reads
+//! as the whole day.
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::sync::{LazyLock, RwLock};
+
+use crate::error::{CoreError, Result};
+
+/// Zone installed for evaluating and showing dates
+static CURRENT: LazyLock<RwLock<Zone>> = LazyLock::new(|| RwLock::new(Zone::Local));
+
+/// A time zone: the system's, a named one or a fixed UTC offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Zone {
+    /// The system's local zone
+    #[default]
+    Local,
+    /// A zone from the IANA database, e.g. "Europe/Berlin"
+    Named(Tz),
+    /// A fixed offset from UTC, e.g. "+05:30"
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    /// Parse "local", an IANA zone name ("Europe/Berlin", "UTC", case
+    /// insensitive) or a UTC offset ("+02:00", "-0530", "UTC+2")
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        if let Some(offset) = parse_offset(input) {
+            return Ok(Self::Fixed(offset));
+        }
+        input
+            .parse::<Tz>()
+            .ok()
+            .or_else(|| {
+                chrono_tz::TZ_VARIANTS
+                    .iter()
+                    .copied()
+                    .find(|tz| tz.name().eq_ignore_ascii_case(input))
+            })
+            .map(Self::Named)
+            .ok_or_else(|| {
+                CoreError::validation(
+                    "time_zone",
+                    format!(
+                        "Unknown time zone '{}' (use 'local', a name such as 'Europe/Berlin' or an offset such as '+02:00')",
+                        input
+                    ),
+                )
+            })
+    }
+
+    /// Make this the zone dates are evaluated and shown in
+    pub fn install(self) {
+        *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = self;
+    }
+
+    /// The zone dates are evaluated and shown in
+    pub fn current() -> Self {
+        *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Today's date in this zone
+    pub fn today(&self) -> NaiveDate {
+        self.to_naive(&Local::now()).date()
+    }
+
+    /// The wall-clock time of a moment in this zone
+    pub fn to_naive(&self, dt: &DateTime<Local>) -> NaiveDateTime {
+        match self {
+            Self::Local => dt.naive_local(),
+            Self::Named(tz) => dt.with_timezone(tz).naive_local(),
+            Self::Fixed(offset) => dt.with_timezone(offset).naive_local(),
+        }
+    }
+
+    /// The moment a wall-clock time in this zone stands for (the earlier one
+    /// when clocks go back)
+    pub fn from_naive(&self, naive: &NaiveDateTime) -> Option<DateTime<Local>> {
+        match self {
+            Self::Local => Local.from_local_datetime(naive).earliest(),
+            Self::Named(tz) => tz.from_local_datetime(naive).earliest().map(|dt| dt.with_timezone(&Local)),
+            Self::Fixed(offset) => offset.from_local_datetime(naive).earliest().map(|dt| dt.with_timezone(&Local)),
+        }
+    }
+
+    /// The day of a moment in this zone
+    pub fn date_of(&self, dt: &DateTime<Local>) -> NaiveDate {
+        self.to_naive(dt).date()
+    }
+
+    /// A zone that can be written to a file for a moment: the local zone by
+    /// its offset at that moment, other zones as they are
+    pub fn resolve(&self, at: &DateTime<Local>) -> Self {
+        match self {
+            Self::Local => Self::Fixed(at.offset().fix()),
+            zone => *zone,
+        }
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Local => write!(f, "local"),
+            Self::Named(tz) => write!(f, "{}", tz.name()),
+            Self::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+impl Serialize for Zone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Zone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::parse(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse "+02:00", "-0530", "+2" or the same after "UTC"/"GMT"
+fn parse_offset(input: &str) -> Option<FixedOffset> {
+    let upper = input.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    let (sign, digits) = match rest.chars().next()? {
+        '+' => (1, &rest[1..]),
+        '-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+    // Hours and minutes are split by byte position below
+    if !digits.is_ascii() {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h, m),
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_parse_zone() {
+        assert_eq!(Zone::parse("local").unwrap(), Zone::Local);
+        assert_eq!(Zone::parse("Europe/Berlin").unwrap(), Zone::Named(Tz::Europe__Berlin));
+        assert_eq!(Zone::parse("america/new_york").unwrap(), Zone::Named(Tz::America__New_York));
+        assert_eq!(Zone::parse("UTC").unwrap().to_string(), "UTC");
+        assert_eq!(Zone::parse("+05:30").unwrap().to_string(), "+05:30");
+        assert_eq!(Zone::parse("UTC-8").unwrap().to_string(), "-08:00");
+        assert_eq!(Zone::parse("-0330").unwrap().to_string(), "-03:30");
+        assert!(Zone::parse("Mars/Olympus").is_err());
+        assert!(Zone::parse("+25:00").is_err());
+        assert!(Zone::parse("+é1").is_err());
+        assert!(Zone::parse("UTC+é1").is_err());
+        assert!(Zone::parse("-1é30").is_err());
+    }
+
+    #[test]
+    fn test_zone_conversions() {
+        let berlin = Zone::parse("Europe/Berlin").unwrap();
+        let tokyo = Zone::parse("Asia/Tokyo").unwrap();
+        let naive = NaiveDate::from_ymd_opt(2026, 1, 25).unwrap().and_hms_opt(15, 0, 0).unwrap();
+
+        let moment = berlin.from_naive(&naive).unwrap();
+        assert_eq!(moment.with_timezone(&Utc).to_rfc3339(), "2026-01-25T14:00:00+00:00");
+        assert_eq!(tokyo.to_naive(&moment).to_string(), "2026-01-25 23:00:00");
+        assert_eq!(berlin.resolve(&moment), berlin);
+
+        assert_eq!(tokyo.date_of(&moment), naive.date());
+        assert_eq!(Zone::Local.resolve(&moment), Zone::Fixed(moment.offset().fix()));
+    }
+}
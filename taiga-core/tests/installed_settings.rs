@@ -0,0 +1,99 @@
+//! Dates with a non-default working-day calendar, zone and locale installed
+//!
+//! These settings are process-wide, so the tests live in their own binary,
+//! take turns through a lock and put the defaults back when done.
+
+use chrono::{Datelike, Days, Local, NaiveDate, TimeZone, Utc, Weekday};
+use std::sync::{Mutex, MutexGuard};
+use taiga_core::date::{floating_date, format_date_human, parse_date, parse_datetime};
+use taiga_core::{DeadlineState, Locale, Query, Task, WorkCalendar, Zone};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Holds the lock and restores the default settings when dropped
+struct Installed {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        WorkCalendar::default().install();
+        Zone::Local.install();
+        Locale::EN.install();
+    }
+}
+
+fn install(calendar: WorkCalendar, zone: &str, locale: Locale) -> Installed {
+    let guard = Installed {
+        _lock: LOCK.lock().unwrap_or_else(|e| e.into_inner()),
+    };
+    calendar.install();
+    Zone::parse(zone).unwrap().install();
+    locale.install();
+    guard
+}
+
+/// The first day after `date` falling on `weekday`
+fn next_weekday(date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    date.iter_days()
+        .skip(1)
+        .find(|day| day.weekday() == weekday)
+        .unwrap()
+}
+
+#[test]
+fn test_parse_date_uses_installed_calendar() {
+    let _installed = install(
+        WorkCalendar::from_config("sat", None).unwrap(),
+        "local",
+        Locale::EN,
+    );
+    let saturday = next_weekday(Zone::current().today(), Weekday::Sat);
+    assert_eq!(parse_date("next workday").unwrap(), saturday);
+
+    // A holiday on that Saturday moves the next workday a week on
+    let holidays = format!("{} Company day", saturday.format("%Y-%m-%d"));
+    WorkCalendar::from_config("sat", Some(&holidays)).unwrap().install();
+    assert_eq!(
+        parse_date("next workday").unwrap(),
+        saturday.checked_add_days(Days::new(7)).unwrap()
+    );
+}
+
+#[test]
+fn test_dates_use_installed_zone() {
+    let _installed = install(WorkCalendar::default(), "+05:00", Locale::EN);
+
+    // Times of day are read on the installed zone's clock
+    let dt = parse_datetime("2026-03-01 09:00").unwrap();
+    assert_eq!(dt, Utc.with_ymd_and_hms(2026, 3, 1, 4, 0, 0).unwrap());
+
+    // Queries compare dates on the installed zone's calendar
+    let late_evening_utc = Utc.with_ymd_and_hms(2026, 2, 28, 23, 30, 0).unwrap().with_timezone(&Local);
+    let task = Task::new("Call").with_scheduled(Some(late_evening_utc));
+    let query = Query::parse("due:2026-03-01").unwrap();
+    assert!(query.matches(&task));
+    Zone::parse("-05:00").unwrap().install();
+    assert!(!query.matches(&task));
+
+    // A whole-day deadline ends with the day in the installed zone
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 20, 0, 0).unwrap().with_timezone(&Local);
+    let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+    let task = Task::new("File taxes").with_deadline(floating_date(day));
+    assert_eq!(task.deadline_state_at(now, 0), Some(DeadlineState::Approaching));
+    Zone::parse("+05:00").unwrap().install();
+    let task = Task::new("File taxes").with_deadline(floating_date(day));
+    assert_eq!(task.deadline_state_at(now, 0), Some(DeadlineState::Passed));
+}
+
+#[test]
+fn test_dates_use_installed_locale() {
+    let _installed = install(WorkCalendar::default(), "local", Locale::DE);
+    let today = Zone::current().today();
+    let tomorrow = today.succ_opt().unwrap();
+
+    assert_eq!(parse_date("morgen").unwrap(), tomorrow);
+    assert_eq!(parse_date("25.12.2026").unwrap(), NaiveDate::from_ymd_opt(2026, 12, 25).unwrap());
+    assert_eq!(format_date_human(tomorrow, true), "Morgen");
+    assert_eq!(format_date_human(today, true), "Heute");
+}